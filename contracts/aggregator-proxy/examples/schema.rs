@@ -3,7 +3,10 @@ use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
-use aggregator_proxy::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use aggregator_proxy::msg::{
+    ConfirmationTimeResponse, ExecuteMsg, GuardedRoundDataResponse, InstantiateMsg,
+    PhaseInfoResponse, PhasesResponse, ProposalComparisonResponse, QueryMsg,
+};
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -14,4 +17,9 @@ fn main() {
     export_schema(&schema_for!(InstantiateMsg), &out_dir);
     export_schema(&schema_for!(ExecuteMsg), &out_dir);
     export_schema(&schema_for!(QueryMsg), &out_dir);
+    export_schema(&schema_for!(ConfirmationTimeResponse), &out_dir);
+    export_schema(&schema_for!(GuardedRoundDataResponse), &out_dir);
+    export_schema(&schema_for!(PhaseInfoResponse), &out_dir);
+    export_schema(&schema_for!(ProposalComparisonResponse), &out_dir);
+    export_schema(&schema_for!(PhasesResponse), &out_dir);
 }
@@ -1,23 +1,43 @@
-use std::convert::TryInto;
+use std::convert::{TryFrom, TryInto};
 
-use chainlink_aggregator::{LatestAnswerResponse, QueryMsg::*, RoundDataResponse};
+use chainlink_aggregator::{
+    compose_round_id, parse_round_id, LatestAnswerResponse, QueryMsg::*, RoundDataResponse,
+};
 use cosmwasm_std::{
     to_binary, Addr, Binary, Deps, DepsMut, Env, Event, MessageInfo, Order, Response, StdError,
     StdResult, Storage, Uint128,
 };
+use cw_storage_plus::Bound;
 use owned::contract::{
-    execute_accept_ownership, execute_transfer_ownership, get_owner,
-    instantiate as owned_instantiate,
+    execute_accept_ownership, execute_cancel_ownership_transfer, execute_renounce_ownership,
+    execute_transfer_ownership, get_owner, get_ownership_state, instantiate as owned_instantiate,
+};
+use owned::roles::{
+    execute_grant_role, execute_renounce_role, execute_revoke_role, has_role, query_role_members,
+    validate_role, CONFIG_ADMIN,
 };
 use serde::de::DeserializeOwned;
 
 use crate::{
     error::ContractError,
-    msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, PhaseAggregators, QueryMsg},
-    state::{Phase, CURRENT_PHASE, PHASE_AGGREGATORS, PROPOSED_AGGREGATOR},
+    msg::{
+        ConfirmationTimeResponse, DataSource, ExecuteMsg, GuardMsg, GuardedRoundDataResponse,
+        InstantiateMsg, MigrateMsg, PhaseAggregators, PhaseInfoResponse, PhasesResponse,
+        ProposalComparisonResponse, QueryMsg, RoundComparison,
+    },
+    state::{
+        DelayDecrease, Guard, Phase, PhaseInfo, Policy, CONFIRMATION_DELAY, CURRENT_PHASE,
+        EARLIEST_CONFIRMATION, GUARD, MAX_PROPOSAL_DIVERGENCE, PENDING_DELAY_DECREASE,
+        PHASE_AGGREGATORS, PHASE_INFO, PROPOSED_AGGREGATOR,
+    },
 };
 
-static PHASE_OFFSET: Uint128 = Uint128::new(64);
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+const MAX_COMPARISON_ROUNDS: u32 = 30;
+static THRESHOLD_MULTIPLIER: u128 = 100000;
+/// Phase ID reserved for round IDs of the guard's backup aggregator
+pub const BACKUP_PHASE_ID: u16 = u16::MAX;
 
 pub fn instantiate(
     mut deps: DepsMut,
@@ -25,11 +45,24 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    let started_at = env.block.time.seconds();
     owned_instantiate(deps.branch(), env, info, owned::msg::InstantiateMsg {})?;
 
     let aggregator_addr = deps.api.addr_validate(&msg.aggregator)?;
+    CONFIRMATION_DELAY.save(deps.storage, &msg.confirmation_delay.unwrap_or_default())?;
 
+    let first_round_id = latest_phase_round_id(deps.as_ref(), &aggregator_addr, 1);
     PHASE_AGGREGATORS.save(deps.storage, 1.into(), &aggregator_addr)?;
+    PHASE_INFO.save(
+        deps.storage,
+        1.into(),
+        &PhaseInfo {
+            started_at,
+            first_round_id,
+            ended_at: None,
+            last_round_id: None,
+        },
+    )?;
     CURRENT_PHASE.save(
         deps.storage,
         &Phase {
@@ -54,36 +87,184 @@ pub fn execute(
         ExecuteMsg::ConfirmAggregator { aggregator } => {
             execute_confirm_aggregator(deps, env, info, aggregator)
         }
-        ExecuteMsg::TransferOwnership { to } => {
-            execute_transfer_ownership(deps, env, info, to.to_string()).map_err(ContractError::from)
+        ExecuteMsg::CancelProposedAggregator {} => {
+            execute_cancel_proposed_aggregator(deps, env, info)
+        }
+        ExecuteMsg::SetConfirmationDelay { delay } => {
+            execute_set_confirmation_delay(deps, env, info, delay)
+        }
+        ExecuteMsg::SetMaxProposalDivergence { divergence } => {
+            execute_set_max_proposal_divergence(deps, env, info, divergence)
+        }
+        ExecuteMsg::SetGuard { guard } => execute_set_guard(deps, env, info, guard),
+        ExecuteMsg::TransferOwnership { to, expires_at } => {
+            execute_transfer_ownership(deps, env, info, to.to_string(), expires_at)
+                .map_err(ContractError::from)
         }
         ExecuteMsg::AcceptOwnership {} => {
             execute_accept_ownership(deps, env, info).map_err(ContractError::from)
         }
+        ExecuteMsg::CancelOwnershipTransfer {} => {
+            execute_cancel_ownership_transfer(deps, env, info).map_err(ContractError::from)
+        }
+        ExecuteMsg::RenounceOwnership {} => {
+            execute_renounce_ownership(deps, env, info).map_err(ContractError::from)
+        }
+        ExecuteMsg::GrantRole { role, account } => {
+            execute_grant_role(deps, env, info, role, account).map_err(ContractError::from)
+        }
+        ExecuteMsg::RevokeRole { role, account } => {
+            execute_revoke_role(deps, env, info, role, account).map_err(ContractError::from)
+        }
+        ExecuteMsg::RenounceRole { role } => {
+            execute_renounce_role(deps, env, info, role).map_err(ContractError::from)
+        }
     }
 }
 
 pub fn execute_propose_aggregator(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     aggregator: String,
 ) -> Result<Response, ContractError> {
-    validate_ownership(deps.as_ref(), &info)?;
+    validate_role(deps.as_ref(), &info, CONFIG_ADMIN)?;
 
     let aggregator_addr = deps.api.addr_validate(&aggregator)?;
+    let delay = confirmation_delay(deps.storage, env.block.time.seconds())?;
+    let earliest_confirmation = env.block.time.plus_seconds(delay).seconds();
     PROPOSED_AGGREGATOR.save(deps.storage, &aggregator_addr)?;
+    EARLIEST_CONFIRMATION.save(deps.storage, &earliest_confirmation)?;
 
-    Ok(Response::default())
+    Ok(Response::new().add_event(
+        Event::new("propose_aggregator")
+            .add_attribute("aggregator", aggregator_addr)
+            .add_attribute("earliest_confirmation", earliest_confirmation.to_string()),
+    ))
 }
 
-pub fn execute_confirm_aggregator(
+pub fn execute_cancel_proposed_aggregator(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    validate_role(deps.as_ref(), &info, CONFIG_ADMIN)?;
+
+    let proposed = PROPOSED_AGGREGATOR
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoProposedAggregator {})?;
+    PROPOSED_AGGREGATOR.remove(deps.storage);
+    EARLIEST_CONFIRMATION.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_event(Event::new("cancel_proposed_aggregator").add_attribute("aggregator", proposed)))
+}
+
+pub fn execute_set_confirmation_delay(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    delay: u64,
+) -> Result<Response, ContractError> {
+    validate_role(deps.as_ref(), &info, CONFIG_ADMIN)?;
+
+    let now = env.block.time.seconds();
+    let current = confirmation_delay(deps.storage, now)?;
+    CONFIRMATION_DELAY.save(deps.storage, &delay.max(current))?;
+    // a shorter delay would let proposals made right away be confirmed before anyone
+    // watching the previous delay expects it
+    let effective_at = if delay < current {
+        let effective_at = now.saturating_add(current);
+        PENDING_DELAY_DECREASE.save(
+            deps.storage,
+            &DelayDecrease {
+                delay,
+                effective_at,
+            },
+        )?;
+        effective_at
+    } else {
+        PENDING_DELAY_DECREASE.remove(deps.storage);
+        now
+    };
+
+    Ok(Response::new().add_event(
+        Event::new("set_confirmation_delay")
+            .add_attribute("delay", delay.to_string())
+            .add_attribute("effective_at", effective_at.to_string()),
+    ))
+}
+
+pub fn execute_set_max_proposal_divergence(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    divergence: Option<u32>,
+) -> Result<Response, ContractError> {
+    validate_role(deps.as_ref(), &info, CONFIG_ADMIN)?;
+
+    let mut event = Event::new("set_max_proposal_divergence");
+    match divergence {
+        Some(divergence) => {
+            MAX_PROPOSAL_DIVERGENCE.save(deps.storage, &divergence)?;
+            event = event.add_attribute("divergence", divergence.to_string());
+        }
+        None => MAX_PROPOSAL_DIVERGENCE.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_event(event))
+}
+
+pub fn execute_set_guard(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
+    guard: Option<GuardMsg>,
+) -> Result<Response, ContractError> {
+    validate_role(deps.as_ref(), &info, CONFIG_ADMIN)?;
+
+    let guard = match guard {
+        Some(guard) => guard,
+        None => {
+            GUARD.remove(deps.storage);
+            return Ok(Response::new()
+                .add_event(Event::new("set_guard").add_attribute("enabled", false.to_string())));
+        }
+    };
+    let validate =
+        |addr: Option<String>| addr.map(|addr| deps.api.addr_validate(&addr)).transpose();
+    let guard = Guard {
+        flags: validate(guard.flags)?,
+        backup: validate(guard.backup)?,
+        flagged_policy: guard.flagged_policy,
+        stale_policy: guard.stale_policy,
+        max_staleness: guard.max_staleness,
+    };
+    GUARD.save(deps.storage, &guard)?;
+
+    let mut event = Event::new("set_guard")
+        .add_attribute("enabled", true.to_string())
+        .add_attribute("flagged_policy", guard.flagged_policy.to_string())
+        .add_attribute("stale_policy", guard.stale_policy.to_string());
+    if let Some(flags) = guard.flags {
+        event = event.add_attribute("flags", flags);
+    }
+    if let Some(backup) = guard.backup {
+        event = event.add_attribute("backup", backup);
+    }
+    if let Some(max_staleness) = guard.max_staleness {
+        event = event.add_attribute("max_staleness", max_staleness.to_string());
+    }
+    Ok(Response::new().add_event(event))
+}
+
+pub fn execute_confirm_aggregator(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
     aggregator: String,
 ) -> Result<Response, ContractError> {
-    validate_ownership(deps.as_ref(), &info)?;
+    validate_role(deps.as_ref(), &info, CONFIG_ADMIN)?;
 
     let mut response = Response::new();
 
@@ -95,12 +276,57 @@ pub fn execute_confirm_aggregator(
     if proposed != aggregator_addr {
         return Err(ContractError::InvalidProposedAggregator {});
     }
+    let earliest_confirmation = EARLIEST_CONFIRMATION
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    if env.block.time.seconds() < earliest_confirmation {
+        return Err(ContractError::ConfirmationDelayNotPassed {});
+    }
+    if let Some(max_divergence) = MAX_PROPOSAL_DIVERGENCE.may_load(deps.storage)? {
+        let comparison = get_proposal_comparison(deps.as_ref(), env.clone(), 1)?;
+        let divergence = comparison
+            .rounds
+            .first()
+            .and_then(|round| round.relative_divergence)
+            .ok_or(ContractError::ProposalDivergenceUnavailable {})?;
+        if divergence > Uint128::from(max_divergence) {
+            return Err(ContractError::ProposalDivergenceTooHigh {});
+        }
+    }
 
     PROPOSED_AGGREGATOR.remove(deps.storage);
+    EARLIEST_CONFIRMATION.remove(deps.storage);
 
     let phase = CURRENT_PHASE.load(deps.storage)?;
     let new_id = phase.id + 1;
+    let now = env.block.time.seconds();
+
+    // phases confirmed before boundaries were recorded have no info
+    if let Some(info) = PHASE_INFO.may_load(deps.storage, phase.id.into())? {
+        let last_round_id = latest_phase_round_id(deps.as_ref(), &phase.aggregator_addr, phase.id);
+        PHASE_INFO.save(
+            deps.storage,
+            phase.id.into(),
+            &PhaseInfo {
+                ended_at: Some(now),
+                last_round_id,
+                ..info
+            },
+        )?;
+    }
+
+    let first_round_id = latest_phase_round_id(deps.as_ref(), &aggregator_addr, new_id);
     PHASE_AGGREGATORS.save(deps.storage, new_id.into(), &aggregator_addr)?;
+    PHASE_INFO.save(
+        deps.storage,
+        new_id.into(),
+        &PhaseInfo {
+            started_at: now,
+            first_round_id,
+            ended_at: None,
+            last_round_id: None,
+        },
+    )?;
     CURRENT_PHASE.save(
         deps.storage,
         &Phase {
@@ -126,12 +352,40 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             to_binary(&get_proposed_latest_round_data(deps, env)?)
         }
         QueryMsg::GetProposedAggregator {} => to_binary(&get_proposed_aggregator(deps, env)?),
+        QueryMsg::GetEarliestConfirmation {} => to_binary(&get_earliest_confirmation(deps, env)?),
+        QueryMsg::GetConfirmationDelay {} => to_binary(&get_confirmation_delay(deps, env)?),
+        QueryMsg::GetProposalComparison { rounds } => {
+            to_binary(&get_proposal_comparison(deps, env, rounds)?)
+        }
+        QueryMsg::GetMaxProposalDivergence {} => {
+            to_binary(&MAX_PROPOSAL_DIVERGENCE.may_load(deps.storage)?)
+        }
         QueryMsg::GetAggregator {} => to_binary(&get_aggregator(deps, env)?),
         QueryMsg::GetPhaseId {} => to_binary(&get_phase_id(deps, env)?),
+        QueryMsg::GetPhaseRoundData { phase_id, round_id } => {
+            to_binary(&get_phase_round_data(deps, env, phase_id, round_id)?)
+        }
+        QueryMsg::GetPhaseInfo { phase_id } => to_binary(&get_phase_info(deps, env, phase_id)?),
+        QueryMsg::GetPhases { start_after, limit } => {
+            to_binary(&get_phases(deps, env, start_after, limit)?)
+        }
         QueryMsg::GetOwner {} => to_binary(&get_owner(deps)?),
+        QueryMsg::GetOwnershipState {} => to_binary(&get_ownership_state(deps)?),
+        QueryMsg::HasRole { role, account } => {
+            to_binary(&has_role(deps, &role, &deps.api.addr_validate(&account)?)?)
+        }
+        QueryMsg::GetRoleMembers {
+            role,
+            start_after,
+            limit,
+        } => to_binary(&query_role_members(deps, role, start_after, limit)?),
         QueryMsg::AggregatorQuery(GetRoundData { round_id }) => {
             to_binary(&get_round_data(deps, env, round_id)?)
         }
+        QueryMsg::GetGuard {} => to_binary(&GUARD.may_load(deps.storage)?),
+        QueryMsg::GetGuardedLatestRoundData {} => {
+            to_binary(&get_guarded_latest_round_data(deps, env)?)
+        }
         QueryMsg::AggregatorQuery(GetLatestRoundData {}) => {
             to_binary(&get_latest_round_data(deps, env)?)
         }
@@ -154,8 +408,12 @@ pub fn get_description(deps: Deps, _env: Env) -> StdResult<String> {
     query_current(deps, GetDescription {})
 }
 
-pub fn get_latest_answer(deps: Deps, _env: Env) -> StdResult<LatestAnswerResponse> {
-    query_current(deps, GetLatestAnswer {})
+pub fn get_latest_answer(deps: Deps, env: Env) -> StdResult<LatestAnswerResponse> {
+    if GUARD.may_load(deps.storage)?.is_none() {
+        return query_current(deps, GetLatestAnswer {});
+    }
+    let res = get_guarded_latest_round_data(deps, env)?;
+    Ok(LatestAnswerResponse(res.round_data.answer))
 }
 
 pub fn get_phase_aggregators(deps: Deps, _env: Env) -> StdResult<PhaseAggregators> {
@@ -172,19 +430,217 @@ pub fn get_phase_aggregators(deps: Deps, _env: Env) -> StdResult<PhaseAggregator
         .collect()
 }
 
-pub fn get_round_data(deps: Deps, _env: Env, round_id: u32) -> StdResult<RoundDataResponse> {
-    let phase_id: u16 = (round_id >> PHASE_OFFSET.u128())
-        .try_into()
-        // TODO improve error
-        .map_err(|_| StdError::generic_err("Failed parse"))?;
+/// Queries round data by a proxied round ID, see [`chainlink_aggregator::PHASE_OFFSET`].
+///
+/// Phases start at 1, so a round ID without phase bits is looked up in the current
+/// phase. This keeps callers passing plain aggregator round IDs working. Round IDs with
+/// [`BACKUP_PHASE_ID`] are looked up in the guard's backup aggregator.
+pub fn get_round_data(deps: Deps, env: Env, round_id: u64) -> StdResult<RoundDataResponse> {
+    let (phase_id, aggregator_round_id) = parse_round_id(round_id)
+        .ok_or_else(|| StdError::generic_err(ContractError::InvalidRoundId {}.to_string()))?;
+    let phase_id = match phase_id {
+        0 => CURRENT_PHASE.load(deps.storage)?.id,
+        BACKUP_PHASE_ID => {
+            let backup = GUARD
+                .may_load(deps.storage)?
+                .and_then(|guard| guard.backup)
+                .ok_or_else(|| {
+                    StdError::generic_err(ContractError::NoBackupAggregator {}.to_string())
+                })?;
+            let res: RoundDataResponse = deps.querier.query_wasm_smart(
+                backup,
+                &GetRoundData {
+                    round_id: aggregator_round_id.into(),
+                }
+                .wrap(),
+            )?;
+            return add_phase_ids(res, BACKUP_PHASE_ID);
+        }
+        id => id,
+    };
+    get_phase_round_data(deps, env, phase_id, aggregator_round_id)
+}
+
+pub fn get_phase_round_data(
+    deps: Deps,
+    _env: Env,
+    phase_id: u16,
+    aggregator_round_id: u32,
+) -> StdResult<RoundDataResponse> {
     let aggregator = PHASE_AGGREGATORS.load(deps.storage, phase_id.into())?;
-    let res: RoundDataResponse = deps
-        .querier
-        .query_wasm_smart(aggregator, &GetRoundData { round_id }.wrap())?;
-    Ok(add_phase_ids(res, phase_id))
+    let res: RoundDataResponse = deps.querier.query_wasm_smart(
+        aggregator,
+        &GetRoundData {
+            round_id: aggregator_round_id.into(),
+        }
+        .wrap(),
+    )?;
+    add_phase_ids(res, phase_id)
+}
+
+pub fn get_phase_info(deps: Deps, _env: Env, phase_id: u16) -> StdResult<PhaseInfoResponse> {
+    Ok(PhaseInfoResponse {
+        phase_id,
+        aggregator: PHASE_AGGREGATORS.load(deps.storage, phase_id.into())?,
+        info: PHASE_INFO.may_load(deps.storage, phase_id.into())?,
+    })
+}
+
+pub fn get_phases(
+    deps: Deps,
+    _env: Env,
+    start_after: Option<u16>,
+    limit: Option<u32>,
+) -> StdResult<PhasesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|id| Bound::exclusive(id.to_be_bytes().to_vec()));
+
+    let phases = PHASE_AGGREGATORS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|entry| {
+            let (key, aggregator) = entry?;
+            let phase_id = u16::from_be_bytes(key.as_slice().try_into().unwrap());
+            Ok(PhaseInfoResponse {
+                phase_id,
+                aggregator,
+                info: PHASE_INFO.may_load(deps.storage, phase_id.into())?,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(PhasesResponse { phases })
+}
+
+/// Latest round data of the current aggregator, or its backup depending on the guard
+pub fn get_latest_round_data(deps: Deps, env: Env) -> StdResult<RoundDataResponse> {
+    if GUARD.may_load(deps.storage)?.is_none() {
+        return get_current_latest_round_data(deps);
+    }
+    Ok(get_guarded_latest_round_data(deps, env)?.round_data)
+}
+
+pub fn get_guarded_latest_round_data(deps: Deps, env: Env) -> StdResult<GuardedRoundDataResponse> {
+    let phase = CURRENT_PHASE.load(deps.storage)?;
+    let round_data = get_current_latest_round_data(deps)?;
+    let guard = match GUARD.may_load(deps.storage)? {
+        Some(guard) => guard,
+        None => {
+            return Ok(GuardedRoundDataResponse {
+                round_data,
+                source: DataSource::Current,
+                aggregator: phase.aggregator_addr,
+                flagged: false,
+                stale: false,
+            })
+        }
+    };
+
+    let now = env.block.time.seconds();
+    let flagged = is_flagged(deps, &guard, &phase.aggregator_addr)?;
+    let stale = is_stale(&guard, &round_data, now);
+
+    let policy = [
+        (flagged, &guard.flagged_policy),
+        (stale, &guard.stale_policy),
+    ]
+    .iter()
+    .filter(|(applies, _)| *applies)
+    .map(|(_, policy)| *policy)
+    .max()
+    .unwrap_or(&Policy::Serve);
+
+    match policy {
+        Policy::Serve => Ok(GuardedRoundDataResponse {
+            round_data,
+            source: DataSource::Current,
+            aggregator: phase.aggregator_addr,
+            flagged,
+            stale,
+        }),
+        Policy::Error if flagged && guard.flagged_policy == Policy::Error => Err(
+            StdError::generic_err(ContractError::AggregatorFlagged {}.to_string()),
+        ),
+        Policy::Error => Err(StdError::generic_err(
+            ContractError::StaleAnswer {}.to_string(),
+        )),
+        Policy::Backup => {
+            let (aggregator, backup_data) = match &guard.backup {
+                Some(backup) => {
+                    let res: RoundDataResponse = deps
+                        .querier
+                        .query_wasm_smart(backup, &GetLatestRoundData {}.wrap())?;
+                    (backup.clone(), add_phase_ids(res, BACKUP_PHASE_ID)?)
+                }
+                None if phase.id > 1 => {
+                    let previous_id = phase.id - 1;
+                    let previous = PHASE_AGGREGATORS.load(deps.storage, previous_id.into())?;
+                    let res: RoundDataResponse = deps
+                        .querier
+                        .query_wasm_smart(&previous, &GetLatestRoundData {}.wrap())?;
+                    // the previous phase's answer is only worth serving if it is newer
+                    if res.updated_at <= round_data.updated_at {
+                        return Err(StdError::generic_err(
+                            ContractError::StaleAnswer {}.to_string(),
+                        ));
+                    }
+                    (previous, add_phase_ids(res, previous_id)?)
+                }
+                None => {
+                    return Err(StdError::generic_err(
+                        ContractError::NoBackupAggregator {}.to_string(),
+                    ))
+                }
+            };
+            // the backup is held to the same standard as the current aggregator
+            if is_flagged(deps, &guard, &aggregator)? {
+                return Err(StdError::generic_err(
+                    ContractError::AggregatorFlagged {}.to_string(),
+                ));
+            }
+            if is_stale(&guard, &backup_data, now) {
+                return Err(StdError::generic_err(
+                    ContractError::StaleAnswer {}.to_string(),
+                ));
+            }
+            let round_data = backup_data;
+            Ok(GuardedRoundDataResponse {
+                round_data,
+                source: DataSource::Backup,
+                aggregator,
+                flagged,
+                stale,
+            })
+        }
+    }
+}
+
+fn is_flagged(deps: Deps, guard: &Guard, aggregator: &Addr) -> StdResult<bool> {
+    let flags = match &guard.flags {
+        Some(flags) => flags,
+        None => return Ok(false),
+    };
+    // flags that were never raised are left out of the response
+    let res: Vec<bool> = deps.querier.query_wasm_smart(
+        flags,
+        &flags::msg::QueryMsg::GetFlags {
+            subjects: vec![aggregator.to_string()],
+        },
+    )?;
+    Ok(res.first() == Some(&true))
+}
+
+fn is_stale(guard: &Guard, round_data: &RoundDataResponse, now: u64) -> bool {
+    match guard.max_staleness {
+        Some(max_staleness) => match round_data.updated_at {
+            Some(updated_at) => now > updated_at.saturating_add(max_staleness),
+            None => true,
+        },
+        None => false,
+    }
 }
 
-pub fn get_latest_round_data(deps: Deps, _env: Env) -> StdResult<RoundDataResponse> {
+fn get_current_latest_round_data(deps: Deps) -> StdResult<RoundDataResponse> {
     let Phase {
         aggregator_addr,
         id,
@@ -192,13 +648,13 @@ pub fn get_latest_round_data(deps: Deps, _env: Env) -> StdResult<RoundDataRespon
     let res: RoundDataResponse = deps
         .querier
         .query_wasm_smart(aggregator_addr, &GetLatestRoundData {}.wrap())?;
-    Ok(add_phase_ids(res, id))
+    add_phase_ids(res, id)
 }
 
 pub fn get_proposed_round_data(
     deps: Deps,
     _env: Env,
-    round_id: u32,
+    round_id: u64,
 ) -> StdResult<RoundDataResponse> {
     let proposed = get_proposed(deps.storage)?;
     deps.querier
@@ -211,10 +667,93 @@ pub fn get_proposed_latest_round_data(deps: Deps, _env: Env) -> StdResult<RoundD
         .query_wasm_smart(proposed, &GetLatestRoundData {}.wrap())
 }
 
+pub fn get_proposal_comparison(
+    deps: Deps,
+    env: Env,
+    rounds: u32,
+) -> StdResult<ProposalComparisonResponse> {
+    let rounds = rounds.min(MAX_COMPARISON_ROUNDS);
+    let current_aggregator = CURRENT_PHASE.load(deps.storage)?.aggregator_addr;
+    let proposed_aggregator = get_proposed(deps.storage)?;
+    let current_decimals: u8 = deps
+        .querier
+        .query_wasm_smart(&current_aggregator, &GetDecimals {}.wrap())?;
+    let proposed_decimals: u8 = deps
+        .querier
+        .query_wasm_smart(&proposed_aggregator, &GetDecimals {}.wrap())?;
+
+    let current = latest_rounds(deps, &current_aggregator, rounds);
+    let proposed = latest_rounds(deps, &proposed_aggregator, rounds);
+    let staleness = |rounds: &[RoundDataResponse]| {
+        rounds
+            .first()
+            .and_then(|round| round.updated_at)
+            .map(|updated_at| env.block.time.seconds().saturating_sub(updated_at))
+    };
+    let current_staleness = staleness(&current);
+    let proposed_staleness = staleness(&proposed);
+
+    let mut current = current.into_iter();
+    let mut proposed = proposed.into_iter();
+    let mut comparisons = vec![];
+    for _ in 0..rounds {
+        let (current, proposed) = match (current.next(), proposed.next()) {
+            (None, None) => break,
+            rounds => rounds,
+        };
+        let answers = current
+            .as_ref()
+            .and_then(|round| round.answer)
+            .zip(proposed.as_ref().and_then(|round| round.answer));
+        let (absolute_divergence, relative_divergence) = match answers {
+            Some((current_answer, proposed_answer)) => {
+                let proposed_answer =
+                    normalize(proposed_answer, proposed_decimals, current_decimals)?;
+                let change = if current_answer > proposed_answer {
+                    current_answer - proposed_answer
+                } else {
+                    proposed_answer - current_answer
+                };
+                (Some(change), relative_divergence(change, current_answer))
+            }
+            None => (None, None),
+        };
+        comparisons.push(RoundComparison {
+            current,
+            proposed,
+            absolute_divergence,
+            relative_divergence,
+        });
+    }
+
+    Ok(ProposalComparisonResponse {
+        current_aggregator,
+        proposed_aggregator,
+        current_decimals,
+        proposed_decimals,
+        rounds: comparisons,
+        current_staleness,
+        proposed_staleness,
+    })
+}
+
 pub fn get_proposed_aggregator(deps: Deps, _env: Env) -> StdResult<Addr> {
     PROPOSED_AGGREGATOR.load(deps.storage)
 }
 
+pub fn get_earliest_confirmation(deps: Deps, _env: Env) -> StdResult<ConfirmationTimeResponse> {
+    Ok(ConfirmationTimeResponse {
+        aggregator: get_proposed(deps.storage)?,
+        earliest_confirmation: EARLIEST_CONFIRMATION
+            .may_load(deps.storage)?
+            .unwrap_or_default(),
+    })
+}
+
+pub fn get_confirmation_delay(deps: Deps, env: Env) -> StdResult<u64> {
+    confirmation_delay(deps.storage, env.block.time.seconds())
+}
+
 pub fn get_aggregator(deps: Deps, _env: Env) -> StdResult<Addr> {
     CURRENT_PHASE
         .load(deps.storage)
@@ -225,6 +764,15 @@ pub fn get_phase_id(deps: Deps, _env: Env) -> StdResult<u16> {
     Ok(CURRENT_PHASE.load(deps.storage)?.id)
 }
 
+/// Confirmation delay in effect at `now`, in seconds
+fn confirmation_delay(storage: &dyn Storage, now: u64) -> StdResult<u64> {
+    match PENDING_DELAY_DECREASE.may_load(storage)? {
+        Some(decrease) if now >= decrease.effective_at => Ok(decrease.delay),
+        // contracts migrated from before the delay was introduced have none
+        _ => Ok(CONFIRMATION_DELAY.may_load(storage)?.unwrap_or_default()),
+    }
+}
+
 fn get_proposed(storage: &dyn Storage) -> StdResult<Addr> {
     PROPOSED_AGGREGATOR
         .may_load(storage)?
@@ -232,14 +780,70 @@ fn get_proposed(storage: &dyn Storage) -> StdResult<Addr> {
         .map_err(|err| StdError::generic_err(err.to_string()))
 }
 
-fn add_phase_ids(round_data: RoundDataResponse, phase_id: u16) -> RoundDataResponse {
-    RoundDataResponse {
-        round_id: add_phase(phase_id, round_data.round_id),
+fn add_phase_ids(round_data: RoundDataResponse, phase_id: u16) -> StdResult<RoundDataResponse> {
+    Ok(RoundDataResponse {
+        round_id: add_phase(phase_id, round_data.round_id)?,
         answer: round_data.answer,
         started_at: round_data.started_at,
         updated_at: round_data.updated_at,
-        answered_in_round: add_phase(phase_id, round_data.answered_in_round),
+        answered_in_round: add_phase(phase_id, round_data.answered_in_round)?,
+    })
+}
+
+/// Answered rounds among the latest `rounds` rounds of an aggregator, most recent first
+fn latest_rounds(deps: Deps, aggregator: &Addr, rounds: u32) -> Vec<RoundDataResponse> {
+    let latest: RoundDataResponse = match deps
+        .querier
+        .query_wasm_smart(aggregator, &GetLatestRoundData {}.wrap())
+    {
+        Ok(latest) => latest,
+        Err(_) => return vec![],
+    };
+    (0..rounds as u64)
+        .map_while(|offset| latest.round_id.checked_sub(offset).filter(|id| *id > 0))
+        .filter_map(|round_id| {
+            deps.querier
+                .query_wasm_smart(aggregator, &GetRoundData { round_id }.wrap())
+                .ok()
+        })
+        .collect()
+}
+
+/// `change` relative to `answer`, `THRESHOLD_MULTIPLIER` being 100%. `None` for a zero answer
+fn relative_divergence(change: Uint128, answer: Uint128) -> Option<Uint128> {
+    let divergence = change
+        .full_mul(THRESHOLD_MULTIPLIER)
+        .checked_div(answer.into())
+        .ok()?;
+    // divergences too large for `Uint128` are beyond any bound
+    Some(Uint128::try_from(divergence).unwrap_or(Uint128::MAX))
+}
+
+fn normalize(value: Uint128, from: u8, to: u8) -> StdResult<Uint128> {
+    let factor = |diff: u8| {
+        10u128
+            .checked_pow(diff.into())
+            .map(Uint128::new)
+            .ok_or_else(|| StdError::generic_err("Decimals difference is too large"))
+    };
+    if from > to {
+        Ok(value / factor(from - to)?)
+    } else {
+        Ok(value.checked_mul(factor(to - from)?)?)
+    }
+}
+
+/// Latest proxied round ID of a phase's aggregator, `None` if it has no data
+fn latest_phase_round_id(deps: Deps, aggregator: &Addr, phase_id: u16) -> Option<u64> {
+    let res: RoundDataResponse = deps
+        .querier
+        .query_wasm_smart(aggregator, &GetLatestRoundData {}.wrap())
+        .ok()?;
+    // round IDs start at 1
+    if res.round_id == 0 {
+        return None;
     }
+    add_phase(phase_id, res.round_id).ok()
 }
 
 fn query_current<T: DeserializeOwned>(
@@ -253,19 +857,11 @@ fn query_current<T: DeserializeOwned>(
         .query_wasm_smart(aggregator_addr, &query.wrap())
 }
 
-fn add_phase(phase: u16, original_id: u32) -> u32 {
-    (phase as u32)
-        .checked_shl(PHASE_OFFSET.u128() as u32)
-        .unwrap_or(0)
-        | original_id
-}
-
-fn validate_ownership(deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
-    let owner = get_owner(deps)?;
-    if info.sender != owner {
-        return Err(ContractError::NotOwner {});
-    }
-    Ok(())
+fn add_phase(phase: u16, original_id: u64) -> StdResult<u64> {
+    let original_id: u32 = original_id
+        .try_into()
+        .map_err(|_| StdError::generic_err(ContractError::InvalidRoundId {}.to_string()))?;
+    Ok(compose_round_id(phase, original_id))
 }
 
 // Called when migrating a contract instance to a new code ID
@@ -287,6 +883,7 @@ mod tests {
     const OWNER: &str = "admin0001";
 
     const PAYMENT_AMOUNT: Uint128 = Uint128::new(3);
+    const ORACLE: &str = "oracle";
 
     pub fn contract_proxy() -> Box<dyn Contract<Empty>> {
         let contract = ContractWrapper::new(
@@ -319,10 +916,36 @@ mod tests {
             deviation_flagging_validator::contract::execute,
             deviation_flagging_validator::contract::instantiate,
             deviation_flagging_validator::contract::query,
+        )
+        .with_reply(deviation_flagging_validator::contract::reply);
+        Box::new(contract)
+    }
+
+    pub fn contract_flags() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            flags::contract::execute,
+            flags::contract::instantiate,
+            flags::contract::query,
         );
         Box::new(contract)
     }
 
+    pub fn instantiate_flags(app: &mut App) -> Addr {
+        let flags_id = app.store_code(contract_flags());
+        app.instantiate_contract(
+            flags_id,
+            Addr::unchecked(OWNER),
+            &flags::msg::InstantiateMsg {
+                rac_address: OWNER.to_owned(),
+                lac_address: None,
+            },
+            &[],
+            "Flags",
+            None,
+        )
+        .unwrap()
+    }
+
     pub fn instantiate_link(app: &mut App) -> Addr {
         let link_id = app.store_code(contract_link_token());
         let main_balance = Cw20Coin {
@@ -391,9 +1014,18 @@ mod tests {
     }
 
     pub fn instantiate_proxy(app: &mut App, aggregator: Addr) -> Addr {
+        instantiate_proxy_with_delay(app, aggregator, None)
+    }
+
+    pub fn instantiate_proxy_with_delay(
+        app: &mut App,
+        aggregator: Addr,
+        confirmation_delay: Option<u64>,
+    ) -> Addr {
         let proxy_id = app.store_code(contract_proxy());
         let msg = crate::msg::InstantiateMsg {
             aggregator: aggregator.to_string(),
+            confirmation_delay,
         };
         app.instantiate_contract(
             proxy_id,
@@ -406,6 +1038,96 @@ mod tests {
         .unwrap()
     }
 
+    /// Registers the aggregator with its validator, funds it and adds a single oracle
+    pub fn enable_submissions(app: &mut App, link_addr: Addr, validator_addr: Addr, flux: Addr) {
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            validator_addr,
+            &deviation_flagging_validator::msg::ExecuteMsg::SetAggregatorConfig {
+                aggregator: flux.to_string(),
+                config: Some(deviation_flagging_validator::state::AggregatorConfig {
+                    relative_threshold: None,
+                    absolute_threshold: None,
+                    recovery: None,
+                }),
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            link_addr,
+            &cw20::Cw20ExecuteMsg::Send {
+                contract: flux.to_string(),
+                amount: Uint128::new(100),
+                msg: Binary::from(b""),
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            flux,
+            &flux_aggregator::msg::ExecuteMsg::ChangeOracles {
+                removed: vec![],
+                added: vec![ORACLE.to_owned()],
+                added_admins: vec![ORACLE.to_owned()],
+                min_submissions: 1,
+                max_submissions: 1,
+                restart_delay: 0,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    pub fn submit(app: &mut App, flux: &Addr, round_id: u32, answer: u128) {
+        app.execute_contract(
+            Addr::unchecked(ORACLE),
+            flux.clone(),
+            &flux_aggregator::msg::ExecuteMsg::Submit {
+                round_id,
+                submission: Uint128::new(answer),
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    /// Instantiates an aggregator that accepts submissions from [`ORACLE`]
+    pub fn instantiate_feed(app: &mut App, description: &str) -> Addr {
+        let link_addr = instantiate_link(app);
+        let df_validator_addr = instantiate_df_validator(app);
+        let flux_aggregator_addr = instantiate_flux(
+            app,
+            link_addr.clone(),
+            df_validator_addr.clone(),
+            description,
+        );
+        enable_submissions(
+            app,
+            link_addr,
+            df_validator_addr,
+            flux_aggregator_addr.clone(),
+        );
+        flux_aggregator_addr
+    }
+
+    /// Proposes and confirms the aggregator
+    pub fn upgrade(app: &mut App, proxy_addr: &Addr, aggregator: &Addr) {
+        for msg in [
+            ExecuteMsg::ProposeAggregator {
+                aggregator: aggregator.to_string(),
+            },
+            ExecuteMsg::ConfirmAggregator {
+                aggregator: aggregator.to_string(),
+            },
+        ] {
+            app.execute_contract(Addr::unchecked(OWNER), proxy_addr.clone(), &msg, &[])
+                .unwrap();
+        }
+    }
+
     fn mock_app() -> App {
         let env = mock_env();
         let api = MockApi::default();
@@ -595,4 +1317,810 @@ mod tests {
             .unwrap();
         assert_eq!("LUNA/USD".to_string(), res);
     }
+
+    #[test]
+    fn round_ids_include_phase() {
+        let mut app = mock_app();
+
+        let flux_aggregator_addr = instantiate_feed(&mut app, "LINK/USD");
+        let flux_aggregator_addr2 = instantiate_feed(&mut app, "LINK/USD");
+
+        let proxy_addr = instantiate_proxy(&mut app, flux_aggregator_addr.clone());
+
+        submit(&mut app, &flux_aggregator_addr, 1, 100);
+        submit(&mut app, &flux_aggregator_addr, 2, 200);
+
+        let res: RoundDataResponse = app
+            .wrap()
+            .query_wasm_smart(&proxy_addr, &GetLatestRoundData {}.wrap())
+            .unwrap();
+        assert_eq!(res.round_id, compose_round_id(1, 2));
+        assert_eq!(res.answered_in_round, compose_round_id(1, 2));
+        assert_eq!(res.answer, Some(Uint128::new(200)));
+
+        upgrade(&mut app, &proxy_addr, &flux_aggregator_addr2);
+        submit(&mut app, &flux_aggregator_addr2, 1, 300);
+
+        let res: RoundDataResponse = app
+            .wrap()
+            .query_wasm_smart(&proxy_addr, &GetLatestRoundData {}.wrap())
+            .unwrap();
+        assert_eq!(res.round_id, compose_round_id(2, 1));
+        assert_eq!(res.answer, Some(Uint128::new(300)));
+
+        // rounds of previous phases stay reachable
+        let res: RoundDataResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &proxy_addr,
+                &GetRoundData {
+                    round_id: compose_round_id(1, 1),
+                }
+                .wrap(),
+            )
+            .unwrap();
+        assert_eq!(res.round_id, compose_round_id(1, 1));
+        assert_eq!(res.answer, Some(Uint128::new(100)));
+
+        // round IDs without a phase refer to the current phase
+        let res: RoundDataResponse = app
+            .wrap()
+            .query_wasm_smart(&proxy_addr, &GetRoundData { round_id: 1 }.wrap())
+            .unwrap();
+        assert_eq!(res.round_id, compose_round_id(2, 1));
+        assert_eq!(res.answer, Some(Uint128::new(300)));
+
+        // unknown phase
+        let res: StdResult<RoundDataResponse> = app.wrap().query_wasm_smart(
+            &proxy_addr,
+            &GetRoundData {
+                round_id: compose_round_id(3, 1),
+            }
+            .wrap(),
+        );
+        assert!(res.is_err());
+
+        // bits above the phase ID
+        let res: StdResult<RoundDataResponse> = app
+            .wrap()
+            .query_wasm_smart(&proxy_addr, &GetRoundData { round_id: 1 << 48 }.wrap());
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn phase_round_data() {
+        let mut app = mock_app();
+        let flux_aggregator_addr = instantiate_feed(&mut app, "LINK/USD");
+        let flux_aggregator_addr2 = instantiate_feed(&mut app, "LINK/USD");
+        let proxy_addr = instantiate_proxy(&mut app, flux_aggregator_addr.clone());
+
+        submit(&mut app, &flux_aggregator_addr, 1, 100);
+        upgrade(&mut app, &proxy_addr, &flux_aggregator_addr2);
+        submit(&mut app, &flux_aggregator_addr2, 1, 200);
+
+        let query = |phase_id, round_id| -> StdResult<RoundDataResponse> {
+            app.wrap().query_wasm_smart(
+                &proxy_addr,
+                &QueryMsg::GetPhaseRoundData { phase_id, round_id },
+            )
+        };
+        let res = query(1, 1).unwrap();
+        assert_eq!(res.round_id, compose_round_id(1, 1));
+        assert_eq!(res.answer, Some(Uint128::new(100)));
+        let res = query(2, 1).unwrap();
+        assert_eq!(res.round_id, compose_round_id(2, 1));
+        assert_eq!(res.answer, Some(Uint128::new(200)));
+        assert!(query(1, 2).is_err());
+        assert!(query(3, 1).is_err());
+    }
+
+    #[test]
+    fn phase_boundaries() {
+        let mut app = mock_app();
+        let flux_aggregator_addr = instantiate_feed(&mut app, "LINK/USD");
+        let flux_aggregator_addr2 = instantiate_feed(&mut app, "LINK/USD");
+        let flux_aggregator_addr3 = instantiate_feed(&mut app, "LINK/USD");
+        let started_at = app.block_info().time.seconds();
+        let proxy_addr = instantiate_proxy(&mut app, flux_aggregator_addr.clone());
+
+        submit(&mut app, &flux_aggregator_addr, 1, 100);
+        submit(&mut app, &flux_aggregator_addr, 2, 100);
+        // the next aggregator already has data when it's confirmed
+        submit(&mut app, &flux_aggregator_addr2, 1, 100);
+        app.update_block(|block| block.time = block.time.plus_seconds(100));
+        upgrade(&mut app, &proxy_addr, &flux_aggregator_addr2);
+        submit(&mut app, &flux_aggregator_addr2, 2, 100);
+        app.update_block(|block| block.time = block.time.plus_seconds(100));
+        upgrade(&mut app, &proxy_addr, &flux_aggregator_addr3);
+
+        let res: PhaseInfoResponse = app
+            .wrap()
+            .query_wasm_smart(&proxy_addr, &QueryMsg::GetPhaseInfo { phase_id: 1 })
+            .unwrap();
+        assert_eq!(
+            res,
+            PhaseInfoResponse {
+                phase_id: 1,
+                aggregator: flux_aggregator_addr,
+                info: Some(PhaseInfo {
+                    started_at,
+                    first_round_id: None,
+                    ended_at: Some(started_at + 100),
+                    last_round_id: Some(compose_round_id(1, 2)),
+                }),
+            }
+        );
+
+        let res: PhasesResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &proxy_addr,
+                &QueryMsg::GetPhases {
+                    start_after: Some(1),
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            res.phases,
+            vec![
+                PhaseInfoResponse {
+                    phase_id: 2,
+                    aggregator: flux_aggregator_addr2,
+                    info: Some(PhaseInfo {
+                        started_at: started_at + 100,
+                        first_round_id: Some(compose_round_id(2, 1)),
+                        ended_at: Some(started_at + 200),
+                        last_round_id: Some(compose_round_id(2, 2)),
+                    }),
+                },
+                PhaseInfoResponse {
+                    phase_id: 3,
+                    aggregator: flux_aggregator_addr3,
+                    info: Some(PhaseInfo {
+                        started_at: started_at + 200,
+                        first_round_id: None,
+                        ended_at: None,
+                        last_round_id: None,
+                    }),
+                },
+            ]
+        );
+
+        let res: PhasesResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &proxy_addr,
+                &QueryMsg::GetPhases {
+                    start_after: None,
+                    limit: Some(2),
+                },
+            )
+            .unwrap();
+        let ids: Vec<u16> = res.phases.iter().map(|phase| phase.phase_id).collect();
+        assert_eq!(ids, vec![1, 2]);
+    }
+
+    #[test]
+    fn confirmation_delay() {
+        let mut app = mock_app();
+        let flux_aggregator_addr = instantiate_feed(&mut app, "LINK/USD");
+        let flux_aggregator_addr2 = instantiate_feed(&mut app, "LINK/USD");
+        let proxy_addr =
+            instantiate_proxy_with_delay(&mut app, flux_aggregator_addr.clone(), Some(100));
+        let proposed_at = app.block_info().time.seconds();
+
+        let res = app
+            .execute_contract(
+                Addr::unchecked(OWNER),
+                proxy_addr.clone(),
+                &ExecuteMsg::ProposeAggregator {
+                    aggregator: flux_aggregator_addr2.to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+        assert!(res.has_event(
+            &Event::new("wasm-propose_aggregator")
+                .add_attribute("aggregator", flux_aggregator_addr2.to_string())
+                .add_attribute("earliest_confirmation", (proposed_at + 100).to_string())
+        ));
+
+        let res: ConfirmationTimeResponse = app
+            .wrap()
+            .query_wasm_smart(&proxy_addr, &QueryMsg::GetEarliestConfirmation {})
+            .unwrap();
+        assert_eq!(
+            res,
+            ConfirmationTimeResponse {
+                aggregator: flux_aggregator_addr2.clone(),
+                earliest_confirmation: proposed_at + 100,
+            }
+        );
+
+        let confirm = ExecuteMsg::ConfirmAggregator {
+            aggregator: flux_aggregator_addr2.to_string(),
+        };
+        let err = app
+            .execute_contract(Addr::unchecked(OWNER), proxy_addr.clone(), &confirm, &[])
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            ContractError::ConfirmationDelayNotPassed {}.to_string()
+        );
+
+        app.update_block(|block| block.time = block.time.plus_seconds(99));
+        app.execute_contract(Addr::unchecked(OWNER), proxy_addr.clone(), &confirm, &[])
+            .unwrap_err();
+
+        app.update_block(|block| block.time = block.time.plus_seconds(1));
+        app.execute_contract(Addr::unchecked(OWNER), proxy_addr.clone(), &confirm, &[])
+            .unwrap();
+        let res: Addr = app
+            .wrap()
+            .query_wasm_smart(&proxy_addr, &QueryMsg::GetAggregator {})
+            .unwrap();
+        assert_eq!(res, flux_aggregator_addr2);
+
+        // a shorter delay only applies to later proposals
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            proxy_addr.clone(),
+            &ExecuteMsg::ProposeAggregator {
+                aggregator: flux_aggregator_addr.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+        let decreased_at = app.block_info().time.seconds();
+        let res = app
+            .execute_contract(
+                Addr::unchecked(OWNER),
+                proxy_addr.clone(),
+                &ExecuteMsg::SetConfirmationDelay { delay: 0 },
+                &[],
+            )
+            .unwrap();
+        assert!(res.has_event(
+            &Event::new("wasm-set_confirmation_delay")
+                .add_attribute("delay", "0")
+                .add_attribute("effective_at", (decreased_at + 100).to_string())
+        ));
+        let confirm = ExecuteMsg::ConfirmAggregator {
+            aggregator: flux_aggregator_addr.to_string(),
+        };
+        app.execute_contract(Addr::unchecked(OWNER), proxy_addr.clone(), &confirm, &[])
+            .unwrap_err();
+
+        // and only once the previous delay passed
+        let propose = ExecuteMsg::ProposeAggregator {
+            aggregator: flux_aggregator_addr.to_string(),
+        };
+        let delay = |app: &App| -> u64 {
+            app.wrap()
+                .query_wasm_smart(&proxy_addr, &QueryMsg::GetConfirmationDelay {})
+                .unwrap()
+        };
+        app.update_block(|block| block.time = block.time.plus_seconds(99));
+        assert_eq!(delay(&app), 100);
+        app.execute_contract(Addr::unchecked(OWNER), proxy_addr.clone(), &propose, &[])
+            .unwrap();
+        let res: ConfirmationTimeResponse = app
+            .wrap()
+            .query_wasm_smart(&proxy_addr, &QueryMsg::GetEarliestConfirmation {})
+            .unwrap();
+        assert_eq!(res.earliest_confirmation, decreased_at + 199);
+
+        app.update_block(|block| block.time = block.time.plus_seconds(1));
+        assert_eq!(delay(&app), 0);
+        app.execute_contract(Addr::unchecked(OWNER), proxy_addr.clone(), &propose, &[])
+            .unwrap();
+        app.execute_contract(Addr::unchecked(OWNER), proxy_addr, &confirm, &[])
+            .unwrap();
+    }
+
+    #[test]
+    fn config_admin_role() {
+        let mut app = mock_app();
+        let flux_aggregator_addr = instantiate_feed(&mut app, "LINK/USD");
+        let proxy_addr = instantiate_proxy(&mut app, flux_aggregator_addr);
+        let set_delay = ExecuteMsg::SetConfirmationDelay { delay: 60 };
+
+        app.execute_contract(
+            Addr::unchecked("admin"),
+            proxy_addr.clone(),
+            &set_delay,
+            &[],
+        )
+        .unwrap_err();
+        let grant = ExecuteMsg::GrantRole {
+            role: CONFIG_ADMIN.to_owned(),
+            account: "admin".to_owned(),
+        };
+        app.execute_contract(Addr::unchecked("admin"), proxy_addr.clone(), &grant, &[])
+            .unwrap_err();
+        app.execute_contract(Addr::unchecked(OWNER), proxy_addr.clone(), &grant, &[])
+            .unwrap();
+        let has_role: bool = app
+            .wrap()
+            .query_wasm_smart(
+                &proxy_addr,
+                &QueryMsg::HasRole {
+                    role: CONFIG_ADMIN.to_owned(),
+                    account: "admin".to_owned(),
+                },
+            )
+            .unwrap();
+        assert!(has_role);
+
+        app.execute_contract(
+            Addr::unchecked("admin"),
+            proxy_addr.clone(),
+            &set_delay,
+            &[],
+        )
+        .unwrap();
+        let delay: u64 = app
+            .wrap()
+            .query_wasm_smart(&proxy_addr, &QueryMsg::GetConfirmationDelay {})
+            .unwrap();
+        assert_eq!(delay, 60);
+
+        let renounce = ExecuteMsg::RenounceRole {
+            role: CONFIG_ADMIN.to_owned(),
+        };
+        app.execute_contract(Addr::unchecked("admin"), proxy_addr.clone(), &renounce, &[])
+            .unwrap();
+        app.execute_contract(Addr::unchecked("admin"), proxy_addr, &set_delay, &[])
+            .unwrap_err();
+    }
+
+    #[test]
+    fn cancel_proposed_aggregator() {
+        let mut app = mock_app();
+        let flux_aggregator_addr = instantiate_feed(&mut app, "LINK/USD");
+        let flux_aggregator_addr2 = instantiate_feed(&mut app, "LINK/USD");
+        let proxy_addr = instantiate_proxy(&mut app, flux_aggregator_addr.clone());
+
+        let cancel = ExecuteMsg::CancelProposedAggregator {};
+        let err = app
+            .execute_contract(Addr::unchecked(OWNER), proxy_addr.clone(), &cancel, &[])
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            ContractError::NoProposedAggregator {}.to_string()
+        );
+
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            proxy_addr.clone(),
+            &ExecuteMsg::ProposeAggregator {
+                aggregator: flux_aggregator_addr2.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("stranger"),
+                proxy_addr.clone(),
+                &cancel,
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            owned::error::ContractError::MissingRole {
+                role: CONFIG_ADMIN.to_owned(),
+            }
+            .to_string()
+        );
+
+        let res = app
+            .execute_contract(Addr::unchecked(OWNER), proxy_addr.clone(), &cancel, &[])
+            .unwrap();
+        assert!(res.has_event(
+            &Event::new("wasm-cancel_proposed_aggregator")
+                .add_attribute("aggregator", flux_aggregator_addr2.to_string())
+        ));
+
+        let res: StdResult<ConfirmationTimeResponse> = app
+            .wrap()
+            .query_wasm_smart(&proxy_addr, &QueryMsg::GetEarliestConfirmation {});
+        assert!(res.is_err());
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked(OWNER),
+                proxy_addr.clone(),
+                &ExecuteMsg::ConfirmAggregator {
+                    aggregator: flux_aggregator_addr2.to_string(),
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            ContractError::InvalidProposedAggregator {}.to_string()
+        );
+
+        let res: Addr = app
+            .wrap()
+            .query_wasm_smart(&proxy_addr, &QueryMsg::GetAggregator {})
+            .unwrap();
+        assert_eq!(res, flux_aggregator_addr);
+    }
+
+    #[test]
+    fn relative_divergence_bounds() {
+        assert_eq!(
+            relative_divergence(Uint128::new(5), Uint128::new(100)),
+            Some(Uint128::new(5000))
+        );
+        assert_eq!(relative_divergence(Uint128::new(5), Uint128::zero()), None);
+        assert_eq!(
+            relative_divergence(Uint128::MAX, Uint128::new(1)),
+            Some(Uint128::MAX)
+        );
+    }
+
+    #[test]
+    fn proposal_comparison() {
+        let mut app = mock_app();
+        let flux_aggregator_addr = instantiate_feed(&mut app, "LINK/USD");
+        let flux_aggregator_addr2 = instantiate_feed(&mut app, "LINK/USD");
+        let proxy_addr = instantiate_proxy(&mut app, flux_aggregator_addr.clone());
+
+        submit(&mut app, &flux_aggregator_addr, 1, 1000);
+        submit(&mut app, &flux_aggregator_addr, 2, 2000);
+        submit(&mut app, &flux_aggregator_addr2, 1, 1100);
+        app.update_block(|block| block.time = block.time.plus_seconds(10));
+        submit(&mut app, &flux_aggregator_addr2, 2, 1900);
+        app.update_block(|block| block.time = block.time.plus_seconds(5));
+
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            proxy_addr.clone(),
+            &ExecuteMsg::ProposeAggregator {
+                aggregator: flux_aggregator_addr2.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let res: ProposalComparisonResponse = app
+            .wrap()
+            .query_wasm_smart(&proxy_addr, &QueryMsg::GetProposalComparison { rounds: 3 })
+            .unwrap();
+        assert_eq!(res.current_aggregator, flux_aggregator_addr);
+        assert_eq!(res.proposed_aggregator, flux_aggregator_addr2);
+        assert_eq!(res.current_staleness, Some(15));
+        assert_eq!(res.proposed_staleness, Some(5));
+        assert_eq!(res.rounds.len(), 2);
+        assert_eq!(res.rounds[0].current.as_ref().unwrap().round_id, 2);
+        assert_eq!(res.rounds[0].proposed.as_ref().unwrap().round_id, 2);
+        assert_eq!(res.rounds[0].absolute_divergence, Some(Uint128::new(100)));
+        // 5%
+        assert_eq!(res.rounds[0].relative_divergence, Some(Uint128::new(5000)));
+        assert_eq!(res.rounds[1].absolute_divergence, Some(Uint128::new(100)));
+        // 10%
+        assert_eq!(res.rounds[1].relative_divergence, Some(Uint128::new(10000)));
+    }
+
+    #[test]
+    fn confirm_refused_above_max_divergence() {
+        let mut app = mock_app();
+        let flux_aggregator_addr = instantiate_feed(&mut app, "LINK/USD");
+        let flux_aggregator_addr2 = instantiate_feed(&mut app, "LINK/USD");
+        let proxy_addr = instantiate_proxy(&mut app, flux_aggregator_addr.clone());
+        submit(&mut app, &flux_aggregator_addr, 1, 1000);
+
+        let owner = Addr::unchecked(OWNER);
+        let res = app
+            .execute_contract(
+                owner.clone(),
+                proxy_addr.clone(),
+                &ExecuteMsg::SetMaxProposalDivergence {
+                    divergence: Some(5000),
+                },
+                &[],
+            )
+            .unwrap();
+        assert!(res.has_event(
+            &Event::new("wasm-set_max_proposal_divergence").add_attribute("divergence", "5000")
+        ));
+        app.execute_contract(
+            owner.clone(),
+            proxy_addr.clone(),
+            &ExecuteMsg::ProposeAggregator {
+                aggregator: flux_aggregator_addr2.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+        let confirm = ExecuteMsg::ConfirmAggregator {
+            aggregator: flux_aggregator_addr2.to_string(),
+        };
+
+        // proposed aggregator has no answer yet
+        let err = app
+            .execute_contract(owner.clone(), proxy_addr.clone(), &confirm, &[])
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            ContractError::ProposalDivergenceUnavailable {}.to_string()
+        );
+
+        submit(&mut app, &flux_aggregator_addr2, 1, 1100);
+        let err = app
+            .execute_contract(owner.clone(), proxy_addr.clone(), &confirm, &[])
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            ContractError::ProposalDivergenceTooHigh {}.to_string()
+        );
+
+        submit(&mut app, &flux_aggregator_addr2, 2, 1050);
+        app.execute_contract(owner, proxy_addr.clone(), &confirm, &[])
+            .unwrap();
+        let res: Addr = app
+            .wrap()
+            .query_wasm_smart(&proxy_addr, &QueryMsg::GetAggregator {})
+            .unwrap();
+        assert_eq!(res, flux_aggregator_addr2);
+    }
+
+    fn set_guard(app: &mut App, proxy_addr: &Addr, guard: GuardMsg) {
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            proxy_addr.clone(),
+            &ExecuteMsg::SetGuard { guard: Some(guard) },
+            &[],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn guard_flagged_aggregator() {
+        let mut app = mock_app();
+        let flux_aggregator_addr = instantiate_feed(&mut app, "LINK/USD");
+        let backup_addr = instantiate_feed(&mut app, "LINK/USD");
+        let flags_addr = instantiate_flags(&mut app);
+        let proxy_addr = instantiate_proxy(&mut app, flux_aggregator_addr.clone());
+        submit(&mut app, &flux_aggregator_addr, 1, 100);
+        submit(&mut app, &backup_addr, 1, 500);
+
+        let guard = GuardMsg {
+            flags: Some(flags_addr.to_string()),
+            backup: None,
+            flagged_policy: Policy::Serve,
+            stale_policy: Policy::Serve,
+            max_staleness: None,
+        };
+        let res = app
+            .execute_contract(
+                Addr::unchecked(OWNER),
+                proxy_addr.clone(),
+                &ExecuteMsg::SetGuard {
+                    guard: Some(guard.clone()),
+                },
+                &[],
+            )
+            .unwrap();
+        assert!(res.has_event(
+            &Event::new("wasm-set_guard")
+                .add_attribute("enabled", "true")
+                .add_attribute("flagged_policy", "serve")
+                .add_attribute("stale_policy", "serve")
+                .add_attribute("flags", flags_addr.to_string())
+        ));
+        let query = |app: &App| -> StdResult<GuardedRoundDataResponse> {
+            app.wrap()
+                .query_wasm_smart(&proxy_addr, &QueryMsg::GetGuardedLatestRoundData {})
+        };
+        let res = query(&app).unwrap();
+        assert_eq!(res.source, DataSource::Current);
+        assert!(!res.flagged);
+
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            flags_addr.clone(),
+            &flags::msg::ExecuteMsg::RaiseFlag {
+                subject: flux_aggregator_addr.to_string(),
+                ttl: None,
+            },
+            &[],
+        )
+        .unwrap();
+
+        // served with a warning
+        let res = query(&app).unwrap();
+        assert_eq!(res.source, DataSource::Current);
+        assert_eq!(res.aggregator, flux_aggregator_addr);
+        assert_eq!(res.round_data.answer, Some(Uint128::new(100)));
+        assert!(res.flagged);
+        assert!(!res.stale);
+
+        set_guard(
+            &mut app,
+            &proxy_addr,
+            GuardMsg {
+                flagged_policy: Policy::Error,
+                ..guard.clone()
+            },
+        );
+        let err = query(&app).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains(&ContractError::AggregatorFlagged {}.to_string()));
+        let res: StdResult<RoundDataResponse> = app
+            .wrap()
+            .query_wasm_smart(&proxy_addr, &GetLatestRoundData {}.wrap());
+        assert!(res.is_err());
+
+        // no backup configured and no previous phase
+        set_guard(
+            &mut app,
+            &proxy_addr,
+            GuardMsg {
+                flagged_policy: Policy::Backup,
+                ..guard.clone()
+            },
+        );
+        let err = query(&app).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains(&ContractError::NoBackupAggregator {}.to_string()));
+
+        set_guard(
+            &mut app,
+            &proxy_addr,
+            GuardMsg {
+                backup: Some(backup_addr.to_string()),
+                flagged_policy: Policy::Backup,
+                ..guard.clone()
+            },
+        );
+        let res = query(&app).unwrap();
+        assert_eq!(res.source, DataSource::Backup);
+        assert_eq!(res.aggregator, backup_addr);
+        assert_eq!(
+            res.round_data.round_id,
+            compose_round_id(BACKUP_PHASE_ID, 1)
+        );
+        assert_eq!(res.round_data.answer, Some(Uint128::new(500)));
+        assert!(res.flagged);
+
+        let res: LatestAnswerResponse = app
+            .wrap()
+            .query_wasm_smart(&proxy_addr, &GetLatestAnswer {}.wrap())
+            .unwrap();
+        assert_eq!(res, LatestAnswerResponse(Some(Uint128::new(500))));
+
+        // backup round IDs don't collide with the current phase's
+        let round = |app: &App, round_id| -> RoundDataResponse {
+            app.wrap()
+                .query_wasm_smart(&proxy_addr, &GetRoundData { round_id }.wrap())
+                .unwrap()
+        };
+        assert_eq!(round(&app, 1).answer, Some(Uint128::new(100)));
+        assert_eq!(
+            round(&app, compose_round_id(BACKUP_PHASE_ID, 1)).answer,
+            Some(Uint128::new(500))
+        );
+
+        // a stale backup isn't served
+        set_guard(
+            &mut app,
+            &proxy_addr,
+            GuardMsg {
+                backup: Some(backup_addr.to_string()),
+                flagged_policy: Policy::Backup,
+                max_staleness: Some(10),
+                ..guard.clone()
+            },
+        );
+        app.update_block(|block| block.time = block.time.plus_seconds(11));
+        let err = query(&app).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains(&ContractError::StaleAnswer {}.to_string()));
+
+        // nor a flagged one
+        app.execute_contract(
+            Addr::unchecked(OWNER),
+            flags_addr.clone(),
+            &flags::msg::ExecuteMsg::RaiseFlag {
+                subject: backup_addr.to_string(),
+                ttl: None,
+            },
+            &[],
+        )
+        .unwrap();
+        set_guard(
+            &mut app,
+            &proxy_addr,
+            GuardMsg {
+                backup: Some(backup_addr.to_string()),
+                flagged_policy: Policy::Backup,
+                ..guard
+            },
+        );
+        let err = query(&app).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains(&ContractError::AggregatorFlagged {}.to_string()));
+
+        let res = app
+            .execute_contract(
+                Addr::unchecked(OWNER),
+                proxy_addr.clone(),
+                &ExecuteMsg::SetGuard { guard: None },
+                &[],
+            )
+            .unwrap();
+        assert!(res.has_event(&Event::new("wasm-set_guard").add_attribute("enabled", "false")));
+        let res = query(&app).unwrap();
+        assert_eq!(res.source, DataSource::Current);
+        assert!(!res.flagged);
+    }
+
+    #[test]
+    fn guard_stale_answer_falls_back_to_previous_phase() {
+        let mut app = mock_app();
+        let flux_aggregator_addr = instantiate_feed(&mut app, "LINK/USD");
+        let flux_aggregator_addr2 = instantiate_feed(&mut app, "LINK/USD");
+        let proxy_addr = instantiate_proxy(&mut app, flux_aggregator_addr.clone());
+        submit(&mut app, &flux_aggregator_addr, 1, 100);
+        upgrade(&mut app, &proxy_addr, &flux_aggregator_addr2);
+        submit(&mut app, &flux_aggregator_addr2, 1, 200);
+
+        let guard = GuardMsg {
+            flags: None,
+            backup: None,
+            flagged_policy: Policy::Serve,
+            stale_policy: Policy::Backup,
+            max_staleness: Some(10),
+        };
+        set_guard(&mut app, &proxy_addr, guard.clone());
+        let query = |app: &App| -> StdResult<GuardedRoundDataResponse> {
+            app.wrap()
+                .query_wasm_smart(&proxy_addr, &QueryMsg::GetGuardedLatestRoundData {})
+        };
+
+        app.update_block(|block| block.time = block.time.plus_seconds(10));
+        let res = query(&app).unwrap();
+        assert_eq!(res.source, DataSource::Current);
+        assert_eq!(res.round_data.round_id, compose_round_id(2, 1));
+        assert!(!res.stale);
+
+        // the previous phase's answer is no newer
+        app.update_block(|block| block.time = block.time.plus_seconds(1));
+        let err = query(&app).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains(&ContractError::StaleAnswer {}.to_string()));
+
+        submit(&mut app, &flux_aggregator_addr, 2, 110);
+        let res = query(&app).unwrap();
+        assert_eq!(res.source, DataSource::Backup);
+        assert_eq!(res.aggregator, flux_aggregator_addr);
+        assert_eq!(res.round_data.round_id, compose_round_id(1, 2));
+        assert_eq!(res.round_data.answer, Some(Uint128::new(110)));
+        assert!(res.stale);
+
+        set_guard(
+            &mut app,
+            &proxy_addr,
+            GuardMsg {
+                stale_policy: Policy::Error,
+                ..guard
+            },
+        );
+        let err = query(&app).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains(&ContractError::StaleAnswer {}.to_string()));
+    }
 }
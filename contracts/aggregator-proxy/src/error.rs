@@ -17,7 +17,31 @@ pub enum ContractError {
     #[error("No proposed aggregator present")]
     NoProposedAggregator {},
 
-    /// Only callable by owner
-    #[error("Only callable by owner")]
-    NotOwner {},
+    /// Round ID doesn't fit the phase and aggregator round layout
+    #[error("Invalid round ID")]
+    InvalidRoundId {},
+
+    /// Confirmation delay of the proposed aggregator hasn't passed
+    #[error("Proposed aggregator can't be confirmed yet")]
+    ConfirmationDelayNotPassed {},
+
+    /// Latest answers of the current and proposed aggregator diverge too much
+    #[error("Proposed aggregator diverges too much from the current one")]
+    ProposalDivergenceTooHigh {},
+
+    /// Divergence of the proposed aggregator can't be determined
+    #[error("Proposed aggregator divergence unavailable")]
+    ProposalDivergenceUnavailable {},
+
+    /// The current aggregator is flagged
+    #[error("Aggregator is flagged")]
+    AggregatorFlagged {},
+
+    /// The current aggregator's latest answer is stale
+    #[error("Aggregator answer is stale")]
+    StaleAnswer {},
+
+    /// Neither a backup aggregator nor a previous phase is available
+    #[error("No backup aggregator available")]
+    NoBackupAggregator {},
 }
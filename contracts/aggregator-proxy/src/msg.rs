@@ -1,36 +1,191 @@
-use cosmwasm_std::Addr;
+use chainlink_aggregator::RoundDataResponse;
+use cosmwasm_std::{Addr, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::state::{PhaseInfo, Policy};
+
 pub type PhaseAggregators = Vec<(u16, Addr)>;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     pub aggregator: String,
+    /// Minimum seconds between proposing and confirming an aggregator, defaults to 0
+    pub confirmation_delay: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    ProposeAggregator { aggregator: String },
-    ConfirmAggregator { aggregator: String },
+    ProposeAggregator {
+        aggregator: String,
+    },
+    ConfirmAggregator {
+        aggregator: String,
+    },
+    CancelProposedAggregator {},
+    /// Only applies to aggregators proposed afterwards. A shorter delay takes effect once
+    /// the current delay passed
+    SetConfirmationDelay {
+        delay: u64,
+    },
+    /// Refuse confirming aggregators whose latest answer diverges more than `divergence`
+    /// (100000 being 100%) from the current one. `None` removes the bound
+    SetMaxProposalDivergence {
+        divergence: Option<u32>,
+    },
+    /// Configures how flagged or stale data is served. `None` serves it unchecked
+    SetGuard {
+        guard: Option<GuardMsg>,
+    },
     // owned
-    TransferOwnership { to: Addr },
+    TransferOwnership {
+        to: Addr,
+        expires_at: Option<u64>,
+    },
     AcceptOwnership {},
+    CancelOwnershipTransfer {},
+    RenounceOwnership {},
+    GrantRole {
+        role: String,
+        account: String,
+    },
+    RevokeRole {
+        role: String,
+        account: String,
+    },
+    RenounceRole {
+        role: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GuardMsg {
+    /// Flags contract checked for flags raised on the current aggregator
+    pub flags: Option<String>,
+    /// Backup aggregator, the previous phase's aggregator is used if not set
+    pub backup: Option<String>,
+    pub flagged_policy: Policy,
+    pub stale_policy: Policy,
+    /// Seconds after which the latest answer is stale
+    pub max_staleness: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     GetPhaseAggregators {},
-    GetProposedRoundData { round_id: u32 },
+    GetProposedRoundData {
+        round_id: u64,
+    },
     GetProposedLatestRoundData {},
     GetProposedAggregator {},
+    /// Response: [`ConfirmationTimeResponse`]
+    GetEarliestConfirmation {},
+    GetConfirmationDelay {},
+    /// Latest `rounds` rounds of the current and proposed aggregator side by side.
+    /// Response: [`ProposalComparisonResponse`]
+    GetProposalComparison {
+        rounds: u32,
+    },
+    GetMaxProposalDivergence {},
+    GetGuard {},
+    /// Latest round data with the configured guard applied.
+    /// Response: [`GuardedRoundDataResponse`]
+    GetGuardedLatestRoundData {},
     GetAggregator {},
     GetPhaseId {},
+    /// Round data of the aggregator of the given phase, with phased round IDs
+    GetPhaseRoundData {
+        phase_id: u16,
+        round_id: u32,
+    },
+    /// Response: [`PhaseInfoResponse`]
+    GetPhaseInfo {
+        phase_id: u16,
+    },
+    /// Phases in ascending order. Response: [`PhasesResponse`]
+    GetPhases {
+        start_after: Option<u16>,
+        limit: Option<u32>,
+    },
     AggregatorQuery(chainlink_aggregator::QueryMsg),
     // owned
     GetOwner {},
+    GetOwnershipState {},
+    HasRole {
+        role: String,
+        account: String,
+    },
+    GetRoleMembers {
+        role: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfirmationTimeResponse {
+    pub aggregator: Addr,
+    /// Earliest block time the aggregator can be confirmed at, in seconds
+    pub earliest_confirmation: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoundComparison {
+    pub current: Option<RoundDataResponse>,
+    pub proposed: Option<RoundDataResponse>,
+    /// Absolute difference of the answers, in the current aggregator's decimals
+    pub absolute_divergence: Option<Uint128>,
+    /// Difference relative to the current answer, 100000 being 100%
+    pub relative_divergence: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProposalComparisonResponse {
+    pub current_aggregator: Addr,
+    pub proposed_aggregator: Addr,
+    pub current_decimals: u8,
+    pub proposed_decimals: u8,
+    /// Answered rounds, most recent first
+    pub rounds: Vec<RoundComparison>,
+    /// Seconds since the current aggregator's latest update
+    pub current_staleness: Option<u64>,
+    /// Seconds since the proposed aggregator's latest update
+    pub proposed_staleness: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DataSource {
+    Current,
+    Backup,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GuardedRoundDataResponse {
+    /// Round data, round IDs of the backup aggregator carry `BACKUP_PHASE_ID`
+    pub round_data: RoundDataResponse,
+    pub source: DataSource,
+    /// Aggregator the round data was read from
+    pub aggregator: Addr,
+    /// The current aggregator is flagged
+    pub flagged: bool,
+    /// The current aggregator's latest answer is stale
+    pub stale: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PhaseInfoResponse {
+    pub phase_id: u16,
+    pub aggregator: Addr,
+    /// Boundaries are only known for phases started after they were recorded
+    pub info: Option<PhaseInfo>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PhasesResponse {
+    pub phases: Vec<PhaseInfoResponse>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
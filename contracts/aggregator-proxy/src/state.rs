@@ -1,3 +1,5 @@
+use std::fmt;
+
 use cosmwasm_std::Addr;
 use cw_storage_plus::{Item, Map, U16Key};
 use schemars::JsonSchema;
@@ -9,6 +11,72 @@ pub struct Phase {
     pub aggregator_addr: Addr,
 }
 
+/// Phase boundaries, recorded when a phase starts and ends
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PhaseInfo {
+    /// Block time the phase started at, in seconds
+    pub started_at: u64,
+    /// Latest proxied round of the aggregator when the phase started
+    pub first_round_id: Option<u64>,
+    /// Block time the phase ended at, in seconds
+    pub ended_at: Option<u64>,
+    /// Latest proxied round of the aggregator when the phase ended
+    pub last_round_id: Option<u64>,
+}
+
+/// How the proxy treats flagged or stale data of the current aggregator
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Policy {
+    /// Serve the data, guarded queries report the condition
+    Serve,
+    /// Serve the backup aggregator's data instead, failing if there is none
+    Backup,
+    /// Fail the query
+    Error,
+}
+
+impl fmt::Display for Policy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Policy::Serve => write!(f, "serve"),
+            Policy::Backup => write!(f, "backup"),
+            Policy::Error => write!(f, "error"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Guard {
+    /// Flags contract checked for flags raised on the current aggregator
+    pub flags: Option<Addr>,
+    /// Backup aggregator, the previous phase's aggregator is used if not set
+    pub backup: Option<Addr>,
+    pub flagged_policy: Policy,
+    pub stale_policy: Policy,
+    /// Seconds after which the latest answer is stale, answers never go stale if not set
+    pub max_staleness: Option<u64>,
+}
+
+/// Shorter confirmation delay, taking effect once the previous delay passed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DelayDecrease {
+    pub delay: u64,
+    /// Block time the delay takes effect at, in seconds
+    pub effective_at: u64,
+}
+
 pub const CURRENT_PHASE: Item<Phase> = Item::new("current_phase");
 pub const PROPOSED_AGGREGATOR: Item<Addr> = Item::new("proposed_aggregator");
+/// Minimum seconds between proposing and confirming an aggregator
+pub const CONFIRMATION_DELAY: Item<u64> = Item::new("confirmation_delay");
+pub const PENDING_DELAY_DECREASE: Item<DelayDecrease> = Item::new("pending_delay_decrease");
+/// Earliest block time the proposed aggregator can be confirmed at, in seconds
+pub const EARLIEST_CONFIRMATION: Item<u64> = Item::new("earliest_confirmation");
+/// Maximum relative divergence between the latest answers of the current and proposed
+/// aggregator allowing confirmation, `THRESHOLD_MULTIPLIER` being 100%
+pub const MAX_PROPOSAL_DIVERGENCE: Item<u32> = Item::new("max_proposal_divergence");
+/// Handling of flagged and stale data, latest round data is served unchecked if not set
+pub const GUARD: Item<Guard> = Item::new("guard");
 pub const PHASE_AGGREGATORS: Map<U16Key, Addr> = Map::new("phase_aggreagtors");
+pub const PHASE_INFO: Map<U16Key, PhaseInfo> = Map::new("phase_info");
@@ -38,7 +38,7 @@ contract!(
                 answered_in_round: latest_round.answered_in_round
             })
         }
-        GetRoundData (round_id: u32) {
+        GetRoundData (round_id: u64) {
             let round_data: RoundDataResponse = deps.querier.query_wasm_smart(
                 state.proxy_contract,
                 &ProxyQuery::AggregatorQuery(GetRoundData{ round_id })
@@ -100,11 +100,11 @@ contract!(
 
     [Response] {
         RoundDataResponse {
-            round_id: u32,
+            round_id: u64,
             answer: Option<Uint128>,
             started_at: Option<u64>,
             updated_at: Option<u64>,
-            answered_in_round: u32
+            answered_in_round: u64
         }
 
         Answer {value: Option<Uint128>}
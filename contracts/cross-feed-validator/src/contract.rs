@@ -0,0 +1,509 @@
+use std::convert::TryFrom;
+
+use chainlink_aggregator::{FeedQuerier, Price, Rounding};
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128,
+    Uint256, WasmMsg,
+};
+use median::calculate_median;
+
+use crate::error::ContractError;
+use crate::msg::*;
+use crate::state::*;
+
+use flags::msg::ExecuteMsg as FlagsMsg;
+use owned::contract::{
+    execute_accept_ownership, execute_cancel_ownership_transfer, execute_renounce_ownership,
+    execute_transfer_ownership, get_owner, get_ownership_state, instantiate as owned_init,
+};
+use owned::roles::{
+    execute_grant_role, execute_renounce_role, execute_revoke_role, has_role, query_role_members,
+    validate_role, CONFIG_ADMIN,
+};
+
+static THRESHOLD_MULTIPLIER: u128 = 100000;
+
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let flags = deps.api.addr_validate(&msg.flags)?;
+    CONFIG.save(deps.storage, &State { flags })?;
+    owned_init(deps, env, info, owned::msg::InstantiateMsg {})?;
+
+    Ok(Response::default())
+}
+
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::SetFlagsAddress { flags } => execute_set_flags_address(deps, env, info, flags),
+        ExecuteMsg::SetAggregatorConfig {
+            aggregator,
+            reference_feeds,
+            max_divergence,
+            max_staleness,
+        } => execute_set_aggregator_config(
+            deps,
+            env,
+            info,
+            aggregator,
+            reference_feeds,
+            max_divergence,
+            max_staleness,
+        ),
+        ExecuteMsg::RemoveAggregator { aggregator } => {
+            execute_remove_aggregator(deps, env, info, aggregator)
+        }
+        ExecuteMsg::Validate {
+            round_id, answer, ..
+        } => execute_validate(deps, env, info, round_id, answer),
+        ExecuteMsg::TransferOwnership { to, expires_at } => {
+            execute_transfer_ownership(deps, env, info, to, expires_at).map_err(ContractError::from)
+        }
+        ExecuteMsg::AcceptOwnership {} => {
+            execute_accept_ownership(deps, env, info).map_err(ContractError::from)
+        }
+        ExecuteMsg::CancelOwnershipTransfer {} => {
+            execute_cancel_ownership_transfer(deps, env, info).map_err(ContractError::from)
+        }
+        ExecuteMsg::RenounceOwnership {} => {
+            execute_renounce_ownership(deps, env, info).map_err(ContractError::from)
+        }
+        ExecuteMsg::GrantRole { role, account } => {
+            execute_grant_role(deps, env, info, role, account).map_err(ContractError::from)
+        }
+        ExecuteMsg::RevokeRole { role, account } => {
+            execute_revoke_role(deps, env, info, role, account).map_err(ContractError::from)
+        }
+        ExecuteMsg::RenounceRole { role } => {
+            execute_renounce_role(deps, env, info, role).map_err(ContractError::from)
+        }
+    }
+}
+
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::GetFlagsAddress {} => to_binary(&CONFIG.load(deps.storage)?.flags),
+        QueryMsg::GetAggregatorConfig { aggregator } => {
+            to_binary(&query_aggregator_config(deps, aggregator)?)
+        }
+        QueryMsg::GetDivergence { aggregator } => to_binary(&query_divergence(deps, aggregator)?),
+        QueryMsg::GetOwner {} => to_binary(&get_owner(deps)?),
+        QueryMsg::GetOwnershipState {} => to_binary(&get_ownership_state(deps)?),
+        QueryMsg::HasRole { role, account } => {
+            to_binary(&has_role(deps, &role, &deps.api.addr_validate(&account)?)?)
+        }
+        QueryMsg::GetRoleMembers {
+            role,
+            start_after,
+            limit,
+        } => to_binary(&query_role_members(deps, role, start_after, limit)?),
+    }
+}
+
+pub fn execute_validate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    round_id: u32,
+    answer: Uint128,
+) -> Result<Response, ContractError> {
+    let response = Response::new().add_attribute("action", "validate");
+    let config = match AGGREGATORS.may_load(deps.storage, &info.sender)? {
+        Some(config) => config,
+        None => return Ok(response.add_attribute("unregistered", true.to_string())),
+    };
+
+    // reference feeds without a fresh answer can't be compared to
+    let now = env.block.time.seconds();
+    let reference_answer = match reference_answer(deps.as_ref(), &config, now) {
+        Some(reference_answer) if !reference_answer.is_zero() => reference_answer,
+        _ => return Ok(response.add_attribute("reference_unavailable", true.to_string())),
+    };
+
+    let change = if reference_answer > answer {
+        reference_answer - answer
+    } else {
+        answer - reference_answer
+    };
+    let divergence = change.full_mul(THRESHOLD_MULTIPLIER) / Uint256::from(reference_answer);
+    // divergences too large for `Uint128` are invalid either way
+    let divergence = Uint128::try_from(divergence).unwrap_or(Uint128::MAX);
+    let is_valid = divergence <= Uint128::from(config.max_divergence);
+
+    DIVERGENCES.save(
+        deps.storage,
+        &info.sender,
+        &Divergence {
+            round_id,
+            answer,
+            reference_answer,
+            divergence,
+            validated_at: env.block.time.seconds(),
+        },
+    )?;
+
+    let mut response = response
+        .add_attribute("reference_answer", reference_answer)
+        .add_attribute("divergence", divergence)
+        .add_attribute("is_valid", is_valid.to_string())
+        .set_data(to_binary(&is_valid)?);
+    if !is_valid {
+        let flags = CONFIG.load(deps.storage)?.flags;
+        response = response.add_message(WasmMsg::Execute {
+            contract_addr: String::from(flags),
+            msg: to_binary(&FlagsMsg::RaiseFlag {
+                subject: info.sender.to_string(),
+                ttl: None,
+            })?,
+            funds: vec![],
+        });
+    }
+
+    Ok(response)
+}
+
+pub fn execute_set_flags_address(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    flags: String,
+) -> Result<Response, ContractError> {
+    validate_role(deps.as_ref(), &info, CONFIG_ADMIN)?;
+    let flags = deps.api.addr_validate(&flags)?;
+    let previous = CONFIG.load(deps.storage)?.flags;
+    CONFIG.save(deps.storage, &State { flags })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "flags_address_updated")
+        .add_attribute("previous", previous))
+}
+
+pub fn execute_set_aggregator_config(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    aggregator: String,
+    reference_feeds: Vec<String>,
+    max_divergence: u32,
+    max_staleness: u64,
+) -> Result<Response, ContractError> {
+    validate_role(deps.as_ref(), &info, CONFIG_ADMIN)?;
+    if reference_feeds.is_empty() {
+        return Err(ContractError::NoReferenceFeeds {});
+    }
+    let aggregator = deps.api.addr_validate(&aggregator)?;
+    let reference_feeds = reference_feeds
+        .iter()
+        .map(|feed| deps.api.addr_validate(feed))
+        .collect::<StdResult<Vec<Addr>>>()?;
+    let decimals = deps.querier.get_decimals(aggregator.clone())?;
+
+    AGGREGATORS.save(
+        deps.storage,
+        &aggregator,
+        &AggregatorConfig {
+            reference_feeds,
+            max_divergence,
+            max_staleness,
+            decimals,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "aggregator_config_updated")
+        .add_attribute("aggregator", aggregator))
+}
+
+pub fn execute_remove_aggregator(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    aggregator: String,
+) -> Result<Response, ContractError> {
+    validate_role(deps.as_ref(), &info, CONFIG_ADMIN)?;
+    let aggregator = deps.api.addr_validate(&aggregator)?;
+    AGGREGATORS.remove(deps.storage, &aggregator);
+    DIVERGENCES.remove(deps.storage, &aggregator);
+
+    Ok(Response::new()
+        .add_attribute("action", "aggregator_removed")
+        .add_attribute("aggregator", aggregator))
+}
+
+/// Median of the reference feeds' latest answers in the aggregator's decimals.
+/// Feeds that fail to answer or whose answer is stale are left out so they can't
+/// block submissions or get healthy aggregators flagged
+fn reference_answer(deps: Deps, config: &AggregatorConfig, now: u64) -> Option<Uint128> {
+    let mut answers = config
+        .reference_feeds
+        .iter()
+        .filter_map(|feed| {
+            let round = deps.querier.get_latest_round_data(feed.clone()).ok()?;
+            let decimals = deps.querier.get_decimals(feed.clone()).ok()?;
+            Price::from_fresh_round(&round, decimals, now, config.max_staleness)
+                .and_then(|price| price.rescale(config.decimals, Rounding::Down))
+                .ok()
+        })
+        .map(|price| price.value.u128())
+        .collect::<Vec<u128>>();
+
+    calculate_median(&mut answers).ok().map(Uint128::new)
+}
+
+pub fn query_aggregator_config(deps: Deps, aggregator: String) -> StdResult<AggregatorConfig> {
+    let aggregator = deps.api.addr_validate(&aggregator)?;
+    AGGREGATORS.load(deps.storage, &aggregator)
+}
+
+pub fn query_divergence(deps: Deps, aggregator: String) -> StdResult<Option<Divergence>> {
+    let aggregator = deps.api.addr_validate(&aggregator)?;
+    DIVERGENCES.may_load(deps.storage, &aggregator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chainlink_aggregator::{QueryMsg as FeedQueryMsg, RoundDataResponse};
+    use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+    use cosmwasm_std::{
+        attr, from_slice, ContractResult, CosmosMsg, Empty, OwnedDeps, Querier, QuerierResult,
+        QueryRequest, SystemError, SystemResult, WasmQuery,
+    };
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct FeedQuery {
+        aggregator_query: FeedQueryMsg,
+    }
+
+    /// Mocks feeds named after their decimals and answer, e.g. "feed_8_100",
+    /// answering at block time unless suffixed with "_stale"
+    struct FeedsQuerier;
+
+    impl Querier for FeedsQuerier {
+        fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+            let (contract_addr, msg) = match from_slice(bin_request).unwrap() {
+                QueryRequest::<Empty>::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                    (contract_addr, msg)
+                }
+                _ => unimplemented!(),
+            };
+            let parts: Vec<&str> = contract_addr.split('_').collect();
+            if parts.len() != 3 && parts.len() != 4 {
+                return SystemResult::Err(SystemError::NoSuchContract {
+                    addr: contract_addr,
+                });
+            }
+            let decimals: u8 = parts[1].parse().unwrap();
+            let answer: u128 = parts[2].parse().unwrap();
+            let updated_at = if parts.len() == 4 {
+                0
+            } else {
+                mock_env().block.time.seconds()
+            };
+            let query: FeedQuery = from_slice(&msg).unwrap();
+            let response = match query.aggregator_query {
+                FeedQueryMsg::GetDecimals {} => to_binary(&decimals),
+                FeedQueryMsg::GetLatestRoundData {} => to_binary(&RoundDataResponse {
+                    round_id: 1,
+                    answer: Some(Uint128::new(answer)),
+                    started_at: Some(updated_at),
+                    updated_at: Some(updated_at),
+                    answered_in_round: 1,
+                }),
+                _ => unimplemented!(),
+            };
+            SystemResult::Ok(ContractResult::Ok(response.unwrap()))
+        }
+    }
+
+    fn setup(reference_feeds: Vec<&str>) -> OwnedDeps<MockStorage, MockApi, FeedsQuerier> {
+        let mut deps = OwnedDeps {
+            storage: MockStorage::default(),
+            api: MockApi::default(),
+            querier: FeedsQuerier,
+        };
+
+        let msg = InstantiateMsg {
+            flags: "flags".to_string(),
+        };
+        let info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let msg = ExecuteMsg::SetAggregatorConfig {
+            aggregator: "feed_8_0".to_string(),
+            reference_feeds: reference_feeds.iter().map(|f| f.to_string()).collect(),
+            max_divergence: 1000,
+            max_staleness: 3600,
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        deps
+    }
+
+    fn validate(deps: DepsMut, answer: u128) -> Response {
+        let msg = ExecuteMsg::Validate {
+            previous_round_id: 0,
+            previous_answer: Uint128::zero(),
+            round_id: 1,
+            answer: Uint128::new(answer),
+        };
+        execute(deps, mock_env(), mock_info("feed_8_0", &[]), msg).unwrap()
+    }
+
+    #[test]
+    fn registering_aggregators() {
+        let mut deps = setup(vec!["feed_8_100"]);
+
+        let config = query_aggregator_config(deps.as_ref(), "feed_8_0".to_string()).unwrap();
+        assert_eq!(
+            AggregatorConfig {
+                reference_feeds: vec![Addr::unchecked("feed_8_100")],
+                max_divergence: 1000,
+                max_staleness: 3600,
+                decimals: 8,
+            },
+            config
+        );
+
+        let msg = ExecuteMsg::SetAggregatorConfig {
+            aggregator: "feed_8_0".to_string(),
+            reference_feeds: vec![],
+            max_divergence: 1000,
+            max_staleness: 3600,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg);
+        assert!(matches!(res, Err(ContractError::NoReferenceFeeds {})));
+
+        let msg = ExecuteMsg::RemoveAggregator {
+            aggregator: "feed_8_0".to_string(),
+        };
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("someone", &[]),
+            msg.clone(),
+        );
+        assert!(matches!(
+            res,
+            Err(ContractError::OwnedError(
+                owned::error::ContractError::MissingRole { .. }
+            ))
+        ));
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+
+        let msg = ExecuteMsg::Validate {
+            previous_round_id: 0,
+            previous_answer: Uint128::zero(),
+            round_id: 1,
+            answer: Uint128::new(100),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("feed_8_0", &[]), msg).unwrap();
+        assert_eq!(
+            vec![attr("action", "validate"), attr("unregistered", "true")],
+            res.attributes
+        );
+        assert!(res.messages.is_empty());
+        let divergence = query_divergence(deps.as_ref(), "feed_8_0".to_string()).unwrap();
+        assert_eq!(divergence, None);
+    }
+
+    #[test]
+    fn validate_against_reference_feeds() {
+        // reference answers 1.00, 1.02 and 1.50 in different decimals
+        let mut deps = setup(vec![
+            "feed_6_1000000",
+            "feed_8_102000000",
+            "feed_18_1500000000000000000",
+        ]);
+
+        let res = validate(deps.as_mut(), 101000000);
+        assert_eq!(0, res.messages.len());
+        assert_eq!(
+            vec![
+                attr("action", "validate"),
+                attr("reference_answer", "102000000"),
+                attr("divergence", "980"),
+                attr("is_valid", "true"),
+            ],
+            res.attributes
+        );
+
+        let res = validate(deps.as_mut(), 90000000);
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "flags".to_string(),
+                msg: to_binary(&FlagsMsg::RaiseFlag {
+                    subject: "feed_8_0".to_string(),
+                    ttl: None,
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+
+        let divergence = query_divergence(deps.as_ref(), "feed_8_0".to_string())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            Divergence {
+                round_id: 1,
+                answer: Uint128::new(90000000),
+                reference_answer: Uint128::new(102000000),
+                divergence: Uint128::new(11764),
+                validated_at: mock_env().block.time.seconds(),
+            },
+            divergence
+        );
+    }
+
+    #[test]
+    fn unavailable_reference_feeds_are_skipped() {
+        let mut deps = setup(vec!["missing", "feed_8_100000000"]);
+
+        let res = validate(deps.as_mut(), 100000000);
+        assert_eq!(attr("is_valid", "true"), res.attributes[3]);
+
+        let mut deps = setup(vec!["missing"]);
+        let res = validate(deps.as_mut(), 100000000);
+        assert_eq!(
+            vec![
+                attr("action", "validate"),
+                attr("reference_unavailable", "true"),
+            ],
+            res.attributes
+        );
+        assert_eq!(
+            None,
+            query_divergence(deps.as_ref(), "feed_8_0".to_string()).unwrap()
+        );
+    }
+
+    #[test]
+    fn stale_reference_feeds_are_skipped() {
+        // the stale feed would flag the aggregator
+        let mut deps = setup(vec!["feed_8_50000000_stale", "feed_8_100000000"]);
+        let res = validate(deps.as_mut(), 100000000);
+        assert_eq!(attr("is_valid", "true"), res.attributes[3]);
+
+        let mut deps = setup(vec!["feed_8_50000000_stale"]);
+        let res = validate(deps.as_mut(), 100000000);
+        assert_eq!(attr("reference_unavailable", "true"), res.attributes[1]);
+    }
+
+    #[test]
+    fn huge_divergence_does_not_overflow() {
+        let mut deps = setup(vec!["feed_8_1"]);
+        let res = validate(deps.as_mut(), u128::MAX);
+        assert_eq!(attr("divergence", Uint128::MAX), res.attributes[2]);
+        assert_eq!(attr("is_valid", "false"), res.attributes[3]);
+    }
+}
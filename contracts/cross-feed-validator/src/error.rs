@@ -0,0 +1,13 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+    /// Aggregators need at least one feed to be compared to
+    #[error("At least one reference feed is required")]
+    NoReferenceFeeds {},
+    #[error("{0}")]
+    OwnedError(#[from] owned::error::ContractError),
+}
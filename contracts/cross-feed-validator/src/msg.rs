@@ -0,0 +1,98 @@
+use cosmwasm_std::Uint128;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The address of the flags contract
+    pub flags: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Initiate contract ownership transfer to another address.
+    /// Can be used only by owner
+    TransferOwnership {
+        /// Address to transfer ownership to
+        to: String,
+        /// Block time in seconds after which the transfer can no longer be accepted
+        expires_at: Option<u64>,
+    },
+    /// Finish contract ownership transfer. Can be used only by pending owner
+    AcceptOwnership {},
+    /// Cancel the pending ownership transfer. Can be used only by owner
+    CancelOwnershipTransfer {},
+    /// Leave the contract without owner, disabling functionality restricted to the owner.
+    /// Can be used only by owner
+    RenounceOwnership {},
+    /// Grant a role, see `owned::roles`. Can be used only by owner
+    GrantRole { role: String, account: String },
+    /// Revoke a granted role. Can be used only by owner
+    RevokeRole { role: String, account: String },
+    /// Give up a role granted to the sender
+    RenounceRole { role: String },
+    /// Updates the flagging contract address for raising flags
+    /// Can be used only by owner
+    SetFlagsAddress { flags: String },
+    /// Registers an aggregator with the feeds its answers are compared to.
+    /// The aggregator's decimals are queried when registering
+    /// Can be used only by owner
+    SetAggregatorConfig {
+        /// Address of the aggregator
+        aggregator: String,
+        /// Addresses of the reference feeds
+        reference_feeds: Vec<String>,
+        /// Tolerated divergence from the median reference answer, where
+        /// 100,000 is equivalent to a 100% divergence
+        max_divergence: u32,
+        /// Seconds after which reference answers are considered stale and left out
+        max_staleness: u64,
+    },
+    /// Removes a registered aggregator
+    /// Can be used only by owner
+    RemoveAggregator { aggregator: String },
+    /// Compares the answer to the median answer of the reference feeds,
+    /// raising a flag for the calling aggregator if they diverge too much.
+    /// Answers of unregistered aggregators are ignored, as by the deviation flagging validator
+    Validate {
+        /// ID of the previous round
+        previous_round_id: u32,
+        /// Previous answer, not used by this validator
+        previous_answer: Uint128,
+        /// ID of the current round
+        round_id: u32,
+        /// Current answer which is compared to the reference feeds
+        answer: Uint128,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Query the flags contract address
+    /// Response: [`Addr`]
+    GetFlagsAddress {},
+    /// Query the config of a registered aggregator
+    /// Response: [`AggregatorConfig`]
+    GetAggregatorConfig { aggregator: String },
+    /// Query the divergence recorded by the aggregator's last validation
+    /// Response: [`Option<Divergence>`]
+    GetDivergence { aggregator: String },
+    /// Returns contract owner's address
+    /// Response [`Addr`]
+    GetOwner {},
+    /// Returns the owner, the pending owner and the expiry of the pending transfer
+    /// Response [`owned::msg::OwnershipStateResponse`]
+    GetOwnershipState {},
+    /// Returns whether the account holds the role, which the owner always does
+    /// Response `bool`
+    HasRole { role: String, account: String },
+    /// Returns the accounts granted the role, not including the owner
+    /// Response [`owned::msg::RoleMembersResponse`]
+    GetRoleMembers {
+        role: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
@@ -0,0 +1,42 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct State {
+    pub flags: Addr,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AggregatorConfig {
+    /// Feeds the aggregator's answers are compared to
+    pub reference_feeds: Vec<Addr>,
+    /// Tolerated divergence from the median reference answer, where 100,000
+    /// is equivalent to a 100% divergence
+    pub max_divergence: u32,
+    /// Seconds after which reference answers are considered stale and left out
+    pub max_staleness: u64,
+    /// Decimals of the aggregator's answers, reference answers are scaled to them
+    pub decimals: u8,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Divergence {
+    /// ID of the validated round
+    pub round_id: u32,
+    /// Answer of the validated round
+    pub answer: Uint128,
+    /// Median answer of the reference feeds, in the aggregator's decimals
+    pub reference_answer: Uint128,
+    /// Divergence from the reference answer, where 100,000 is equivalent to 100%
+    pub divergence: Uint128,
+    /// Block time of the validation in seconds
+    pub validated_at: u64,
+}
+
+pub const CONFIG: Item<State> = Item::new("config");
+pub const AGGREGATORS: Map<&Addr, AggregatorConfig> = Map::new("aggregators");
+/// Divergence recorded by the last validation of each aggregator
+pub const DIVERGENCES: Map<&Addr, Divergence> = Map::new("divergences");
@@ -0,0 +1,379 @@
+use std::convert::TryFrom;
+
+use chainlink_aggregator::{FeedQuerier, LatestAnswerResponse, QueryMsg::*, RoundDataResponse};
+use cosmwasm_std::{
+    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Uint128,
+    Uint256,
+};
+
+use crate::error::ContractError;
+use crate::msg::*;
+use crate::state::*;
+
+use owned::contract::{
+    execute_accept_ownership, execute_cancel_ownership_transfer, execute_renounce_ownership,
+    execute_transfer_ownership, get_owner, get_ownership_state, instantiate as owned_init,
+};
+use owned::roles::{
+    execute_grant_role, execute_renounce_role, execute_revoke_role, has_role, query_role_members,
+    validate_role, CONFIG_ADMIN,
+};
+
+static VERSION: Uint128 = Uint128::new(1);
+
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let inputs = validate_inputs(deps.as_ref(), msg.inputs)?;
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            description: msg.description,
+            decimals: msg.decimals,
+            inputs,
+        },
+    )?;
+    owned_init(deps, env, info, owned::msg::InstantiateMsg {})?;
+
+    Ok(Response::default())
+}
+
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::SetInputs { inputs } => execute_set_inputs(deps, env, info, inputs),
+        ExecuteMsg::TransferOwnership { to, expires_at } => {
+            execute_transfer_ownership(deps, env, info, to, expires_at).map_err(ContractError::from)
+        }
+        ExecuteMsg::AcceptOwnership {} => {
+            execute_accept_ownership(deps, env, info).map_err(ContractError::from)
+        }
+        ExecuteMsg::CancelOwnershipTransfer {} => {
+            execute_cancel_ownership_transfer(deps, env, info).map_err(ContractError::from)
+        }
+        ExecuteMsg::RenounceOwnership {} => {
+            execute_renounce_ownership(deps, env, info).map_err(ContractError::from)
+        }
+        ExecuteMsg::GrantRole { role, account } => {
+            execute_grant_role(deps, env, info, role, account).map_err(ContractError::from)
+        }
+        ExecuteMsg::RevokeRole { role, account } => {
+            execute_revoke_role(deps, env, info, role, account).map_err(ContractError::from)
+        }
+        ExecuteMsg::RenounceRole { role } => {
+            execute_renounce_role(deps, env, info, role).map_err(ContractError::from)
+        }
+    }
+}
+
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::GetConfig {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::GetOwner {} => to_binary(&get_owner(deps)?),
+        QueryMsg::GetOwnershipState {} => to_binary(&get_ownership_state(deps)?),
+        QueryMsg::HasRole { role, account } => {
+            to_binary(&has_role(deps, &role, &deps.api.addr_validate(&account)?)?)
+        }
+        QueryMsg::GetRoleMembers {
+            role,
+            start_after,
+            limit,
+        } => to_binary(&query_role_members(deps, role, start_after, limit)?),
+        QueryMsg::AggregatorQuery(GetRoundData { round_id }) => {
+            to_binary(&get_round_data(deps, env, round_id)?)
+        }
+        QueryMsg::AggregatorQuery(GetLatestRoundData {}) => {
+            to_binary(&get_latest_round_data(deps, env)?)
+        }
+        QueryMsg::AggregatorQuery(GetDecimals {}) => {
+            to_binary(&CONFIG.load(deps.storage)?.decimals)
+        }
+        QueryMsg::AggregatorQuery(GetVersion {}) => to_binary(&VERSION),
+        QueryMsg::AggregatorQuery(GetDescription {}) => {
+            to_binary(&CONFIG.load(deps.storage)?.description)
+        }
+        QueryMsg::AggregatorQuery(GetLatestAnswer {}) => to_binary(&LatestAnswerResponse(
+            get_latest_round_data(deps, env)?.answer,
+        )),
+    }
+}
+
+pub fn execute_set_inputs(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    inputs: Vec<InputMsg>,
+) -> Result<Response, ContractError> {
+    validate_role(deps.as_ref(), &info, CONFIG_ADMIN)?;
+
+    let inputs = validate_inputs(deps.as_ref(), inputs)?;
+    CONFIG.update(deps.storage, |config| -> StdResult<_> {
+        Ok(Config { inputs, ..config })
+    })?;
+
+    Ok(Response::new().add_attribute("action", "set_inputs"))
+}
+
+/// Derives the answer from the latest rounds of the input feeds.
+///
+/// The round is as old as the oldest input: `started_at` and `updated_at` are the
+/// earliest among the inputs. The round ID is the newest input `updated_at`, so it
+/// changes whenever any input is updated. The answer is `None` if any input has none.
+pub fn get_latest_round_data(deps: Deps, _env: Env) -> StdResult<RoundDataResponse> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let mut answer = Some(pow10(config.decimals)?);
+    let mut started_at = Some(u64::MAX);
+    let mut updated_at = Some(u64::MAX);
+    let mut round_id = 0;
+    for input in config.inputs {
+        let round = deps.querier.get_latest_round_data(input.feed.clone())?;
+        let scale = pow10(deps.querier.get_decimals(input.feed)?)?;
+
+        answer = match (answer, round.answer) {
+            (Some(value), Some(input_answer)) => Some(match input.operation {
+                Operation::Multiply => multiply_ratio(value, input_answer, scale)?,
+                Operation::Divide => multiply_ratio(value, scale, input_answer)?,
+            }),
+            _ => None,
+        };
+        started_at = started_at.zip(round.started_at).map(|(a, b)| a.min(b));
+        updated_at = updated_at.zip(round.updated_at).map(|(a, b)| a.min(b));
+        round_id = round_id.max(round.updated_at.unwrap_or_default());
+    }
+
+    Ok(RoundDataResponse {
+        round_id,
+        answer,
+        started_at,
+        updated_at,
+        answered_in_round: round_id,
+    })
+}
+
+/// Only the latest round can be derived
+pub fn get_round_data(deps: Deps, env: Env, round_id: u64) -> StdResult<RoundDataResponse> {
+    let latest = get_latest_round_data(deps, env)?;
+    if latest.round_id != round_id {
+        return Err(StdError::generic_err(
+            ContractError::RoundNotAvailable {}.to_string(),
+        ));
+    }
+    Ok(latest)
+}
+
+fn validate_inputs(deps: Deps, inputs: Vec<InputMsg>) -> Result<Vec<Input>, ContractError> {
+    if inputs.is_empty() {
+        return Err(ContractError::NoInputs {});
+    }
+    inputs
+        .into_iter()
+        .map(|input| {
+            Ok(Input {
+                feed: deps.api.addr_validate(&input.feed)?,
+                operation: input.operation,
+            })
+        })
+        .collect()
+}
+
+fn pow10(decimals: u8) -> StdResult<Uint128> {
+    10u128
+        .checked_pow(decimals.into())
+        .map(Uint128::new)
+        .ok_or_else(|| StdError::generic_err("Decimals are too large"))
+}
+
+/// `value * numerator / denominator`, failing instead of panicking
+fn multiply_ratio(value: Uint128, numerator: Uint128, denominator: Uint128) -> StdResult<Uint128> {
+    if denominator.is_zero() {
+        return Err(StdError::generic_err("Input answer is zero"));
+    }
+    Uint128::try_from(value.full_mul(numerator) / Uint256::from(denominator))
+        .map_err(|_| StdError::generic_err("Derived answer overflows"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chainlink_aggregator::testing::{mock_dependencies_with_feeds, MockFeed, MockFeedQuerier};
+    use cosmwasm_std::from_binary;
+    use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+    use cosmwasm_std::OwnedDeps;
+
+    /// Mocks feeds named after their decimals, answer and update time, e.g. "feed_8_100_5"
+    fn mock_feed(name: &str) -> MockFeed {
+        let parts: Vec<&str> = name.split('_').collect();
+        let updated_at: u64 = parts[3].parse().unwrap();
+        MockFeed::new(parts[1].parse().unwrap(), name).with_round(RoundDataResponse {
+            round_id: 7,
+            answer: parts[2].parse().ok().map(Uint128::new),
+            started_at: Some(updated_at - 1),
+            updated_at: Some(updated_at),
+            answered_in_round: 7,
+        })
+    }
+
+    fn setup(
+        decimals: u8,
+        inputs: Vec<(&str, Operation)>,
+    ) -> OwnedDeps<MockStorage, MockApi, MockFeedQuerier> {
+        let mut deps = mock_dependencies_with_feeds(
+            inputs
+                .iter()
+                .map(|(feed, _)| (*feed, mock_feed(feed)))
+                .collect(),
+        );
+        let msg = InstantiateMsg {
+            description: "derived".to_owned(),
+            decimals,
+            inputs: inputs
+                .into_iter()
+                .map(|(feed, operation)| InputMsg {
+                    feed: feed.to_owned(),
+                    operation,
+                })
+                .collect(),
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+        deps
+    }
+
+    fn latest(deps: Deps) -> StdResult<RoundDataResponse> {
+        let msg = QueryMsg::AggregatorQuery(GetLatestRoundData {});
+        query(deps, mock_env(), msg).map(|res| from_binary(&res).unwrap())
+    }
+
+    #[test]
+    fn instantiate_requires_inputs() {
+        let mut deps = mock_dependencies_with_feeds(vec![]);
+        let msg = InstantiateMsg {
+            description: "derived".to_owned(),
+            decimals: 8,
+            inputs: vec![],
+        };
+        let err = instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg);
+        assert!(matches!(err, Err(ContractError::NoInputs {})));
+    }
+
+    #[test]
+    fn cross_rate() {
+        // LUNA/USD = 50.0 with 8 decimals, EUR/USD = 1.25 with 6 decimals
+        let deps = setup(
+            8,
+            vec![
+                ("feed_8_5000000000_100", Operation::Multiply),
+                ("feed_6_1250000_90", Operation::Divide),
+            ],
+        );
+        let res = latest(deps.as_ref()).unwrap();
+        // LUNA/EUR = 40.0
+        assert_eq!(res.answer, Some(Uint128::new(4000000000)));
+        assert_eq!(res.started_at, Some(89));
+        assert_eq!(res.updated_at, Some(90));
+        assert_eq!(res.round_id, 100);
+        assert_eq!(res.answered_in_round, 100);
+
+        let res: LatestAnswerResponse = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::AggregatorQuery(GetLatestAnswer {}),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(res, LatestAnswerResponse(Some(Uint128::new(4000000000))));
+
+        let decimals: u8 = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::AggregatorQuery(GetDecimals {}),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(decimals, 8);
+
+        let msg = QueryMsg::AggregatorQuery(GetRoundData { round_id: 100 });
+        let res: RoundDataResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert_eq!(res.answer, Some(Uint128::new(4000000000)));
+        let msg = QueryMsg::AggregatorQuery(GetRoundData { round_id: 99 });
+        assert!(query(deps.as_ref(), mock_env(), msg).is_err());
+    }
+
+    #[test]
+    fn inverse() {
+        // USD/EUR from EUR/USD = 1.25
+        let deps = setup(18, vec![("feed_6_1250000_10", Operation::Divide)]);
+        let res = latest(deps.as_ref()).unwrap();
+        assert_eq!(res.answer, Some(Uint128::new(800000000000000000)));
+    }
+
+    #[test]
+    fn missing_and_invalid_answers() {
+        let deps = setup(
+            8,
+            vec![
+                ("feed_8_none_100", Operation::Multiply),
+                ("feed_8_100_100", Operation::Divide),
+            ],
+        );
+        assert_eq!(latest(deps.as_ref()).unwrap().answer, None);
+
+        let deps = setup(8, vec![("feed_8_0_100", Operation::Divide)]);
+        assert!(latest(deps.as_ref()).is_err());
+
+        let deps = setup(
+            8,
+            vec![
+                (
+                    "feed_0_340282366920938463463374607431768211455_1",
+                    Operation::Multiply,
+                ),
+                ("feed_0_2_1", Operation::Multiply),
+            ],
+        );
+        assert!(latest(deps.as_ref()).is_err());
+    }
+
+    #[test]
+    fn set_inputs() {
+        let mut deps = setup(8, vec![("feed_8_100_1", Operation::Multiply)]);
+        deps.querier
+            .update_feed("feed_8_200_1", mock_feed("feed_8_200_1"));
+        let msg = ExecuteMsg::SetInputs {
+            inputs: vec![InputMsg {
+                feed: "feed_8_200_1".to_owned(),
+                operation: Operation::Multiply,
+            }],
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("stranger", &[]),
+            msg.clone(),
+        );
+        assert!(matches!(
+            err,
+            Err(ContractError::OwnedError(
+                owned::error::ContractError::MissingRole { .. }
+            ))
+        ));
+
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+        assert_eq!(
+            latest(deps.as_ref()).unwrap().answer,
+            Some(Uint128::new(200))
+        );
+    }
+}
@@ -0,0 +1,16 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+    /// The answer is derived from at least one feed
+    #[error("At least one input feed is required")]
+    NoInputs {},
+    /// Only the latest round is derived, earlier input answers aren't known
+    #[error("Only the latest round is available")]
+    RoundNotAvailable {},
+    #[error("{0}")]
+    OwnedError(#[from] owned::error::ContractError),
+}
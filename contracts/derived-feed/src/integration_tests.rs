@@ -0,0 +1,138 @@
+#![cfg(test)]
+
+use chainlink_aggregator::{compose_round_id, FeedQuerier};
+use chainlink_testing::{
+    contract_mock_aggregator, MockAggregatorExecuteMsg, MockAggregatorInstantiateMsg,
+};
+use cosmwasm_std::{
+    testing::{mock_env, MockApi, MockStorage},
+    Addr, Empty, Uint128,
+};
+use cw_multi_test::{App, BankKeeper, Contract, ContractWrapper, Executor};
+
+use crate::{
+    contract::{execute, instantiate, query},
+    msg::{InputMsg, InstantiateMsg},
+    state::Operation,
+};
+
+static OWNER: &str = "owner";
+
+fn mock_app() -> App {
+    let env = mock_env();
+    let api = MockApi::default();
+    let bank = BankKeeper::new();
+    let storage = MockStorage::new();
+
+    App::new(api, env.block, bank, storage)
+}
+
+pub fn contract_derived_feed() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(execute, instantiate, query);
+    Box::new(contract)
+}
+
+pub fn contract_proxy() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        aggregator_proxy::contract::execute,
+        aggregator_proxy::contract::instantiate,
+        aggregator_proxy::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn instantiate_aggregator(router: &mut App, decimals: u8, answer: u128, updated_at: u64) -> Addr {
+    let id = router.store_code(contract_mock_aggregator());
+    let aggregator = router
+        .instantiate_contract(
+            id,
+            Addr::unchecked(OWNER),
+            &MockAggregatorInstantiateMsg {
+                decimals,
+                description: String::new(),
+                initial_answer: None,
+            },
+            &[],
+            "Aggregator",
+            None,
+        )
+        .unwrap();
+    router
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            aggregator.clone(),
+            &MockAggregatorExecuteMsg::UpdateRoundData {
+                round_id: 1,
+                answer: Some(Uint128::new(answer)),
+                started_at: Some(updated_at),
+                updated_at: Some(updated_at),
+                answered_in_round: None,
+            },
+            &[],
+        )
+        .unwrap();
+    aggregator
+}
+
+#[test]
+fn derived_feed_behind_proxy() {
+    let mut router = mock_app();
+    let luna_usd = instantiate_aggregator(&mut router, 8, 5000000000, 100);
+    let eur_usd = instantiate_aggregator(&mut router, 6, 1250000, 90);
+
+    let id = router.store_code(contract_derived_feed());
+    let luna_eur = router
+        .instantiate_contract(
+            id,
+            Addr::unchecked(OWNER),
+            &InstantiateMsg {
+                description: "LUNA / EUR".to_owned(),
+                decimals: 8,
+                inputs: vec![
+                    InputMsg {
+                        feed: luna_usd.to_string(),
+                        operation: Operation::Multiply,
+                    },
+                    InputMsg {
+                        feed: eur_usd.to_string(),
+                        operation: Operation::Divide,
+                    },
+                ],
+            },
+            &[],
+            "Derived Feed",
+            None,
+        )
+        .unwrap();
+
+    let id = router.store_code(contract_proxy());
+    let proxy = router
+        .instantiate_contract(
+            id,
+            Addr::unchecked(OWNER),
+            &aggregator_proxy::msg::InstantiateMsg {
+                aggregator: luna_eur.to_string(),
+                confirmation_delay: None,
+            },
+            &[],
+            "Aggregator Proxy",
+            None,
+        )
+        .unwrap();
+
+    let querier = router.wrap();
+    let res = querier.get_latest_round_data(proxy.clone()).unwrap();
+    assert_eq!(res.round_id, compose_round_id(1, 100));
+    assert_eq!(res.answer, Some(Uint128::new(4000000000)));
+    assert_eq!(res.updated_at, Some(90));
+    assert_eq!(querier.get_decimals(proxy.clone()).unwrap(), 8);
+    assert_eq!(
+        querier.get_description(proxy.clone()).unwrap(),
+        "LUNA / EUR"
+    );
+
+    let res = querier
+        .get_round_data(proxy, compose_round_id(1, 100))
+        .unwrap();
+    assert_eq!(res.answer, Some(Uint128::new(4000000000)));
+}
@@ -0,0 +1,78 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::state::Operation;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InputMsg {
+    /// Address of the feed, usually an aggregator proxy
+    pub feed: String,
+    pub operation: Operation,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub description: String,
+    /// Decimals of the derived answer
+    pub decimals: u8,
+    /// Feeds applied in order, starting from 1. E.g. LUNA/EUR is derived by
+    /// multiplying by LUNA/USD and dividing by EUR/USD
+    pub inputs: Vec<InputMsg>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Initiate contract ownership transfer to another address.
+    /// Can be used only by owner
+    TransferOwnership {
+        /// Address to transfer ownership to
+        to: String,
+        /// Block time in seconds after which the transfer can no longer be accepted
+        expires_at: Option<u64>,
+    },
+    /// Finish contract ownership transfer. Can be used only by pending owner
+    AcceptOwnership {},
+    /// Cancel the pending ownership transfer. Can be used only by owner
+    CancelOwnershipTransfer {},
+    /// Leave the contract without owner, disabling functionality restricted to the owner.
+    /// Can be used only by owner
+    RenounceOwnership {},
+    /// Grant a role, see `owned::roles`. Can be used only by owner
+    GrantRole { role: String, account: String },
+    /// Revoke a granted role. Can be used only by owner
+    RevokeRole { role: String, account: String },
+    /// Give up a role granted to the sender
+    RenounceRole { role: String },
+    /// Replaces the input feeds
+    /// Can be used only by owner
+    SetInputs { inputs: Vec<InputMsg> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Query the description, decimals and input feeds
+    /// Response: [`crate::state::Config`]
+    GetConfig {},
+    /// Query the contract's owner
+    /// Response: [`cosmwasm_std::Addr`]
+    GetOwner {},
+    /// Returns the owner, the pending owner and the expiry of the pending transfer
+    /// Response: [`owned::msg::OwnershipStateResponse`]
+    GetOwnershipState {},
+    /// Returns whether the account holds the role, which the owner always does
+    /// Response: `bool`
+    HasRole {
+        role: String,
+        account: String,
+    },
+    /// Returns the accounts granted the role, not including the owner
+    /// Response: [`owned::msg::RoleMembersResponse`]
+    GetRoleMembers {
+        role: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    AggregatorQuery(chainlink_aggregator::QueryMsg),
+}
@@ -0,0 +1,29 @@
+use cosmwasm_std::Addr;
+use cw_storage_plus::Item;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// How an input feed's answer is applied to the derived answer
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Operation {
+    Multiply,
+    Divide,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Input {
+    pub feed: Addr,
+    pub operation: Operation,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub description: String,
+    /// Decimals of the derived answer
+    pub decimals: u8,
+    /// Feeds applied in order, starting from 1
+    pub inputs: Vec<Input>,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
@@ -3,8 +3,10 @@ use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
-use deviation_flagging_validator::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
-use deviation_flagging_validator::state::State;
+use deviation_flagging_validator::msg::{
+    AggregatorConfigsResponse, ExecuteMsg, InstantiateMsg, QueryMsg,
+};
+use deviation_flagging_validator::state::{AggregatorConfig, State, ValidatedRound};
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -16,4 +18,7 @@ fn main() {
     export_schema(&schema_for!(ExecuteMsg), &out_dir);
     export_schema(&schema_for!(QueryMsg), &out_dir);
     export_schema(&schema_for!(State), &out_dir);
+    export_schema(&schema_for!(ValidatedRound), &out_dir);
+    export_schema(&schema_for!(AggregatorConfig), &out_dir);
+    export_schema(&schema_for!(AggregatorConfigsResponse), &out_dir);
 }
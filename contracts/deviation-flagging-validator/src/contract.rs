@@ -1,6 +1,8 @@
 use cosmwasm_std::{
-    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128, WasmMsg,
+    to_binary, Addr, Binary, ContractResult, Deps, DepsMut, Env, Event, MessageInfo, Order, Reply,
+    Response, StdError, StdResult, SubMsg, Uint128, Uint256, WasmMsg,
 };
+use cw_storage_plus::Bound;
 
 use crate::error::ContractError;
 use crate::msg::*;
@@ -8,11 +10,21 @@ use crate::state::*;
 
 use flags::msg::ExecuteMsg as FlagsMsg;
 use owned::contract::{
-    execute_accept_ownership, execute_transfer_ownership, get_owner, instantiate as owned_init,
+    execute_accept_ownership, execute_cancel_ownership_transfer, execute_renounce_ownership,
+    execute_transfer_ownership, get_owner, get_ownership_state, instantiate as owned_init,
+};
+use owned::roles::{
+    execute_grant_role, execute_renounce_role, execute_revoke_role, has_role, query_role_members,
+    validate_role, CONFIG_ADMIN,
 };
 
 static THRESHOLD_MULTIPLIER: u128 = 100000;
 
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+const LOWER_FLAGS_REPLY_ID: u64 = 1;
+
 pub fn instantiate(
     deps: DepsMut,
     env: Env,
@@ -42,6 +54,9 @@ pub fn execute(
         ExecuteMsg::SetFlaggingThreshold { threshold } => {
             execute_set_flagging_threshold(deps, env, info, threshold)
         }
+        ExecuteMsg::SetAggregatorConfig { aggregator, config } => {
+            execute_set_aggregator_config(deps, env, info, aggregator, config)
+        }
         ExecuteMsg::Validate {
             previous_round_id,
             previous_answer,
@@ -56,12 +71,27 @@ pub fn execute(
             round_id,
             answer,
         ),
-        ExecuteMsg::TransferOwnership { to } => {
-            execute_transfer_ownership(deps, env, info, to).map_err(ContractError::from)
+        ExecuteMsg::TransferOwnership { to, expires_at } => {
+            execute_transfer_ownership(deps, env, info, to, expires_at).map_err(ContractError::from)
         }
         ExecuteMsg::AcceptOwnership {} => {
             execute_accept_ownership(deps, env, info).map_err(ContractError::from)
         }
+        ExecuteMsg::CancelOwnershipTransfer {} => {
+            execute_cancel_ownership_transfer(deps, env, info).map_err(ContractError::from)
+        }
+        ExecuteMsg::RenounceOwnership {} => {
+            execute_renounce_ownership(deps, env, info).map_err(ContractError::from)
+        }
+        ExecuteMsg::GrantRole { role, account } => {
+            execute_grant_role(deps, env, info, role, account).map_err(ContractError::from)
+        }
+        ExecuteMsg::RevokeRole { role, account } => {
+            execute_revoke_role(deps, env, info, role, account).map_err(ContractError::from)
+        }
+        ExecuteMsg::RenounceRole { role } => {
+            execute_renounce_role(deps, env, info, role).map_err(ContractError::from)
+        }
     }
 }
 
@@ -70,9 +100,34 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::IsValid {
             previous_answer,
             answer,
-        } => to_binary(&is_valid(deps, previous_answer, answer)?),
+            aggregator,
+        } => to_binary(&query_is_valid(deps, previous_answer, answer, aggregator)?),
         QueryMsg::GetFlaggingThreshold {} => to_binary(&query_flagging_threshold(deps)?),
         QueryMsg::GetOwner {} => to_binary(&get_owner(deps)?),
+        QueryMsg::GetOwnershipState {} => to_binary(&get_ownership_state(deps)?),
+        QueryMsg::HasRole { role, account } => {
+            to_binary(&has_role(deps, &role, &deps.api.addr_validate(&account)?)?)
+        }
+        QueryMsg::GetRoleMembers {
+            role,
+            start_after,
+            limit,
+        } => to_binary(&query_role_members(deps, role, start_after, limit)?),
+        QueryMsg::GetLastValidated { aggregator } => {
+            to_binary(&query_last_validated(deps, aggregator)?)
+        }
+        QueryMsg::GetAggregatorConfigs { start_after, limit } => {
+            to_binary(&query_aggregator_configs(deps, start_after, limit)?)
+        }
+    }
+}
+
+/// Reports failed flag lowerings, which don't revert the validation causing them
+pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.result {
+        ContractResult::Err(error) if msg.id == LOWER_FLAGS_REPLY_ID => Ok(Response::new()
+            .add_event(Event::new("lower_flags_failed").add_attribute("error", error))),
+        _ => Ok(Response::default()),
     }
 }
 
@@ -82,38 +137,116 @@ pub fn execute_validate(
     info: MessageInfo,
     _previous_round_id: u32,
     previous_answer: Uint128,
-    _round_id: u32,
+    round_id: u32,
     answer: Uint128,
 ) -> Result<Response, ContractError> {
-    if !(is_valid(deps.as_ref(), previous_answer, answer)?) {
-        let flags = CONFIG.load(deps.storage)?.flags;
+    let config = match AGGREGATORS.may_load(deps.storage, &info.sender)? {
+        Some(config) => config,
+        None => {
+            return Ok(Response::new()
+                .add_attribute("action", "validate")
+                .add_attribute("unregistered", true.to_string()))
+        }
+    };
+    let mut response = Response::new().add_attribute("action", "validate");
+
+    let last_validated = LAST_VALIDATED.may_load(deps.storage, &info.sender)?;
+    let is_new_round = match &last_validated {
+        Some(last) => last.round_id < round_id,
+        None => true,
+    };
+    let previous_answer = match last_validated {
+        // nothing recorded yet, fall back to the answer supplied by the aggregator
+        None => previous_answer,
+        // the round's answer got updated, validate against the same answer as before
+        Some(last) if last.round_id == round_id => last.previous_answer,
+        Some(last) if last.round_id < round_id => {
+            if round_id - last.round_id > 1 {
+                response = response
+                    .add_attribute("skipped_rounds", (round_id - last.round_id - 1).to_string());
+            }
+            last.answer
+        }
+        // rounds validated out of order are ignored
+        Some(last) => {
+            return Ok(response
+                .add_attribute("out_of_order", true.to_string())
+                .add_attribute("last_validated_round", last.round_id.to_string()));
+        }
+    };
+
+    LAST_VALIDATED.save(
+        deps.storage,
+        &info.sender,
+        &ValidatedRound {
+            round_id,
+            answer,
+            previous_answer,
+        },
+    )?;
+
+    let flags = CONFIG.load(deps.storage)?.flags;
+    if !(is_valid(deps.as_ref(), &config, previous_answer, answer)?) {
         let raise_flag_msg = WasmMsg::Execute {
             contract_addr: String::from(flags),
             msg: to_binary(&FlagsMsg::RaiseFlag {
                 subject: info.sender.to_string(),
+                ttl: None,
             })?,
             funds: vec![],
         };
-        Ok(Response::new()
+        if config.recovery.is_some() {
+            RECOVERING.save(deps.storage, &info.sender, &0)?;
+        }
+        return Ok(response
             .add_message(raise_flag_msg)
-            .add_attribute("action", "validate")
             .add_attribute("is_valid", false.to_string())
-            .set_data(to_binary(&false)?))
-    } else {
-        Ok(Response::new()
-            .add_attribute("action", "validate")
-            .add_attribute("is_valid", true.to_string())
-            .set_data(to_binary(&true)?))
+            .set_data(to_binary(&false)?));
     }
+
+    response = response
+        .add_attribute("is_valid", true.to_string())
+        .set_data(to_binary(&true)?);
+
+    let recovering = RECOVERING.may_load(deps.storage, &info.sender)?;
+    if let (Some(policy), Some(recovered_rounds)) = (config.recovery, recovering) {
+        let recovered_rounds =
+            if !within_relative_threshold(previous_answer, answer, policy.threshold) {
+                0
+            } else if is_new_round {
+                recovered_rounds + 1
+            } else {
+                recovered_rounds
+            };
+
+        if recovered_rounds >= policy.rounds {
+            RECOVERING.remove(deps.storage, &info.sender);
+            let lower_flag_msg = WasmMsg::Execute {
+                contract_addr: String::from(flags),
+                msg: to_binary(&FlagsMsg::LowerFlags {
+                    subjects: vec![info.sender.to_string()],
+                })?,
+                funds: vec![],
+            };
+            response = response
+                .add_submessage(SubMsg::reply_on_error(lower_flag_msg, LOWER_FLAGS_REPLY_ID))
+                .add_attribute("flag_lowered", true.to_string());
+        } else {
+            RECOVERING.save(deps.storage, &info.sender, &recovered_rounds)?;
+            response = response.add_attribute("recovered_rounds", recovered_rounds.to_string());
+        }
+    }
+
+    Ok(response)
 }
 
 pub fn execute_set_flags_address(
     deps: DepsMut,
-    env: Env,
+    _env: Env,
     info: MessageInfo,
     flags: Addr,
 ) -> Result<Response, ContractError> {
-    validate_ownership(deps.as_ref(), &env, info)?;
+    validate_role(deps.as_ref(), &info, CONFIG_ADMIN)?;
     let previous = CONFIG.load(deps.storage)?.flags;
     if previous != flags {
         CONFIG.update(deps.storage, |mut state| -> StdResult<_> {
@@ -129,11 +262,11 @@ pub fn execute_set_flags_address(
 
 pub fn execute_set_flagging_threshold(
     deps: DepsMut,
-    env: Env,
+    _env: Env,
     info: MessageInfo,
     threshold: u32,
 ) -> Result<Response, ContractError> {
-    validate_ownership(deps.as_ref(), &env, info)?;
+    validate_role(deps.as_ref(), &info, CONFIG_ADMIN)?;
     let previous_ft = CONFIG.load(deps.storage)?.flagging_threshold;
 
     if previous_ft != threshold {
@@ -149,21 +282,94 @@ pub fn execute_set_flagging_threshold(
         .add_attribute("current", threshold.to_string()))
 }
 
-fn is_valid(deps: Deps, previous_answer: Uint128, answer: Uint128) -> StdResult<bool> {
+pub fn execute_set_aggregator_config(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    aggregator: String,
+    config: Option<AggregatorConfig>,
+) -> Result<Response, ContractError> {
+    validate_role(deps.as_ref(), &info, CONFIG_ADMIN)?;
+    let aggregator = deps.api.addr_validate(&aggregator)?;
+
+    match config {
+        Some(config) => AGGREGATORS.save(deps.storage, &aggregator, &config)?,
+        None => {
+            AGGREGATORS.remove(deps.storage, &aggregator);
+            LAST_VALIDATED.remove(deps.storage, &aggregator);
+            RECOVERING.remove(deps.storage, &aggregator);
+        }
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "aggregator_config_updated")
+        .add_attribute("aggregator", aggregator))
+}
+
+fn is_valid(
+    deps: Deps,
+    config: &AggregatorConfig,
+    previous_answer: Uint128,
+    answer: Uint128,
+) -> StdResult<bool> {
     if previous_answer == Uint128::zero() {
-        Ok(true)
+        return Ok(true);
+    }
+    let change = if previous_answer.u128() > answer.u128() {
+        previous_answer.u128() - answer.u128()
     } else {
-        let flagging_threshold = CONFIG.load(deps.storage)?.flagging_threshold;
-        let change = if previous_answer.u128() > answer.u128() {
-            previous_answer.u128() - answer.u128()
-        } else {
-            answer.u128() - previous_answer.u128()
-        };
-        //Uint128::from(previous_answer.u128() - answer.u128());
-        let ratio_numerator = change * THRESHOLD_MULTIPLIER;
-        let ratio = ratio_numerator / previous_answer.u128();
-        Ok(ratio <= flagging_threshold as u128)
+        answer.u128() - previous_answer.u128()
+    };
+
+    if let Some(absolute_threshold) = config.absolute_threshold {
+        if change > absolute_threshold.u128() {
+            return Ok(false);
+        }
     }
+
+    let relative_threshold = match (config.relative_threshold, config.absolute_threshold) {
+        (Some(threshold), _) => threshold,
+        (None, Some(_)) => return Ok(true),
+        (None, None) => CONFIG.load(deps.storage)?.flagging_threshold,
+    };
+    Ok(within_relative_threshold(
+        previous_answer,
+        answer,
+        relative_threshold,
+    ))
+}
+
+fn within_relative_threshold(previous_answer: Uint128, answer: Uint128, threshold: u32) -> bool {
+    if previous_answer == Uint128::zero() {
+        return true;
+    }
+    let change = if previous_answer > answer {
+        previous_answer - answer
+    } else {
+        answer - previous_answer
+    };
+    let ratio = change.full_mul(THRESHOLD_MULTIPLIER) / Uint256::from(previous_answer);
+    ratio <= Uint256::from(threshold as u128)
+}
+
+pub fn query_is_valid(
+    deps: Deps,
+    previous_answer: Uint128,
+    answer: Uint128,
+    aggregator: Option<String>,
+) -> StdResult<bool> {
+    let config = match aggregator {
+        Some(aggregator) => {
+            let aggregator = deps.api.addr_validate(&aggregator)?;
+            AGGREGATORS.load(deps.storage, &aggregator)?
+        }
+        None => AggregatorConfig {
+            relative_threshold: None,
+            absolute_threshold: None,
+            recovery: None,
+        },
+    };
+    is_valid(deps, &config, previous_answer, answer)
 }
 
 pub fn query_flagging_threshold(deps: Deps) -> StdResult<FlaggingThresholdResponse> {
@@ -173,12 +379,33 @@ pub fn query_flagging_threshold(deps: Deps) -> StdResult<FlaggingThresholdRespon
     })
 }
 
-fn validate_ownership(deps: Deps, _env: &Env, info: MessageInfo) -> Result<(), ContractError> {
-    let owner = get_owner(deps)?;
-    if info.sender != owner {
-        return Err(ContractError::NotOwner {});
-    }
-    Ok(())
+pub fn query_last_validated(deps: Deps, aggregator: String) -> StdResult<Option<ValidatedRound>> {
+    let aggregator = deps.api.addr_validate(&aggregator)?;
+    LAST_VALIDATED.may_load(deps.storage, &aggregator)
+}
+
+pub fn query_aggregator_configs(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AggregatorConfigsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let aggregators = AGGREGATORS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (key, config) = item?;
+            let aggregator = String::from_utf8(key).map_err(StdError::invalid_utf8)?;
+            Ok(AggregatorConfigResponse {
+                aggregator: Addr::unchecked(aggregator),
+                config,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(AggregatorConfigsResponse { aggregators })
 }
 
 #[cfg(test)]
@@ -187,6 +414,23 @@ mod tests {
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
     use cosmwasm_std::{attr, coins, Api};
 
+    fn register(
+        deps: DepsMut,
+        aggregator: &str,
+        relative_threshold: Option<u32>,
+        absolute_threshold: Option<u128>,
+    ) {
+        let msg = ExecuteMsg::SetAggregatorConfig {
+            aggregator: aggregator.to_string(),
+            config: Some(AggregatorConfig {
+                relative_threshold,
+                absolute_threshold: absolute_threshold.map(Uint128::new),
+                recovery: None,
+            }),
+        };
+        execute(deps, mock_env(), mock_info("creator", &[]), msg).unwrap();
+    }
+
     #[test]
     fn proper_initialization() {
         let mut deps = mock_dependencies(&[]);
@@ -259,21 +503,29 @@ mod tests {
         let res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
         assert_eq!(0, res.messages.len());
 
+        let default_config = AggregatorConfig {
+            relative_threshold: None,
+            absolute_threshold: None,
+            recovery: None,
+        };
         let previous_answer = Uint128::from(100 as u64);
         let answer = Uint128::from(5 as u64);
-        let check_valid = is_valid(deps.as_ref(), previous_answer, answer).unwrap();
+        let check_valid =
+            is_valid(deps.as_ref(), &default_config, previous_answer, answer).unwrap();
         assert_eq!(false, check_valid);
 
         // this input should return true
         let previous_answer = Uint128::from(3 as u64);
         let answer = Uint128::from(1 as u64);
-        let check_valid = is_valid(deps.as_ref(), previous_answer, answer).unwrap();
+        let check_valid =
+            is_valid(deps.as_ref(), &default_config, previous_answer, answer).unwrap();
         assert_eq!(true, check_valid);
 
         // should return true if previous_answer is 0
         let previous_answer = Uint128::zero();
         let answer = Uint128::from(5 as u64);
-        let check_valid = is_valid(deps.as_ref(), previous_answer, answer).unwrap();
+        let check_valid =
+            is_valid(deps.as_ref(), &default_config, previous_answer, answer).unwrap();
         assert_eq!(true, check_valid);
     }
 
@@ -291,6 +543,24 @@ mod tests {
         let res = instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
         assert_eq!(0, res.messages.len());
 
+        // answers of unregistered callers are ignored
+        let msg = ExecuteMsg::Validate {
+            previous_round_id: 2,
+            previous_answer: Uint128::from(3 as u64),
+            answer: Uint128::from(1 as u64),
+            round_id: 3,
+        };
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        assert_eq!(
+            res.attributes,
+            vec![attr("action", "validate"), attr("unregistered", "true")]
+        );
+        assert!(res.messages.is_empty());
+        let last_validated = query_last_validated(deps.as_ref(), "creator".to_string()).unwrap();
+        assert_eq!(last_validated, None);
+
+        register(deps.as_mut(), "creator", None, None);
+
         let msg = ExecuteMsg::Validate {
             previous_round_id: 2,
             previous_answer: Uint128::from(3 as u64),
@@ -308,13 +578,15 @@ mod tests {
             res.attributes
         );
 
+        // the answer is now compared against the recorded round, not the supplied one
         let msg = ExecuteMsg::Validate {
-            previous_round_id: 2,
-            previous_answer: Uint128::from(100 as u64),
+            previous_round_id: 3,
+            previous_answer: Uint128::from(5 as u64),
             answer: Uint128::from(5 as u64),
-            round_id: 3,
+            round_id: 4,
         };
         let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(1, res.messages.len());
         assert_eq!(
             vec![
                 attr("action", "validate"),
@@ -324,6 +596,95 @@ mod tests {
         );
     }
 
+    #[test]
+    fn validate_tracks_rounds() {
+        let mut deps = mock_dependencies(&[]);
+
+        let msg = InstantiateMsg {
+            flags: "flags".to_string(),
+            flagging_threshold: 80000,
+        };
+        let info = mock_info("creator", &coins(1000, "earth"));
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+        register(deps.as_mut(), "aggregator", None, None);
+
+        let aggregator = mock_info("aggregator", &[]);
+        let validate = |round_id: u32, answer: u64| ExecuteMsg::Validate {
+            previous_round_id: round_id - 1,
+            previous_answer: Uint128::from(answer),
+            answer: Uint128::from(answer),
+            round_id,
+        };
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            aggregator.clone(),
+            validate(1, 100),
+        )
+        .unwrap();
+
+        // updating the answer of the same round keeps the original reference
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            aggregator.clone(),
+            validate(1, 150),
+        )
+        .unwrap();
+        assert_eq!(attr("is_valid", true.to_string()), res.attributes[1]);
+        let last = query_last_validated(deps.as_ref(), "aggregator".to_string())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            ValidatedRound {
+                round_id: 1,
+                answer: Uint128::from(150u64),
+                previous_answer: Uint128::from(100u64),
+            },
+            last
+        );
+
+        // skipped rounds are reported and validated against the last recorded answer
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            aggregator.clone(),
+            validate(4, 10),
+        )
+        .unwrap();
+        assert_eq!(
+            vec![
+                attr("action", "validate"),
+                attr("skipped_rounds", "2"),
+                attr("is_valid", false.to_string())
+            ],
+            res.attributes
+        );
+
+        // older rounds are ignored
+        let res = execute(deps.as_mut(), mock_env(), aggregator, validate(2, 1000)).unwrap();
+        assert_eq!(0, res.messages.len());
+        assert_eq!(
+            vec![
+                attr("action", "validate"),
+                attr("out_of_order", true.to_string()),
+                attr("last_validated_round", "4")
+            ],
+            res.attributes
+        );
+        let last = query_last_validated(deps.as_ref(), "aggregator".to_string())
+            .unwrap()
+            .unwrap();
+        assert_eq!(4, last.round_id);
+
+        // other aggregators are tracked separately
+        assert_eq!(
+            None,
+            query_last_validated(deps.as_ref(), "other".to_string()).unwrap()
+        );
+    }
+
     #[test]
     fn test_query_flagging_threshold() {
         let mut deps = mock_dependencies(&[]);
@@ -341,4 +702,99 @@ mod tests {
         let flagging_threshold: u32 = query_flagging_threshold(deps.as_ref()).unwrap().threshold;
         assert_eq!(80000 as u32, flagging_threshold);
     }
+
+    #[test]
+    fn aggregator_thresholds() {
+        let mut deps = mock_dependencies(&[]);
+
+        let msg = InstantiateMsg {
+            flags: "flags".to_string(),
+            flagging_threshold: 80000,
+        };
+        let info = mock_info("creator", &coins(1000, "earth"));
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        // only the owner can register aggregators
+        let msg = ExecuteMsg::SetAggregatorConfig {
+            aggregator: "btc".to_string(),
+            config: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("someone", &[]), msg);
+        assert!(matches!(
+            res,
+            Err(ContractError::OwnedError(
+                owned::error::ContractError::MissingRole { .. }
+            ))
+        ));
+
+        register(deps.as_mut(), "btc", Some(5000), None);
+        register(deps.as_mut(), "usdc", Some(500), Some(10));
+        register(deps.as_mut(), "eth", None, Some(50));
+
+        let is_valid = |deps: Deps, aggregator: &str, previous_answer: u64, answer: u64| {
+            query_is_valid(
+                deps,
+                Uint128::from(previous_answer),
+                Uint128::from(answer),
+                Some(aggregator.to_string()),
+            )
+            .unwrap()
+        };
+        // 4% change
+        assert!(is_valid(deps.as_ref(), "btc", 1000, 1040));
+        assert!(!is_valid(deps.as_ref(), "usdc", 1000, 1040));
+        assert!(is_valid(deps.as_ref(), "eth", 1000, 1040));
+        // 1% change, but exceeding the absolute threshold
+        assert!(!is_valid(deps.as_ref(), "usdc", 10000, 10020));
+        assert!(!is_valid(deps.as_ref(), "eth", 10000, 10100));
+        // changes of large answers don't overflow
+        let large = |answer: u128| {
+            query_is_valid(
+                deps.as_ref(),
+                Uint128::new(u128::MAX / 2),
+                Uint128::new(answer),
+                Some("btc".to_string()),
+            )
+            .unwrap()
+        };
+        assert!(large(u128::MAX / 2 + u128::MAX / 100));
+        assert!(!large(u128::MAX));
+        // unknown aggregators can't be queried
+        assert!(query_is_valid(
+            deps.as_ref(),
+            Uint128::from(1u64),
+            Uint128::from(1u64),
+            Some("unknown".to_string())
+        )
+        .is_err());
+
+        let res = query_aggregator_configs(deps.as_ref(), None, Some(2)).unwrap();
+        let aggregators: Vec<_> = res
+            .aggregators
+            .iter()
+            .map(|a| a.aggregator.as_str())
+            .collect();
+        assert_eq!(vec!["btc", "eth"], aggregators);
+        let res = query_aggregator_configs(deps.as_ref(), Some("eth".to_string()), None).unwrap();
+        assert_eq!(
+            vec![AggregatorConfigResponse {
+                aggregator: Addr::unchecked("usdc"),
+                config: AggregatorConfig {
+                    relative_threshold: Some(500),
+                    absolute_threshold: Some(Uint128::new(10)),
+                    recovery: None,
+                },
+            }],
+            res.aggregators
+        );
+
+        // removing an aggregator
+        let msg = ExecuteMsg::SetAggregatorConfig {
+            aggregator: "usdc".to_string(),
+            config: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+        let res = query_aggregator_configs(deps.as_ref(), None, None).unwrap();
+        assert_eq!(2, res.aggregators.len());
+    }
 }
@@ -0,0 +1,218 @@
+#![cfg(test)]
+
+use cosmwasm_std::{
+    testing::{mock_env, MockApi, MockStorage},
+    Addr, Empty, Uint128,
+};
+use cw_multi_test::{App, BankKeeper, Contract, ContractWrapper, Executor};
+
+use crate::{
+    contract::{execute, instantiate, query, reply},
+    msg::{ExecuteMsg, InstantiateMsg},
+    state::{AggregatorConfig, RecoveryPolicy},
+};
+
+static OWNER: &str = "owner";
+static AGGREGATOR: &str = "aggregator";
+
+fn mock_app() -> App {
+    let env = mock_env();
+    let api = MockApi::default();
+    let bank = BankKeeper::new();
+    let storage = MockStorage::new();
+
+    App::new(api, env.block, bank, storage)
+}
+
+pub fn contract_df_validator() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(execute, instantiate, query).with_reply(reply);
+    Box::new(contract)
+}
+
+pub fn contract_flags() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        flags::contract::execute,
+        flags::contract::instantiate,
+        flags::contract::query,
+    );
+    Box::new(contract)
+}
+
+/// Returns the app, flags and validator addresses, with the validator allowed
+/// to lower flags and the aggregator registered with a 10% threshold
+fn default_init(recovery: Option<RecoveryPolicy>) -> (App, Addr, Addr) {
+    let mut router = mock_app();
+    let owner = Addr::unchecked(OWNER);
+
+    let id = router.store_code(contract_flags());
+    let flags_addr = router
+        .instantiate_contract(
+            id,
+            owner.clone(),
+            &flags::msg::InstantiateMsg {
+                rac_address: "rac".to_owned(),
+                lac_address: None,
+            },
+            &[],
+            "Flags",
+            None,
+        )
+        .unwrap();
+
+    let id = router.store_code(contract_df_validator());
+    let validator_addr = router
+        .instantiate_contract(
+            id,
+            owner.clone(),
+            &InstantiateMsg {
+                flags: flags_addr.to_string(),
+                flagging_threshold: 100000,
+            },
+            &[],
+            "Deviation Flagging Validator",
+            None,
+        )
+        .unwrap();
+
+    router
+        .execute_contract(
+            owner.clone(),
+            flags_addr.clone(),
+            &flags::msg::ExecuteMsg::SetLoweringAccessController {
+                lac_address: Some(validator_addr.to_string()),
+            },
+            &[],
+        )
+        .unwrap();
+
+    router
+        .execute_contract(
+            owner,
+            validator_addr.clone(),
+            &ExecuteMsg::SetAggregatorConfig {
+                aggregator: AGGREGATOR.to_owned(),
+                config: Some(AggregatorConfig {
+                    relative_threshold: Some(10000),
+                    absolute_threshold: None,
+                    recovery,
+                }),
+            },
+            &[],
+        )
+        .unwrap();
+
+    (router, flags_addr, validator_addr)
+}
+
+fn validate(router: &mut App, validator: &Addr, round_id: u32, answer: u128) {
+    router
+        .execute_contract(
+            Addr::unchecked(AGGREGATOR),
+            validator.clone(),
+            &ExecuteMsg::Validate {
+                previous_round_id: round_id - 1,
+                previous_answer: Uint128::zero(),
+                round_id,
+                answer: Uint128::new(answer),
+            },
+            &[],
+        )
+        .unwrap();
+}
+
+fn is_flagged(router: &App, flags: &Addr) -> bool {
+    router
+        .wrap()
+        .query_wasm_smart(
+            flags,
+            &flags::msg::QueryMsg::GetFlag {
+                subject: AGGREGATOR.to_owned(),
+            },
+        )
+        .unwrap()
+}
+
+#[test]
+fn flag_stays_raised_without_recovery_policy() {
+    let (mut router, flags, validator) = default_init(None);
+
+    validate(&mut router, &validator, 1, 100);
+    validate(&mut router, &validator, 2, 150);
+    assert!(is_flagged(&router, &flags));
+
+    for round_id in 3..10 {
+        validate(&mut router, &validator, round_id, 150);
+    }
+    assert!(is_flagged(&router, &flags));
+}
+
+#[test]
+fn flag_lowered_after_recovery() {
+    let (mut router, flags, validator) = default_init(Some(RecoveryPolicy {
+        threshold: 2000,
+        rounds: 3,
+    }));
+
+    validate(&mut router, &validator, 1, 100);
+    validate(&mut router, &validator, 2, 150);
+    assert!(is_flagged(&router, &flags));
+
+    // within the flagging threshold, but not the recovery threshold
+    validate(&mut router, &validator, 3, 151);
+    validate(&mut router, &validator, 4, 152);
+    validate(&mut router, &validator, 5, 160);
+    assert!(is_flagged(&router, &flags));
+
+    // answers updated within the same round don't count as separate rounds
+    validate(&mut router, &validator, 6, 161);
+    validate(&mut router, &validator, 6, 161);
+    validate(&mut router, &validator, 7, 162);
+    assert!(is_flagged(&router, &flags));
+
+    validate(&mut router, &validator, 8, 162);
+    assert!(!is_flagged(&router, &flags));
+
+    // the flag is raised again on the next deviation
+    validate(&mut router, &validator, 9, 100);
+    assert!(is_flagged(&router, &flags));
+}
+
+#[test]
+fn recovery_needs_lowering_access() {
+    let (mut router, flags, validator) = default_init(Some(RecoveryPolicy {
+        threshold: 2000,
+        rounds: 1,
+    }));
+    router
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            flags.clone(),
+            &flags::msg::ExecuteMsg::SetLoweringAccessController { lac_address: None },
+            &[],
+        )
+        .unwrap();
+
+    validate(&mut router, &validator, 1, 100);
+    validate(&mut router, &validator, 2, 150);
+    assert!(is_flagged(&router, &flags));
+
+    // lowering the flag fails without failing the validation
+    let res = router
+        .execute_contract(
+            Addr::unchecked(AGGREGATOR),
+            validator,
+            &ExecuteMsg::Validate {
+                previous_round_id: 2,
+                previous_answer: Uint128::zero(),
+                round_id: 3,
+                answer: Uint128::new(150),
+            },
+            &[],
+        )
+        .unwrap();
+    assert!(res
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-lower_flags_failed"));
+    assert!(is_flagged(&router, &flags));
+}
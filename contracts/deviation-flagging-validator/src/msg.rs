@@ -1,4 +1,6 @@
 use cosmwasm_std::{Addr, Uint128};
+
+use crate::state::AggregatorConfig;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -6,7 +8,8 @@ use serde::{Deserialize, Serialize};
 pub struct InstantiateMsg {
     /// The address of the flags contract
     pub flags: String,
-    /// The threshold that will trigger a flag to be raised
+    /// The threshold that will trigger a flag to be raised for aggregators
+    /// registered without thresholds of their own
     /// Setting the value of 100,000 is equivalent to tolerating a 100% change
     /// compared to the previous price
     pub flagging_threshold: u32,
@@ -20,22 +23,46 @@ pub enum ExecuteMsg {
     TransferOwnership {
         /// Address to transfer ownership to
         to: String,
+        /// Block time in seconds after which the transfer can no longer be accepted
+        expires_at: Option<u64>,
     },
     /// Finish contract ownership transfer. Can be used only by pending owner
     AcceptOwnership {},
+    /// Cancel the pending ownership transfer. Can be used only by owner
+    CancelOwnershipTransfer {},
+    /// Leave the contract without owner, disabling functionality restricted to the owner.
+    /// Can be used only by owner
+    RenounceOwnership {},
+    /// Grant a role, see `owned::roles`. Can be used only by owner
+    GrantRole { role: String, account: String },
+    /// Revoke a granted role. Can be used only by owner
+    RevokeRole { role: String, account: String },
+    /// Give up a role granted to the sender
+    RenounceRole { role: String },
     /// Updates the flagging threshold
     /// Can be used only by owner
     SetFlaggingThreshold { threshold: u32 },
     /// Updates the flagging contract address for raising flags
     /// Can be used only by owner
     SetFlagsAddress { flags: Addr },
-    /// Checks whether the parameters count as valid by comparing the difference
-    /// change to the flagging threshold
+    /// Registers an aggregator with its own thresholds, or removes it when
+    /// `config` is `None`
+    /// Can be used only by owner
+    SetAggregatorConfig {
+        /// Address of the aggregator
+        aggregator: String,
+        config: Option<AggregatorConfig>,
+    },
+    /// Checks whether the answer counts as valid by comparing its change from
+    /// the answer last validated for the calling aggregator to its thresholds.
+    /// Answers of unregistered aggregators are ignored without storing anything or
+    /// raising flags. They're acknowledged instead of rejected because aggregators
+    /// send `Validate` along with their submissions, which an error would revert
     Validate {
         /// ID of the previous round
         previous_round_id: u32,
-        /// Previous answer, used as the median of difference with the current
-        /// answer to determine if the deviation threshold has been exceeded
+        /// Previous answer, only used when nothing has been validated
+        /// for the calling aggregator yet
         previous_answer: Uint128,
         /// ID of the current round
         round_id: u32,
@@ -58,6 +85,8 @@ pub enum QueryMsg {
         /// Current answer which is compared for a ration of change to make sure
         /// it has not exceeded the flagging threshold
         answer: Uint128,
+        /// Aggregator whose thresholds are used instead of the flagging threshold
+        aggregator: Option<String>,
     },
     /// Query the flagging threshold
     /// Response: [`u32`]
@@ -65,9 +94,47 @@ pub enum QueryMsg {
     /// Returns contract owner's address
     /// Response [`Addr`]
     GetOwner {},
+    /// Returns the owner, the pending owner and the expiry of the pending transfer
+    /// Response [`owned::msg::OwnershipStateResponse`]
+    GetOwnershipState {},
+    /// Returns whether the account holds the role, which the owner always does
+    /// Response `bool`
+    HasRole { role: String, account: String },
+    /// Returns the accounts granted the role, not including the owner
+    /// Response [`owned::msg::RoleMembersResponse`]
+    GetRoleMembers {
+        role: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Query the last round validated for an aggregator
+    /// Response: [`Option<ValidatedRound>`]
+    GetLastValidated {
+        /// Address of the aggregator
+        aggregator: String,
+    },
+    /// Query registered aggregators and their thresholds
+    /// Response: [`AggregatorConfigsResponse`]
+    GetAggregatorConfigs {
+        /// Aggregator address to start after
+        start_after: Option<String>,
+        /// Maximum number of aggregators returned
+        limit: Option<u32>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct FlaggingThresholdResponse {
     pub threshold: u32,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AggregatorConfigResponse {
+    pub aggregator: Addr,
+    pub config: AggregatorConfig,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AggregatorConfigsResponse {
+    pub aggregators: Vec<AggregatorConfigResponse>,
+}
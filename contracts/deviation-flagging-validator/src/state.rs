@@ -1,8 +1,8 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct State {
@@ -10,4 +10,44 @@ pub struct State {
     pub flags: Addr,
 }
 
+/// Thresholds of a registered aggregator. A flag is raised when any of them is
+/// exceeded, the flagging threshold is used when neither is set
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AggregatorConfig {
+    /// Tolerated change relative to the previous answer, where 100,000 is
+    /// equivalent to a 100% change
+    pub relative_threshold: Option<u32>,
+    /// Tolerated change in the answer's own units
+    pub absolute_threshold: Option<Uint128>,
+    /// Lowers the flag raised by the validator once the aggregator recovers.
+    /// Requires the validator to be the lowering access controller of the flags contract,
+    /// or to be granted the flag lowerer role there. Without either, the flag stays raised and
+    /// a `lower_flags_failed` event is emitted instead of reverting the validation
+    pub recovery: Option<RecoveryPolicy>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RecoveryPolicy {
+    /// Tolerated change relative to the previous answer while recovering,
+    /// where 100,000 is equivalent to a 100% change
+    pub threshold: u32,
+    /// Number of consecutive rounds within the recovery threshold needed
+    /// to lower the flag
+    pub rounds: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ValidatedRound {
+    /// ID of the last validated round
+    pub round_id: u32,
+    /// Latest answer validated for the round
+    pub answer: Uint128,
+    /// Answer the round was validated against
+    pub previous_answer: Uint128,
+}
+
 pub const CONFIG: Item<State> = Item::new("config");
+pub const LAST_VALIDATED: Map<&Addr, ValidatedRound> = Map::new("last_validated");
+pub const AGGREGATORS: Map<&Addr, AggregatorConfig> = Map::new("aggregators");
+/// Consecutive rounds within the recovery threshold of aggregators flagged by the validator
+pub const RECOVERING: Map<&Addr, u32> = Map::new("recovering");
@@ -0,0 +1,560 @@
+use std::convert::TryInto;
+
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Deps, DepsMut, Env, Event, MessageInfo, Order, Response, StdResult,
+    WasmMsg,
+};
+use cw_storage_plus::Bound;
+
+use crate::error::ContractError;
+use crate::msg::*;
+use crate::state::*;
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+/// Longest minimum delay, one year
+const MAX_MIN_DELAY: u64 = 365 * 24 * 60 * 60;
+
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let config = validate_config(deps.as_ref(), msg.signers, msg.threshold, msg.min_delay)?;
+    CONFIG.save(deps.storage, &config)?;
+    PROPOSAL_COUNT.save(deps.storage, &0)?;
+
+    Ok(Response::default())
+}
+
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Propose { description, calls } => {
+            execute_propose(deps, env, info, description, calls)
+        }
+        ExecuteMsg::Approve { proposal_id } => execute_approve(deps, env, info, proposal_id),
+        ExecuteMsg::Cancel { proposal_id } => execute_cancel(deps, env, info, proposal_id),
+        ExecuteMsg::Execute { proposal_id } => execute_execute(deps, env, info, proposal_id),
+        ExecuteMsg::AcceptOwnership { contract } => {
+            execute_accept_ownership(deps, env, info, contract)
+        }
+        ExecuteMsg::UpdateConfig {
+            signers,
+            threshold,
+            min_delay,
+        } => execute_update_config(deps, env, info, signers, threshold, min_delay),
+    }
+}
+
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::GetConfig {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::GetProposal { proposal_id } => to_binary(&query_proposal(deps, proposal_id)?),
+        QueryMsg::GetProposals { start_after, limit } => {
+            to_binary(&query_proposals(deps, start_after, limit)?)
+        }
+    }
+}
+
+pub fn execute_propose(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    description: String,
+    calls: Vec<Call>,
+) -> Result<Response, ContractError> {
+    let config = validate_signer(deps.as_ref(), &info)?;
+    if calls.is_empty() {
+        return Err(ContractError::EmptyProposal {});
+    }
+    for call in &calls {
+        deps.api.addr_validate(&call.contract_addr)?;
+    }
+
+    let proposal_id = PROPOSAL_COUNT.load(deps.storage)? + 1;
+    PROPOSAL_COUNT.save(deps.storage, &proposal_id)?;
+
+    let mut proposal = Proposal {
+        proposer: info.sender.clone(),
+        description,
+        calls,
+        approvals: vec![info.sender.clone()],
+        status: Status::Open,
+        executable_at: None,
+    };
+    queue_if_approved(&mut proposal, &config, &env);
+    PROPOSALS.save(deps.storage, proposal_id.into(), &proposal)?;
+
+    Ok(Response::new()
+        .add_event(
+            Event::new("proposal_created")
+                .add_attribute("proposal_id", proposal_id.to_string())
+                .add_attribute("proposer", info.sender),
+        )
+        .set_data(to_binary(&proposal_id)?))
+}
+
+pub fn execute_approve(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let config = validate_signer(deps.as_ref(), &info)?;
+    let mut proposal = load_pending(deps.as_ref(), proposal_id)?;
+    if proposal.approvals.contains(&info.sender) {
+        return Err(ContractError::AlreadyApproved {});
+    }
+
+    proposal.approvals.push(info.sender.clone());
+    queue_if_approved(&mut proposal, &config, &env);
+    PROPOSALS.save(deps.storage, proposal_id.into(), &proposal)?;
+
+    let mut event = Event::new("proposal_approved")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("signer", info.sender);
+    if let Some(executable_at) = proposal.executable_at {
+        event = event.add_attribute("executable_at", executable_at.to_string());
+    }
+    Ok(Response::new().add_event(event))
+}
+
+pub fn execute_cancel(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut proposal = load_pending(deps.as_ref(), proposal_id)?;
+    // Proposals of removed signers can still be cancelled through a proposal
+    if info.sender != env.contract.address {
+        validate_signer(deps.as_ref(), &info)?;
+        if info.sender != proposal.proposer {
+            return Err(ContractError::Unauthorized {});
+        }
+    }
+
+    proposal.status = Status::Cancelled;
+    PROPOSALS.save(deps.storage, proposal_id.into(), &proposal)?;
+
+    Ok(Response::new().add_event(
+        Event::new("proposal_cancelled")
+            .add_attribute("proposal_id", proposal_id.to_string())
+            .add_attribute("signer", info.sender),
+    ))
+}
+
+pub fn execute_execute(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut proposal = load_pending(deps.as_ref(), proposal_id)?;
+    // Approvals of removed signers no longer count
+    if approval_count(&proposal, &config) < config.threshold {
+        return Err(ContractError::NotEnoughApprovals {});
+    }
+    match proposal.executable_at {
+        Some(executable_at) if env.block.time.seconds() >= executable_at => {}
+        _ => return Err(ContractError::DelayNotPassed {}),
+    }
+
+    proposal.status = Status::Executed;
+    PROPOSALS.save(deps.storage, proposal_id.into(), &proposal)?;
+
+    let messages = proposal.calls.into_iter().map(|call| WasmMsg::Execute {
+        contract_addr: call.contract_addr,
+        msg: call.msg,
+        funds: call.funds,
+    });
+    Ok(Response::new().add_messages(messages).add_event(
+        Event::new("proposal_executed").add_attribute("proposal_id", proposal_id.to_string()),
+    ))
+}
+
+pub fn execute_accept_ownership(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    contract: String,
+) -> Result<Response, ContractError> {
+    validate_signer(deps.as_ref(), &info)?;
+    let contract = deps.api.addr_validate(&contract)?;
+
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: contract.to_string(),
+            msg: to_binary(&owned::msg::ExecuteMsg::AcceptOwnership {})?,
+            funds: vec![],
+        })
+        .add_event(Event::new("ownership_accepted").add_attribute("contract", contract)))
+}
+
+pub fn execute_update_config(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    signers: Vec<String>,
+    threshold: u32,
+    min_delay: u64,
+) -> Result<Response, ContractError> {
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+    let config = validate_config(deps.as_ref(), signers, threshold, min_delay)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    // Open proposals meeting a lowered threshold are queued right away
+    let open = PROPOSALS
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|entry| matches!(entry, Ok((_, proposal)) if proposal.status == Status::Open))
+        .collect::<StdResult<Vec<_>>>()?;
+    let mut queued = vec![];
+    for (key, mut proposal) in open {
+        queue_if_approved(&mut proposal, &config, &env);
+        if proposal.status == Status::Queued {
+            let proposal_id = u64::from_be_bytes(key.as_slice().try_into().unwrap());
+            PROPOSALS.save(deps.storage, proposal_id.into(), &proposal)?;
+            queued.push(proposal_id.to_string());
+        }
+    }
+
+    let mut event = Event::new("config_updated")
+        .add_attribute("signers", config.signers.len().to_string())
+        .add_attribute("threshold", threshold.to_string())
+        .add_attribute("min_delay", min_delay.to_string());
+    if !queued.is_empty() {
+        event = event.add_attribute("queued", queued.join(","));
+    }
+    Ok(Response::new().add_event(event))
+}
+
+pub fn query_proposal(deps: Deps, proposal_id: u64) -> StdResult<ProposalResponse> {
+    let proposal = PROPOSALS.load(deps.storage, proposal_id.into())?;
+    Ok(proposal_response(proposal_id, proposal))
+}
+
+pub fn query_proposals(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ProposalsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|id| Bound::exclusive(id.to_be_bytes().to_vec()));
+
+    let proposals = PROPOSALS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|entry| {
+            let (key, proposal) = entry?;
+            let proposal_id = u64::from_be_bytes(key.as_slice().try_into().unwrap());
+            Ok(proposal_response(proposal_id, proposal))
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(ProposalsResponse { proposals })
+}
+
+fn proposal_response(id: u64, proposal: Proposal) -> ProposalResponse {
+    ProposalResponse {
+        id,
+        proposer: proposal.proposer,
+        description: proposal.description,
+        calls: proposal.calls,
+        approvals: proposal.approvals,
+        status: proposal.status,
+        executable_at: proposal.executable_at,
+    }
+}
+
+fn validate_config(
+    deps: Deps,
+    signers: Vec<String>,
+    threshold: u32,
+    min_delay: u64,
+) -> Result<Config, ContractError> {
+    let mut validated: Vec<Addr> = vec![];
+    for signer in signers {
+        let signer = deps.api.addr_validate(&signer)?;
+        if validated.contains(&signer) {
+            return Err(ContractError::DuplicateSigner {});
+        }
+        validated.push(signer);
+    }
+    if threshold == 0 || threshold as usize > validated.len() {
+        return Err(ContractError::InvalidThreshold {});
+    }
+    if min_delay > MAX_MIN_DELAY {
+        return Err(ContractError::InvalidMinDelay {});
+    }
+
+    Ok(Config {
+        signers: validated,
+        threshold,
+        min_delay,
+    })
+}
+
+fn validate_signer(deps: Deps, info: &MessageInfo) -> Result<Config, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if !config.signers.contains(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(config)
+}
+
+/// Loads a proposal that is neither executed nor cancelled
+fn load_pending(deps: Deps, proposal_id: u64) -> Result<Proposal, ContractError> {
+    let proposal = PROPOSALS.load(deps.storage, proposal_id.into())?;
+    match proposal.status {
+        Status::Open | Status::Queued => Ok(proposal),
+        Status::Executed | Status::Cancelled => Err(ContractError::ProposalNotOpen {}),
+    }
+}
+
+fn approval_count(proposal: &Proposal, config: &Config) -> u32 {
+    proposal
+        .approvals
+        .iter()
+        .filter(|signer| config.signers.contains(signer))
+        .count() as u32
+}
+
+/// Starts the delay once the proposal reaches the threshold
+fn queue_if_approved(proposal: &mut Proposal, config: &Config, env: &Env) {
+    if proposal.status == Status::Open && approval_count(proposal, config) >= config.threshold {
+        proposal.status = Status::Queued;
+        let executable_at = env.block.time.seconds().saturating_add(config.min_delay);
+        proposal.executable_at = Some(executable_at);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier};
+    use cosmwasm_std::{from_binary, CosmosMsg, MemoryStorage, OwnedDeps, StdError};
+
+    fn setup() -> OwnedDeps<MemoryStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies(&[]);
+        let msg = InstantiateMsg {
+            signers: vec!["alice".to_owned(), "bob".to_owned(), "carol".to_owned()],
+            threshold: 2,
+            min_delay: 100,
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+        deps
+    }
+
+    fn call() -> Call {
+        Call {
+            contract_addr: "target".to_owned(),
+            msg: to_binary(&owned::msg::ExecuteMsg::RenounceOwnership {}).unwrap(),
+            funds: vec![],
+        }
+    }
+
+    fn run(deps: DepsMut, sender: &str, msg: ExecuteMsg) -> Result<Response, ContractError> {
+        execute(deps, mock_env(), mock_info(sender, &[]), msg)
+    }
+
+    fn propose(deps: DepsMut, sender: &str) -> Result<Response, ContractError> {
+        let msg = ExecuteMsg::Propose {
+            description: "Renounce".to_owned(),
+            calls: vec![call()],
+        };
+        run(deps, sender, msg)
+    }
+
+    #[test]
+    fn instantiate_validates_config() {
+        let mut deps = mock_dependencies(&[]);
+        let msg = |signers: &[&str], threshold| InstantiateMsg {
+            signers: signers.iter().map(|s| s.to_string()).collect(),
+            threshold,
+            min_delay: 0,
+        };
+        let info = mock_info("creator", &[]);
+
+        let err = instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            msg(&["alice", "bob"], 0),
+        );
+        assert_eq!(err.unwrap_err(), ContractError::InvalidThreshold {});
+        let err = instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            msg(&["alice", "bob"], 3),
+        );
+        assert_eq!(err.unwrap_err(), ContractError::InvalidThreshold {});
+        let err = instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            msg(&["alice", "alice"], 1),
+        );
+        assert_eq!(err.unwrap_err(), ContractError::DuplicateSigner {});
+        let delayed = InstantiateMsg {
+            min_delay: MAX_MIN_DELAY + 1,
+            ..msg(&["alice", "bob"], 2)
+        };
+        let err = instantiate(deps.as_mut(), mock_env(), info.clone(), delayed);
+        assert_eq!(err.unwrap_err(), ContractError::InvalidMinDelay {});
+        instantiate(deps.as_mut(), mock_env(), info, msg(&["alice", "bob"], 2)).unwrap();
+    }
+
+    #[test]
+    fn approve_and_execute() {
+        let mut deps = setup();
+
+        let err = propose(deps.as_mut(), "mallory").unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let err = run(
+            deps.as_mut(),
+            "alice",
+            ExecuteMsg::Propose {
+                description: "Nothing".to_owned(),
+                calls: vec![],
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::EmptyProposal {});
+
+        let res = propose(deps.as_mut(), "alice").unwrap();
+        let proposal_id: u64 = from_binary(&res.data.unwrap()).unwrap();
+        assert_eq!(proposal_id, 1);
+
+        let err = run(deps.as_mut(), "bob", ExecuteMsg::Execute { proposal_id }).unwrap_err();
+        assert_eq!(err, ContractError::NotEnoughApprovals {});
+        let err = run(deps.as_mut(), "alice", ExecuteMsg::Approve { proposal_id }).unwrap_err();
+        assert_eq!(err, ContractError::AlreadyApproved {});
+
+        run(deps.as_mut(), "bob", ExecuteMsg::Approve { proposal_id }).unwrap();
+        let proposal = query_proposal(deps.as_ref(), proposal_id).unwrap();
+        assert_eq!(proposal.status, Status::Queued);
+        let now = mock_env().block.time.seconds();
+        assert_eq!(proposal.executable_at, Some(now + 100));
+
+        let err = run(deps.as_mut(), "bob", ExecuteMsg::Execute { proposal_id }).unwrap_err();
+        assert_eq!(err, ContractError::DelayNotPassed {});
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(100);
+        let msg = ExecuteMsg::Execute { proposal_id };
+        let res = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), msg).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "target".to_owned(),
+                msg: call().msg,
+                funds: vec![],
+            })
+        );
+        let proposal = query_proposal(deps.as_ref(), proposal_id).unwrap();
+        assert_eq!(proposal.status, Status::Executed);
+
+        let msg = ExecuteMsg::Execute { proposal_id };
+        let err = execute(deps.as_mut(), env, mock_info("anyone", &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::ProposalNotOpen {});
+    }
+
+    #[test]
+    fn cancel() {
+        let mut deps = setup();
+        propose(deps.as_mut(), "alice").unwrap();
+
+        let err = run(
+            deps.as_mut(),
+            "mallory",
+            ExecuteMsg::Cancel { proposal_id: 1 },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        // Only the proposer can cancel
+        run(deps.as_mut(), "bob", ExecuteMsg::Approve { proposal_id: 1 }).unwrap();
+        let err = run(
+            deps.as_mut(),
+            "carol",
+            ExecuteMsg::Cancel { proposal_id: 1 },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        run(
+            deps.as_mut(),
+            "alice",
+            ExecuteMsg::Cancel { proposal_id: 1 },
+        )
+        .unwrap();
+
+        let err = run(deps.as_mut(), "bob", ExecuteMsg::Approve { proposal_id: 1 }).unwrap_err();
+        assert_eq!(err, ContractError::ProposalNotOpen {});
+        let err = run(deps.as_mut(), "bob", ExecuteMsg::Cancel { proposal_id: 2 }).unwrap_err();
+        assert!(matches!(err, ContractError::Std(StdError::NotFound { .. })));
+
+        propose(deps.as_mut(), "bob").unwrap();
+        let res = query_proposals(deps.as_ref(), Some(1), None).unwrap();
+        assert_eq!(res.proposals.len(), 1);
+        assert_eq!(res.proposals[0].id, 2);
+        assert_eq!(res.proposals[0].status, Status::Open);
+
+        // or the governor itself
+        let contract = mock_env().contract.address;
+        run(
+            deps.as_mut(),
+            contract.as_str(),
+            ExecuteMsg::Cancel { proposal_id: 2 },
+        )
+        .unwrap();
+        let proposal = query_proposal(deps.as_ref(), 2).unwrap();
+        assert_eq!(proposal.status, Status::Cancelled);
+    }
+
+    #[test]
+    fn update_config() {
+        let mut deps = setup();
+        propose(deps.as_mut(), "alice").unwrap();
+        run(deps.as_mut(), "bob", ExecuteMsg::Approve { proposal_id: 1 }).unwrap();
+
+        let update = ExecuteMsg::UpdateConfig {
+            signers: vec!["alice".to_owned(), "carol".to_owned()],
+            threshold: 2,
+            min_delay: 0,
+        };
+        let err = run(deps.as_mut(), "alice", update.clone()).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let contract = mock_env().contract.address;
+        run(deps.as_mut(), contract.as_str(), update).unwrap();
+
+        // Bob's approval no longer counts
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(100);
+        let msg = ExecuteMsg::Execute { proposal_id: 1 };
+        let err = execute(deps.as_mut(), env, mock_info("alice", &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::NotEnoughApprovals {});
+
+        // Open proposals meeting a lowered threshold are queued
+        propose(deps.as_mut(), "carol").unwrap();
+        let update = ExecuteMsg::UpdateConfig {
+            signers: vec!["alice".to_owned(), "carol".to_owned()],
+            threshold: 1,
+            min_delay: 0,
+        };
+        let res = run(deps.as_mut(), contract.as_str(), update).unwrap();
+        assert_eq!(res.events[0].attributes[3], ("queued", "2"));
+        let proposal = query_proposal(deps.as_ref(), 2).unwrap();
+        assert_eq!(proposal.status, Status::Queued);
+        let msg = ExecuteMsg::Execute { proposal_id: 2 };
+        run(deps.as_mut(), "anyone", msg).unwrap();
+    }
+}
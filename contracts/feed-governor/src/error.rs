@@ -0,0 +1,44 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    /// Sender is not a signer
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    /// Threshold is zero or exceeds the number of signers
+    #[error("Invalid threshold")]
+    InvalidThreshold {},
+
+    /// A signer is listed more than once
+    #[error("Duplicate signer")]
+    DuplicateSigner {},
+
+    /// Proposal has no calls
+    #[error("Empty proposal")]
+    EmptyProposal {},
+
+    /// Proposal was already executed or cancelled
+    #[error("Proposal is not open")]
+    ProposalNotOpen {},
+
+    /// Minimum delay exceeds a year
+    #[error("Invalid minimum delay")]
+    InvalidMinDelay {},
+
+    /// Signer already approved the proposal
+    #[error("Already approved")]
+    AlreadyApproved {},
+
+    /// Proposal lacks approvals of current signers
+    #[error("Not enough approvals")]
+    NotEnoughApprovals {},
+
+    /// Minimum delay after the proposal got enough approvals hasn't passed
+    #[error("Proposal can't be executed yet")]
+    DelayNotPassed {},
+}
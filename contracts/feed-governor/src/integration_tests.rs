@@ -0,0 +1,327 @@
+#![cfg(test)]
+
+use chainlink_testing::{contract_mock_aggregator, MockAggregatorInstantiateMsg};
+use cosmwasm_std::{
+    testing::{mock_env, MockApi, MockStorage},
+    to_binary, Addr, Binary, Empty, Uint128,
+};
+use cw_multi_test::{App, BankKeeper, Contract, ContractWrapper, Executor};
+use serde::Serialize;
+
+use crate::{
+    contract::{execute, instantiate, query},
+    error::ContractError,
+    msg::{ExecuteMsg, InstantiateMsg, ProposalResponse, QueryMsg},
+    state::{Call, Status},
+};
+
+const MIN_DELAY: u64 = 3600;
+
+fn mock_app() -> App {
+    let env = mock_env();
+    let api = MockApi::default();
+    let bank = BankKeeper::new();
+    let storage = MockStorage::new();
+
+    App::new(api, env.block, bank, storage)
+}
+
+pub fn contract_feed_governor() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(execute, instantiate, query);
+    Box::new(contract)
+}
+
+pub fn contract_flux_aggregator() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        flux_aggregator::contract::execute,
+        flux_aggregator::contract::instantiate,
+        flux_aggregator::contract::query,
+    );
+    Box::new(contract)
+}
+
+pub fn contract_proxy() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        aggregator_proxy::contract::execute,
+        aggregator_proxy::contract::instantiate,
+        aggregator_proxy::contract::query,
+    );
+    Box::new(contract)
+}
+
+pub fn contract_link_token() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    );
+    Box::new(contract)
+}
+
+/// Governor with signers alice, bob and carol, two of which have to approve
+fn init_governor(app: &mut App) -> Addr {
+    let id = app.store_code(contract_feed_governor());
+    app.instantiate_contract(
+        id,
+        Addr::unchecked("owner"),
+        &InstantiateMsg {
+            signers: vec!["alice".to_owned(), "bob".to_owned(), "carol".to_owned()],
+            threshold: 2,
+            min_delay: MIN_DELAY,
+        },
+        &[],
+        "Feed Governor",
+        None,
+    )
+    .unwrap()
+}
+
+/// Transfers the ownership of `contract` from "owner" to the governor
+fn hand_over(app: &mut App, governor: &Addr, contract: &Addr) {
+    app.execute_contract(
+        Addr::unchecked("owner"),
+        contract.clone(),
+        &owned::msg::ExecuteMsg::TransferOwnership {
+            to: governor.to_string(),
+            expires_at: None,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("alice"),
+        governor.clone(),
+        &ExecuteMsg::AcceptOwnership {
+            contract: contract.to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let owner: Addr = app
+        .wrap()
+        .query_wasm_smart(contract, &owned::msg::QueryMsg::GetOwner {})
+        .unwrap();
+    assert_eq!(&owner, governor);
+}
+
+fn call<T: Serialize>(contract: &Addr, msg: &T) -> Call {
+    Call {
+        contract_addr: contract.to_string(),
+        msg: to_binary(msg).unwrap(),
+        funds: vec![],
+    }
+}
+
+/// Proposes the calls as alice, approves them as bob and waits for the delay
+fn pass(app: &mut App, governor: &Addr, calls: Vec<Call>) -> u64 {
+    let res = app
+        .execute_contract(
+            Addr::unchecked("alice"),
+            governor.clone(),
+            &ExecuteMsg::Propose {
+                description: "Proposal".to_owned(),
+                calls,
+            },
+            &[],
+        )
+        .unwrap();
+    let proposal_id: u64 = cosmwasm_std::from_binary(&res.data.unwrap()).unwrap();
+
+    let approve = ExecuteMsg::Approve { proposal_id };
+    app.execute_contract(Addr::unchecked("bob"), governor.clone(), &approve, &[])
+        .unwrap();
+    app.update_block(|block| block.time = block.time.plus_seconds(MIN_DELAY));
+    proposal_id
+}
+
+#[test]
+fn governs_flux_aggregator() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("owner");
+    let governor = init_governor(&mut app);
+
+    let id = app.store_code(contract_link_token());
+    let link = app
+        .instantiate_contract(
+            id,
+            owner.clone(),
+            &cw20_base::msg::InstantiateMsg {
+                name: String::from("Chainlink"),
+                symbol: String::from("LINK"),
+                decimals: 18,
+                initial_balances: vec![cw20::Cw20Coin {
+                    address: owner.to_string(),
+                    amount: Uint128::new(100),
+                }],
+                mint: None,
+                marketing: None,
+            },
+            &[],
+            "LINK",
+            None,
+        )
+        .unwrap();
+    let id = app.store_code(contract_flux_aggregator());
+    let flux = app
+        .instantiate_contract(
+            id,
+            owner.clone(),
+            &flux_aggregator::msg::InstantiateMsg {
+                link: link.to_string(),
+                payment_amount: Uint128::new(3),
+                timeout: 1800,
+                validator: "validator".to_owned(),
+                min_submission_value: Uint128::new(1),
+                max_submission_value: Uint128::new(10000000),
+                decimals: 18,
+                description: "LINK/USD".to_owned(),
+            },
+            &[],
+            "Flux aggregator",
+            None,
+        )
+        .unwrap();
+    app.execute_contract(
+        owner.clone(),
+        link,
+        &cw20_base::msg::ExecuteMsg::Send {
+            contract: flux.to_string(),
+            amount: Uint128::new(100),
+            msg: Binary::from(b""),
+        },
+        &[],
+    )
+    .unwrap();
+    hand_over(&mut app, &governor, &flux);
+
+    let change_oracles = flux_aggregator::msg::ExecuteMsg::ChangeOracles {
+        removed: vec![],
+        added: vec!["oracle".to_owned()],
+        added_admins: vec!["oracle_admin".to_owned()],
+        min_submissions: 1,
+        max_submissions: 1,
+        restart_delay: 0,
+    };
+    // The previous owner lost its privileges
+    let err = app
+        .execute_contract(owner, flux.clone(), &change_oracles, &[])
+        .unwrap_err();
+    assert_eq!(
+        err,
+        flux_aggregator::error::ContractError::Owned(owned::error::ContractError::MissingRole {
+            role: owned::roles::ORACLE_MANAGER.to_owned()
+        })
+        .to_string()
+    );
+
+    let proposal_id = pass(&mut app, &governor, vec![call(&flux, &change_oracles)]);
+    app.execute_contract(
+        Addr::unchecked("anyone"),
+        governor.clone(),
+        &ExecuteMsg::Execute { proposal_id },
+        &[],
+    )
+    .unwrap();
+
+    let oracles: Vec<Addr> = app
+        .wrap()
+        .query_wasm_smart(&flux, &flux_aggregator::msg::QueryMsg::GetOracles {})
+        .unwrap();
+    assert_eq!(oracles, vec![Addr::unchecked("oracle")]);
+    let proposal: ProposalResponse = app
+        .wrap()
+        .query_wasm_smart(&governor, &QueryMsg::GetProposal { proposal_id })
+        .unwrap();
+    assert_eq!(proposal.status, Status::Executed);
+}
+
+#[test]
+fn governs_aggregator_proxy() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("owner");
+    let governor = init_governor(&mut app);
+
+    let id = app.store_code(contract_mock_aggregator());
+    let mut aggregators = vec![];
+    for label in &["Current", "Next"] {
+        let aggregator = app
+            .instantiate_contract(
+                id,
+                owner.clone(),
+                &MockAggregatorInstantiateMsg {
+                    decimals: 8,
+                    description: "LUNA / USD".to_owned(),
+                    initial_answer: Some(Uint128::new(100)),
+                },
+                &[],
+                *label,
+                None,
+            )
+            .unwrap();
+        aggregators.push(aggregator);
+    }
+    let id = app.store_code(contract_proxy());
+    let proxy = app
+        .instantiate_contract(
+            id,
+            owner,
+            &aggregator_proxy::msg::InstantiateMsg {
+                aggregator: aggregators[0].to_string(),
+                confirmation_delay: None,
+            },
+            &[],
+            "Proxy",
+            None,
+        )
+        .unwrap();
+    hand_over(&mut app, &governor, &proxy);
+
+    let aggregator = aggregators[1].to_string();
+    let calls = vec![
+        call(
+            &proxy,
+            &aggregator_proxy::msg::ExecuteMsg::ProposeAggregator {
+                aggregator: aggregator.clone(),
+            },
+        ),
+        call(
+            &proxy,
+            &aggregator_proxy::msg::ExecuteMsg::ConfirmAggregator { aggregator },
+        ),
+    ];
+
+    // Cancelled proposals can't be executed
+    let proposal_id = pass(&mut app, &governor, calls.clone());
+    app.execute_contract(
+        Addr::unchecked("alice"),
+        governor.clone(),
+        &ExecuteMsg::Cancel { proposal_id },
+        &[],
+    )
+    .unwrap();
+    let err = app
+        .execute_contract(
+            Addr::unchecked("anyone"),
+            governor.clone(),
+            &ExecuteMsg::Execute { proposal_id },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::ProposalNotOpen {}.to_string());
+
+    let proposal_id = pass(&mut app, &governor, calls);
+    app.execute_contract(
+        Addr::unchecked("anyone"),
+        governor,
+        &ExecuteMsg::Execute { proposal_id },
+        &[],
+    )
+    .unwrap();
+
+    let current: Addr = app
+        .wrap()
+        .query_wasm_smart(&proxy, &aggregator_proxy::msg::QueryMsg::GetAggregator {})
+        .unwrap();
+    assert_eq!(current, aggregators[1]);
+}
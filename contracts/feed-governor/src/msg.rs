@@ -0,0 +1,73 @@
+use cosmwasm_std::Addr;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::state::{Call, Status};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub signers: Vec<String>,
+    /// Approvals of signers needed to execute a proposal
+    pub threshold: u32,
+    /// Minimum seconds between a proposal reaching the threshold and its execution,
+    /// at most a year
+    pub min_delay: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Queue calls to be sent by the governor, approved by the proposer.
+    /// Response contains the new `proposal_id`. Can be used only by signers
+    Propose {
+        description: String,
+        calls: Vec<Call>,
+    },
+    /// Can be used only by signers
+    Approve { proposal_id: u64 },
+    /// Cancel an open or queued proposal. Can be used only by its proposer, or by
+    /// the governor itself through a proposal
+    Cancel { proposal_id: u64 },
+    /// Send the calls of a queued proposal once its delay passed
+    Execute { proposal_id: u64 },
+    /// Accept the ownership of an `owned` contract transferred to the governor.
+    /// Can be used only by signers
+    AcceptOwnership { contract: String },
+    /// Replace the signers, threshold and delay. Open proposals meeting the new
+    /// threshold are queued. Can be used only by the governor itself, through a proposal
+    UpdateConfig {
+        signers: Vec<String>,
+        threshold: u32,
+        min_delay: u64,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Response: [`crate::state::Config`]
+    GetConfig {},
+    /// Response: [`ProposalResponse`]
+    GetProposal { proposal_id: u64 },
+    /// Proposals in ascending order. Response: [`ProposalsResponse`]
+    GetProposals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProposalResponse {
+    pub id: u64,
+    pub proposer: Addr,
+    pub description: String,
+    pub calls: Vec<Call>,
+    pub approvals: Vec<Addr>,
+    pub status: Status,
+    pub executable_at: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProposalsResponse {
+    pub proposals: Vec<ProposalResponse>,
+}
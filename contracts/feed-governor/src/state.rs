@@ -0,0 +1,50 @@
+use cosmwasm_std::{Addr, Binary, Coin};
+use cw_storage_plus::{Item, Map, U64Key};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub signers: Vec<Addr>,
+    /// Approvals of signers needed to execute a proposal
+    pub threshold: u32,
+    /// Minimum seconds between a proposal reaching the threshold and its execution,
+    /// at most a year
+    pub min_delay: u64,
+}
+
+/// `WasmMsg::Execute` sent by the governor when a proposal is executed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Call {
+    pub contract_addr: String,
+    pub msg: Binary,
+    #[serde(default)]
+    pub funds: Vec<Coin>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    /// Waiting for approvals
+    Open,
+    /// Has enough approvals, executable once the delay passed
+    Queued,
+    Executed,
+    Cancelled,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Proposal {
+    pub proposer: Addr,
+    pub description: String,
+    pub calls: Vec<Call>,
+    pub approvals: Vec<Addr>,
+    pub status: Status,
+    /// Earliest block time the proposal can be executed at, in seconds. Set when it
+    /// reaches the threshold
+    pub executable_at: Option<u64>,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
+pub const PROPOSALS: Map<U64Key, Proposal> = Map::new("proposals");
@@ -0,0 +1,271 @@
+use cosmwasm_std::{
+    to_binary, to_vec, Addr, Binary, ContractResult, Deps, DepsMut, Empty, Env, Event, MessageInfo,
+    Order, QueryRequest, Response, StdError, StdResult, SystemResult, WasmQuery,
+};
+use cw_storage_plus::{Bound, PrimaryKey};
+
+use crate::error::ContractError;
+use crate::msg::*;
+use crate::state::*;
+
+use owned::contract::{
+    execute_accept_ownership, execute_cancel_ownership_transfer, execute_renounce_ownership,
+    execute_transfer_ownership, get_owner, get_ownership_state, instantiate as owned_init,
+};
+use owned::roles::{
+    execute_grant_role, execute_renounce_role, execute_revoke_role, has_role, query_role_members,
+    validate_role, CONFIG_ADMIN,
+};
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    owned_init(deps, env, info, owned::msg::InstantiateMsg {})?;
+
+    Ok(Response::default())
+}
+
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::ProposeFeed { base, quote, proxy } => {
+            execute_propose_feed(deps, env, info, base, quote, proxy)
+        }
+        ExecuteMsg::ConfirmFeed { base, quote, proxy } => {
+            execute_confirm_feed(deps, env, info, base, quote, proxy)
+        }
+        ExecuteMsg::CancelProposedFeed { base, quote } => {
+            execute_cancel_proposed_feed(deps, env, info, base, quote)
+        }
+        ExecuteMsg::RemoveFeed { base, quote } => execute_remove_feed(deps, env, info, base, quote),
+        ExecuteMsg::TransferOwnership { to, expires_at } => {
+            execute_transfer_ownership(deps, env, info, to, expires_at).map_err(ContractError::from)
+        }
+        ExecuteMsg::AcceptOwnership {} => {
+            execute_accept_ownership(deps, env, info).map_err(ContractError::from)
+        }
+        ExecuteMsg::CancelOwnershipTransfer {} => {
+            execute_cancel_ownership_transfer(deps, env, info).map_err(ContractError::from)
+        }
+        ExecuteMsg::RenounceOwnership {} => {
+            execute_renounce_ownership(deps, env, info).map_err(ContractError::from)
+        }
+        ExecuteMsg::GrantRole { role, account } => {
+            execute_grant_role(deps, env, info, role, account).map_err(ContractError::from)
+        }
+        ExecuteMsg::RevokeRole { role, account } => {
+            execute_revoke_role(deps, env, info, role, account).map_err(ContractError::from)
+        }
+        ExecuteMsg::RenounceRole { role } => {
+            execute_renounce_role(deps, env, info, role).map_err(ContractError::from)
+        }
+    }
+}
+
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::GetFeed { base, quote } => to_binary(&query_feed(deps, base, quote)?),
+        QueryMsg::GetProposedFeed { base, quote } => {
+            to_binary(&query_proposed_feed(deps, base, quote)?)
+        }
+        QueryMsg::GetFeeds { start_after, limit } => {
+            to_binary(&query_feeds(deps, start_after, limit)?)
+        }
+        QueryMsg::AggregatorQuery { base, quote, query } => {
+            query_aggregator(deps, base, quote, query)
+        }
+        QueryMsg::GetOwner {} => to_binary(&get_owner(deps)?),
+        QueryMsg::GetOwnershipState {} => to_binary(&get_ownership_state(deps)?),
+        QueryMsg::HasRole { role, account } => {
+            to_binary(&has_role(deps, &role, &deps.api.addr_validate(&account)?)?)
+        }
+        QueryMsg::GetRoleMembers {
+            role,
+            start_after,
+            limit,
+        } => to_binary(&query_role_members(deps, role, start_after, limit)?),
+    }
+}
+
+pub fn execute_propose_feed(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    base: String,
+    quote: String,
+    proxy: String,
+) -> Result<Response, ContractError> {
+    validate_role(deps.as_ref(), &info, CONFIG_ADMIN)?;
+
+    let proxy = deps.api.addr_validate(&proxy)?;
+    PROPOSED_FEEDS.save(deps.storage, (&base, &quote), &proxy)?;
+
+    Ok(Response::new().add_event(
+        Event::new("feed_proposed")
+            .add_attribute("base", base)
+            .add_attribute("quote", quote)
+            .add_attribute("proxy", proxy),
+    ))
+}
+
+pub fn execute_confirm_feed(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    base: String,
+    quote: String,
+    proxy: String,
+) -> Result<Response, ContractError> {
+    validate_role(deps.as_ref(), &info, CONFIG_ADMIN)?;
+
+    let proxy = deps.api.addr_validate(&proxy)?;
+    let proposed = PROPOSED_FEEDS
+        .may_load(deps.storage, (&base, &quote))?
+        .ok_or(ContractError::NoProposedFeed {})?;
+    if proposed != proxy {
+        return Err(ContractError::InvalidProposedFeed {});
+    }
+
+    let previous = FEEDS.may_load(deps.storage, (&base, &quote))?;
+    PROPOSED_FEEDS.remove(deps.storage, (&base, &quote));
+    FEEDS.save(deps.storage, (&base, &quote), &proxy)?;
+
+    let mut event = Event::new("feed_confirmed")
+        .add_attribute("base", base)
+        .add_attribute("quote", quote)
+        .add_attribute("proxy", proxy);
+    if let Some(previous) = previous {
+        event = event.add_attribute("previous_proxy", previous);
+    }
+    Ok(Response::new().add_event(event))
+}
+
+pub fn execute_cancel_proposed_feed(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    base: String,
+    quote: String,
+) -> Result<Response, ContractError> {
+    validate_role(deps.as_ref(), &info, CONFIG_ADMIN)?;
+
+    let proposed = PROPOSED_FEEDS
+        .may_load(deps.storage, (&base, &quote))?
+        .ok_or(ContractError::NoProposedFeed {})?;
+    PROPOSED_FEEDS.remove(deps.storage, (&base, &quote));
+
+    Ok(Response::new().add_event(
+        Event::new("feed_proposal_cancelled")
+            .add_attribute("base", base)
+            .add_attribute("quote", quote)
+            .add_attribute("proxy", proposed),
+    ))
+}
+
+pub fn execute_remove_feed(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    base: String,
+    quote: String,
+) -> Result<Response, ContractError> {
+    validate_role(deps.as_ref(), &info, CONFIG_ADMIN)?;
+
+    let proxy = FEEDS
+        .may_load(deps.storage, (&base, &quote))?
+        .ok_or(ContractError::FeedNotFound {})?;
+    FEEDS.remove(deps.storage, (&base, &quote));
+
+    Ok(Response::new().add_event(
+        Event::new("feed_removed")
+            .add_attribute("base", base)
+            .add_attribute("quote", quote)
+            .add_attribute("proxy", proxy),
+    ))
+}
+
+pub fn query_feed(deps: Deps, base: String, quote: String) -> StdResult<Addr> {
+    FEEDS
+        .may_load(deps.storage, (&base, &quote))?
+        .ok_or_else(|| StdError::generic_err(ContractError::FeedNotFound {}.to_string()))
+}
+
+pub fn query_proposed_feed(deps: Deps, base: String, quote: String) -> StdResult<Addr> {
+    PROPOSED_FEEDS
+        .may_load(deps.storage, (&base, &quote))?
+        .ok_or_else(|| StdError::generic_err(ContractError::NoProposedFeed {}.to_string()))
+}
+
+pub fn query_feeds(
+    deps: Deps,
+    start_after: Option<(String, String)>,
+    limit: Option<u32>,
+) -> StdResult<FeedsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .map(|(base, quote)| Bound::exclusive((base.as_str(), quote.as_str()).joined_key()));
+
+    let feeds = FEEDS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|entry| {
+            let (key, proxy) = entry?;
+            let (base, quote) = parse_pair(&key)?;
+            Ok(FeedResponse { base, quote, proxy })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(FeedsResponse { feeds })
+}
+
+/// Forwards the query to the pair's proxy, returning its response as is
+pub fn query_aggregator(
+    deps: Deps,
+    base: String,
+    quote: String,
+    query: chainlink_aggregator::QueryMsg,
+) -> StdResult<Binary> {
+    let proxy = query_feed(deps, base, quote)?;
+    let request: QueryRequest<Empty> = WasmQuery::Smart {
+        contract_addr: proxy.into_string(),
+        msg: to_binary(&query.wrap())?,
+    }
+    .into();
+    match deps.querier.raw_query(&to_vec(&request)?) {
+        SystemResult::Err(system_err) => Err(StdError::generic_err(format!(
+            "Querier system error: {}",
+            system_err
+        ))),
+        SystemResult::Ok(ContractResult::Err(contract_err)) => Err(StdError::generic_err(format!(
+            "Querier contract error: {}",
+            contract_err
+        ))),
+        SystemResult::Ok(ContractResult::Ok(value)) => Ok(value),
+    }
+}
+
+/// Splits a `(base, quote)` key, where the base is prefixed with its length
+fn parse_pair(key: &[u8]) -> StdResult<(String, String)> {
+    let invalid = || StdError::generic_err("Invalid pair key");
+    if key.len() < 2 {
+        return Err(invalid());
+    }
+    let (len, rest) = key.split_at(2);
+    let len = u16::from_be_bytes([len[0], len[1]]) as usize;
+    if rest.len() < len {
+        return Err(invalid());
+    }
+    let (base, quote) = rest.split_at(len);
+    let to_string = |bytes: &[u8]| String::from_utf8(bytes.to_vec()).map_err(|_| invalid());
+    Ok((to_string(base)?, to_string(quote)?))
+}
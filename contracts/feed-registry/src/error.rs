@@ -0,0 +1,19 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+    /// No feed has been proposed for the pair
+    #[error("No feed proposed for the pair")]
+    NoProposedFeed {},
+    /// Confirmed feed doesn't match the proposed one
+    #[error("Invalid proposed feed")]
+    InvalidProposedFeed {},
+    /// No feed has been confirmed for the pair
+    #[error("No feed registered for the pair")]
+    FeedNotFound {},
+    #[error("{0}")]
+    OwnedError(#[from] owned::error::ContractError),
+}
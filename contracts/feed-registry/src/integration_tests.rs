@@ -0,0 +1,308 @@
+#![cfg(test)]
+
+use chainlink_aggregator::{
+    FeedRegistryQuerier, LatestAnswerResponse, QueryMsg as AggregatorQueryMsg, RoundDataResponse,
+};
+use chainlink_testing::{contract_mock_aggregator, MockAggregatorInstantiateMsg};
+use cosmwasm_std::{
+    testing::{mock_env, MockApi, MockStorage},
+    Addr, Empty, StdResult, Uint128,
+};
+use cw_multi_test::{App, AppResponse, BankKeeper, Contract, ContractWrapper, Executor};
+
+use crate::{
+    contract::{execute, instantiate, query},
+    error::ContractError,
+    msg::{ExecuteMsg, FeedResponse, FeedsResponse, InstantiateMsg, QueryMsg},
+};
+
+static OWNER: &str = "owner";
+
+fn mock_app() -> App {
+    let env = mock_env();
+    let api = MockApi::default();
+    let bank = BankKeeper::new();
+    let storage = MockStorage::new();
+
+    App::new(api, env.block, bank, storage)
+}
+
+pub fn contract_feed_registry() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(execute, instantiate, query);
+    Box::new(contract)
+}
+
+fn instantiate_proxy(router: &mut App, description: &str, answer: u128) -> Addr {
+    let id = router.store_code(contract_mock_aggregator());
+    router
+        .instantiate_contract(
+            id,
+            Addr::unchecked(OWNER),
+            &MockAggregatorInstantiateMsg {
+                decimals: 8,
+                description: description.to_owned(),
+                initial_answer: Some(Uint128::new(answer)),
+            },
+            &[],
+            "Proxy",
+            None,
+        )
+        .unwrap()
+}
+
+fn default_init() -> (App, Addr) {
+    let mut router = mock_app();
+    let id = router.store_code(contract_feed_registry());
+    let registry = router
+        .instantiate_contract(
+            id,
+            Addr::unchecked(OWNER),
+            &InstantiateMsg {},
+            &[],
+            "Feed Registry",
+            None,
+        )
+        .unwrap();
+    (router, registry)
+}
+
+fn propose(router: &mut App, registry: &Addr, base: &str, quote: &str, proxy: &Addr) {
+    router
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            registry.clone(),
+            &ExecuteMsg::ProposeFeed {
+                base: base.to_owned(),
+                quote: quote.to_owned(),
+                proxy: proxy.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+}
+
+fn confirm(
+    router: &mut App,
+    registry: &Addr,
+    base: &str,
+    quote: &str,
+    proxy: &Addr,
+) -> Result<AppResponse, String> {
+    router.execute_contract(
+        Addr::unchecked(OWNER),
+        registry.clone(),
+        &ExecuteMsg::ConfirmFeed {
+            base: base.to_owned(),
+            quote: quote.to_owned(),
+            proxy: proxy.to_string(),
+        },
+        &[],
+    )
+}
+
+#[test]
+fn propose_and_confirm_feed() {
+    let (mut router, registry) = default_init();
+    let proxy = instantiate_proxy(&mut router, "LUNA / USD", 100);
+    let proxy2 = instantiate_proxy(&mut router, "LUNA / USD", 200);
+
+    let feed = |router: &App| -> StdResult<Addr> {
+        router.wrap().query_wasm_smart(
+            &registry,
+            &QueryMsg::GetFeed {
+                base: "LUNA".to_owned(),
+                quote: "USD".to_owned(),
+            },
+        )
+    };
+    assert!(feed(&router).is_err());
+
+    // nothing proposed
+    let err = confirm(&mut router, &registry, "LUNA", "USD", &proxy).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        ContractError::NoProposedFeed {}.to_string()
+    );
+
+    // only the owner can propose
+    let err = router
+        .execute_contract(
+            Addr::unchecked("stranger"),
+            registry.clone(),
+            &ExecuteMsg::ProposeFeed {
+                base: "LUNA".to_owned(),
+                quote: "USD".to_owned(),
+                proxy: proxy.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        owned::error::ContractError::MissingRole {
+            role: owned::roles::CONFIG_ADMIN.to_owned()
+        }
+        .to_string()
+    );
+
+    propose(&mut router, &registry, "LUNA", "USD", &proxy);
+    assert!(feed(&router).is_err());
+    let err = confirm(&mut router, &registry, "LUNA", "USD", &proxy2).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        ContractError::InvalidProposedFeed {}.to_string()
+    );
+    confirm(&mut router, &registry, "LUNA", "USD", &proxy).unwrap();
+    assert_eq!(feed(&router).unwrap(), proxy);
+
+    // the confirmed feed is served until the next proposal is confirmed
+    propose(&mut router, &registry, "LUNA", "USD", &proxy2);
+    assert_eq!(feed(&router).unwrap(), proxy);
+    let res: Addr = router
+        .wrap()
+        .query_wasm_smart(
+            &registry,
+            &QueryMsg::GetProposedFeed {
+                base: "LUNA".to_owned(),
+                quote: "USD".to_owned(),
+            },
+        )
+        .unwrap();
+    assert_eq!(res, proxy2);
+    let res = confirm(&mut router, &registry, "LUNA", "USD", &proxy2).unwrap();
+    assert!(res.has_event(
+        &cosmwasm_std::Event::new("wasm-feed_confirmed")
+            .add_attribute("proxy", proxy2.to_string())
+            .add_attribute("previous_proxy", proxy.to_string())
+    ));
+    assert_eq!(feed(&router).unwrap(), proxy2);
+
+    // cancelled proposals can't be confirmed
+    propose(&mut router, &registry, "LUNA", "USD", &proxy);
+    router
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            registry.clone(),
+            &ExecuteMsg::CancelProposedFeed {
+                base: "LUNA".to_owned(),
+                quote: "USD".to_owned(),
+            },
+            &[],
+        )
+        .unwrap();
+    confirm(&mut router, &registry, "LUNA", "USD", &proxy).unwrap_err();
+
+    router
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            registry.clone(),
+            &ExecuteMsg::RemoveFeed {
+                base: "LUNA".to_owned(),
+                quote: "USD".to_owned(),
+            },
+            &[],
+        )
+        .unwrap();
+    assert!(feed(&router).is_err());
+}
+
+#[test]
+fn list_feeds() {
+    let (mut router, registry) = default_init();
+    let pairs = [
+        ("ATOM", "USD"),
+        ("LUNA", "EUR"),
+        ("LUNA", "USD"),
+        ("LUNAX", "USD"),
+    ];
+    let mut expected = vec![];
+    // registered out of order
+    for (base, quote) in pairs.iter().rev() {
+        let proxy = instantiate_proxy(&mut router, "", 1);
+        propose(&mut router, &registry, base, quote, &proxy);
+        confirm(&mut router, &registry, base, quote, &proxy).unwrap();
+        expected.insert(
+            0,
+            FeedResponse {
+                base: base.to_string(),
+                quote: quote.to_string(),
+                proxy,
+            },
+        );
+    }
+
+    let feeds = |start_after: Option<(&str, &str)>, limit| -> Vec<FeedResponse> {
+        let res: FeedsResponse = router
+            .wrap()
+            .query_wasm_smart(
+                &registry,
+                &QueryMsg::GetFeeds {
+                    start_after: start_after
+                        .map(|(base, quote)| (base.to_owned(), quote.to_owned())),
+                    limit,
+                },
+            )
+            .unwrap();
+        res.feeds
+    };
+    assert_eq!(feeds(None, None), expected);
+    assert_eq!(feeds(None, Some(2)), expected[..2]);
+    assert_eq!(feeds(Some(("LUNA", "EUR")), Some(2)), expected[2..]);
+    assert_eq!(feeds(Some(("LUNAX", "USD")), None), vec![]);
+}
+
+#[test]
+fn query_by_pair() {
+    let (mut router, registry) = default_init();
+    let proxy = instantiate_proxy(&mut router, "LUNA / USD", 100);
+    propose(&mut router, &registry, "LUNA", "USD", &proxy);
+    confirm(&mut router, &registry, "LUNA", "USD", &proxy).unwrap();
+
+    let res: RoundDataResponse = router
+        .wrap()
+        .query_wasm_smart(
+            &registry,
+            &QueryMsg::AggregatorQuery {
+                base: "LUNA".to_owned(),
+                quote: "USD".to_owned(),
+                query: AggregatorQueryMsg::GetLatestRoundData {},
+            },
+        )
+        .unwrap();
+    assert_eq!(res.round_id, 1);
+    assert_eq!(res.answer, Some(Uint128::new(100)));
+
+    let querier = router.wrap();
+    assert_eq!(
+        querier.get_feed(registry.clone(), "LUNA", "USD").unwrap(),
+        proxy
+    );
+    assert_eq!(
+        querier
+            .get_latest_answer_by_pair(registry.clone(), "LUNA", "USD")
+            .unwrap(),
+        LatestAnswerResponse(Some(Uint128::new(100)))
+    );
+    assert_eq!(
+        querier
+            .get_round_data_by_pair(registry.clone(), "LUNA", "USD", 1)
+            .unwrap()
+            .round_id,
+        1
+    );
+    assert_eq!(
+        querier
+            .get_description_by_pair(registry.clone(), "LUNA", "USD")
+            .unwrap(),
+        "LUNA / USD"
+    );
+    assert_eq!(
+        querier
+            .get_decimals_by_pair(registry.clone(), "LUNA", "USD")
+            .unwrap(),
+        8
+    );
+    assert!(querier
+        .get_latest_round_data_by_pair(registry, "USD", "LUNA")
+        .is_err());
+}
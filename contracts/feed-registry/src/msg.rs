@@ -0,0 +1,110 @@
+use cosmwasm_std::Addr;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Initiate contract ownership transfer to another address.
+    /// Can be used only by owner
+    TransferOwnership {
+        /// Address to transfer ownership to
+        to: String,
+        /// Block time in seconds after which the transfer can no longer be accepted
+        expires_at: Option<u64>,
+    },
+    /// Finish contract ownership transfer. Can be used only by pending owner
+    AcceptOwnership {},
+    /// Cancel the pending ownership transfer. Can be used only by owner
+    CancelOwnershipTransfer {},
+    /// Leave the contract without owner, disabling functionality restricted to the owner.
+    /// Can be used only by owner
+    RenounceOwnership {},
+    /// Grant a role, see `owned::roles`. Can be used only by owner
+    GrantRole { role: String, account: String },
+    /// Revoke a granted role. Can be used only by owner
+    RevokeRole { role: String, account: String },
+    /// Give up a role granted to the sender
+    RenounceRole { role: String },
+    /// Proposes a proxy for a pair, which takes effect once confirmed
+    /// Can be used only by owner
+    ProposeFeed {
+        /// Identifier of the base asset
+        base: String,
+        /// Identifier of the quote asset
+        quote: String,
+        /// Address of the aggregator proxy
+        proxy: String,
+    },
+    /// Makes the proposed proxy the pair's feed
+    /// Can be used only by owner
+    ConfirmFeed {
+        base: String,
+        quote: String,
+        /// Address of the proposed proxy
+        proxy: String,
+    },
+    /// Withdraws the proposed proxy of a pair
+    /// Can be used only by owner
+    CancelProposedFeed { base: String, quote: String },
+    /// Removes a pair's feed
+    /// Can be used only by owner
+    RemoveFeed { base: String, quote: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Query the proxy of a pair
+    /// Response: [`Addr`]
+    GetFeed { base: String, quote: String },
+    /// Query the proposed proxy of a pair
+    /// Response: [`Addr`]
+    GetProposedFeed { base: String, quote: String },
+    /// Query registered pairs in ascending order
+    /// Response: [`FeedsResponse`]
+    GetFeeds {
+        /// Pair to start after
+        start_after: Option<(String, String)>,
+        /// Maximum number of pairs returned
+        limit: Option<u32>,
+    },
+    /// Forwards a query to the proxy of a pair
+    /// Response: the proxy's response
+    AggregatorQuery {
+        base: String,
+        quote: String,
+        query: chainlink_aggregator::QueryMsg,
+    },
+    /// Query the contract's owner
+    /// Response: [`Addr`]
+    GetOwner {},
+    /// Returns the owner, the pending owner and the expiry of the pending transfer
+    /// Response: [`owned::msg::OwnershipStateResponse`]
+    GetOwnershipState {},
+    /// Returns whether the account holds the role, which the owner always does
+    /// Response: `bool`
+    HasRole { role: String, account: String },
+    /// Returns the accounts granted the role, not including the owner
+    /// Response: [`owned::msg::RoleMembersResponse`]
+    GetRoleMembers {
+        role: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeedResponse {
+    pub base: String,
+    pub quote: String,
+    pub proxy: Addr,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeedsResponse {
+    pub feeds: Vec<FeedResponse>,
+}
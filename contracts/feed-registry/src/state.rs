@@ -0,0 +1,7 @@
+use cosmwasm_std::Addr;
+use cw_storage_plus::Map;
+
+/// Confirmed proxy of each `(base, quote)` pair
+pub const FEEDS: Map<(&str, &str), Addr> = Map::new("feeds");
+/// Proxy proposed to replace the confirmed one of a pair
+pub const PROPOSED_FEEDS: Map<(&str, &str), Addr> = Map::new("proposed_feeds");
@@ -1,25 +1,38 @@
 use cosmwasm_std::{
-    attr, entry_point, to_binary, Addr, Deps, DepsMut, Env, MessageInfo, QueryResponse, Response,
+    attr, entry_point, to_binary, Addr, Deps, DepsMut, Env, Event, MessageInfo, QueryResponse,
+    Response, StdResult, Storage,
 };
 use owned::contract::execute_accept_ownership;
+use owned::contract::execute_cancel_ownership_transfer;
+use owned::contract::execute_renounce_ownership;
 use owned::contract::execute_transfer_ownership;
 
 use crate::error::ContractError;
 use crate::msg::*;
 use crate::state::*;
-use owned::contract::{get_owner, instantiate as owned_init};
+use owned::contract::{get_owner, get_ownership_state, instantiate as owned_init};
+use owned::roles::{
+    execute_grant_role, execute_renounce_role, execute_revoke_role, has_role, query_role_members,
+    validate_role, CONFIG_ADMIN, FLAG_LOWERER,
+};
 
 #[entry_point]
 pub fn instantiate(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    _msg: InstantiateMsg,
+    msg: InstantiateMsg,
 ) -> Result<Response, owned::error::ContractError> {
-    // config(deps.storage).save(&State {
-    //     raising_access_controller: msg.rac_address,
-    // })?;
-    owned_init(deps, env, info, owned::msg::InstantiateMsg {})?;
+    let raising_access_controller = deps.api.addr_validate(&msg.rac_address)?;
+    let lowering_access_controller = msg
+        .lac_address
+        .map(|lac_address| deps.api.addr_validate(&lac_address))
+        .transpose()?;
+    config(deps.storage).save(&State {
+        raising_access_controller,
+        lowering_access_controller,
+    })?;
+    owned_init(deps.branch(), env, info, owned::msg::InstantiateMsg {})?;
 
     Ok(Response::default())
 }
@@ -32,47 +45,93 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::RaiseFlag { subject } => execute_raise_flag(deps, env, info, subject),
-        ExecuteMsg::RaiseFlags { subjects } => execute_raise_flags(deps, env, info, subjects),
+        ExecuteMsg::RaiseFlag { subject, ttl } => execute_raise_flag(deps, env, info, subject, ttl),
+        ExecuteMsg::RaiseFlags { subjects, ttl } => {
+            execute_raise_flags(deps, env, info, subjects, ttl)
+        }
         ExecuteMsg::LowerFlags { subjects } => execute_lower_flags(deps, env, info, subjects),
+        ExecuteMsg::ExpireFlags { subjects } => execute_expire_flags(deps, env, info, subjects),
         ExecuteMsg::SetRaisingAccessController { rac_address } => {
             execute_set_raising_access_controller(deps, env, info, rac_address)
         }
-        ExecuteMsg::TransferOwnership { to } => {
-            execute_transfer_ownership(deps, env, info, to).map_err(ContractError::from)
+        ExecuteMsg::SetLoweringAccessController { lac_address } => {
+            execute_set_lowering_access_controller(deps, env, info, lac_address)
+        }
+        ExecuteMsg::TransferOwnership { to, expires_at } => {
+            execute_transfer_ownership(deps, env, info, to, expires_at).map_err(ContractError::from)
         }
         ExecuteMsg::AcceptOwnership {} => {
             execute_accept_ownership(deps, env, info).map_err(ContractError::from)
         }
+        ExecuteMsg::CancelOwnershipTransfer {} => {
+            execute_cancel_ownership_transfer(deps, env, info).map_err(ContractError::from)
+        }
+        ExecuteMsg::RenounceOwnership {} => {
+            execute_renounce_ownership(deps, env, info).map_err(ContractError::from)
+        }
+        ExecuteMsg::GrantRole { role, account } => {
+            execute_grant_role(deps, env, info, role, account).map_err(ContractError::from)
+        }
+        ExecuteMsg::RevokeRole { role, account } => {
+            execute_revoke_role(deps, env, info, role, account).map_err(ContractError::from)
+        }
+        ExecuteMsg::RenounceRole { role } => {
+            execute_renounce_role(deps, env, info, role).map_err(ContractError::from)
+        }
     }
 }
 
 #[entry_point]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<QueryResponse, ContractError> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<QueryResponse, ContractError> {
     match msg {
-        QueryMsg::GetFlag { subject } => Ok(to_binary(&get_flag(deps, subject)?)?),
-        QueryMsg::GetFlags { subjects } => Ok(to_binary(&get_flags(deps, subjects)?)?),
+        QueryMsg::GetFlag { subject } => Ok(to_binary(&get_flag(deps, env, subject)?)?),
+        QueryMsg::GetFlags { subjects } => Ok(to_binary(&get_flags(deps, env, subjects)?)?),
         QueryMsg::GetRac {} => Ok(to_binary(&get_rac(deps)?)?),
+        QueryMsg::GetLac {} => Ok(to_binary(&get_lac(deps)?)?),
         QueryMsg::GetOwner {} => Ok(to_binary(&get_owner(deps)?)?),
+        QueryMsg::GetOwnershipState {} => Ok(to_binary(&get_ownership_state(deps)?)?),
+        QueryMsg::HasRole { role, account } => Ok(to_binary(&has_role(
+            deps,
+            &role,
+            &deps.api.addr_validate(&account)?,
+        )?)?),
+        QueryMsg::GetRoleMembers {
+            role,
+            start_after,
+            limit,
+        } => Ok(to_binary(&query_role_members(
+            deps,
+            role,
+            start_after,
+            limit,
+        )?)?),
     }
 }
 
 pub fn execute_raise_flag(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     _info: MessageInfo,
     subject: String,
+    ttl: Option<u64>,
 ) -> Result<Response, ContractError> {
     check_access(deps.as_ref())?;
     let subject = deps.api.addr_validate(&subject)?;
-    if FLAGS.may_load(deps.as_ref().storage, &subject)? == Some(true) {
-        Ok(Response::new().add_attributes(vec![
+    let now = env.block.time.seconds();
+
+    let mut response = Response::new();
+    if let Some(event) = expire_flag(deps.storage, &subject, now)? {
+        response = response.add_event(event);
+    }
+    let already_raised = FLAGS.may_load(deps.storage, &subject)? == Some(true);
+    raise_flag(deps.storage, &subject, ttl, now)?;
+    if already_raised {
+        Ok(response.add_attributes(vec![
             attr("action", "already raised flag"),
             attr("subject", subject),
         ]))
     } else {
-        FLAGS.save(deps.storage, &subject, &true)?;
-        Ok(Response::new().add_attributes(vec![
+        Ok(response.add_attributes(vec![
             attr("action", "raised flag"),
             attr("subject", subject),
         ]))
@@ -81,9 +140,10 @@ pub fn execute_raise_flag(
 
 pub fn execute_raise_flags(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     _info: MessageInfo,
     subjects: Vec<String>,
+    ttl: Option<u64>,
 ) -> Result<Response, ContractError> {
     check_access(deps.as_ref())?;
 
@@ -91,21 +151,29 @@ pub fn execute_raise_flags(
         .iter()
         .map(|subject| deps.api.addr_validate(subject))
         .collect::<Result<Vec<Addr>, _>>()?;
+    let now = env.block.time.seconds();
 
+    let mut events = vec![];
     let mut attributes = vec![];
     for subject in subjects {
-        if FLAGS.may_load(deps.as_ref().storage, &subject)? == Some(true) {
+        if let Some(event) = expire_flag(deps.storage, &subject, now)? {
+            events.push(event);
+        }
+        let already_raised = FLAGS.may_load(deps.storage, &subject)? == Some(true);
+        raise_flag(deps.storage, &subject, ttl, now)?;
+        if already_raised {
             attributes.extend_from_slice(&[
                 attr("action", "already raised flag"),
                 attr("subject", subject),
             ]);
         } else {
-            FLAGS.save(deps.storage, &subject, &true)?;
             attributes
                 .extend_from_slice(&[attr("action", "flag raised"), attr("subject", subject)]);
         }
     }
-    Ok(Response::new().add_attributes(attributes))
+    Ok(Response::new()
+        .add_attributes(attributes)
+        .add_events(events))
 }
 
 pub fn execute_lower_flags(
@@ -114,36 +182,64 @@ pub fn execute_lower_flags(
     info: MessageInfo,
     subjects: Vec<String>,
 ) -> Result<Response, ContractError> {
-    validate_ownership(deps.as_ref(), &env, info)?;
+    validate_lowering_access(deps.as_ref(), &env, &info)?;
 
     let subjects = subjects
         .iter()
         .map(|subject| deps.api.addr_validate(subject))
         .collect::<Result<Vec<Addr>, _>>()?;
+    let now = env.block.time.seconds();
 
-    let mut attributes = vec![];
+    let mut events = vec![];
     for subject in subjects {
-        if FLAGS.may_load(deps.storage, &subject)? == Some(true) {
-            FLAGS.save(deps.storage, &subject, &false)?;
-            attributes
-                .extend_from_slice(&[attr("action", "flag lowered"), attr("address", subject)]);
+        if let Some(event) = expire_flag(deps.storage, &subject, now)? {
+            events.push(event);
+        } else if FLAGS.may_load(deps.storage, &subject)? == Some(true) {
+            lower_flag(deps.storage, &subject)?;
+            events.push(
+                Event::new("flag_lowered")
+                    .add_attribute("subject", subject)
+                    .add_attribute("lowered_by", info.sender.clone()),
+            );
         }
     }
-    Ok(Response::new().add_attributes(attributes))
+    Ok(Response::new().add_events(events))
 }
 
-pub fn execute_set_raising_access_controller(
+pub fn execute_expire_flags(
     deps: DepsMut,
     env: Env,
+    _info: MessageInfo,
+    subjects: Vec<String>,
+) -> Result<Response, ContractError> {
+    let subjects = subjects
+        .iter()
+        .map(|subject| deps.api.addr_validate(subject))
+        .collect::<Result<Vec<Addr>, _>>()?;
+    let now = env.block.time.seconds();
+
+    let mut events = vec![];
+    for subject in subjects {
+        if let Some(event) = expire_flag(deps.storage, &subject, now)? {
+            events.push(event);
+        }
+    }
+    Ok(Response::new().add_events(events))
+}
+
+pub fn execute_set_raising_access_controller(
+    deps: DepsMut,
+    _env: Env,
     info: MessageInfo,
     rac_address: String,
 ) -> Result<Response, ContractError> {
-    validate_ownership(deps.as_ref(), &env, info)?;
+    validate_role(deps.as_ref(), &info, CONFIG_ADMIN)?;
 
     let new_rac = deps.api.addr_validate(&rac_address)?;
     let prev_rac = config_read(deps.storage).load()?.raising_access_controller;
-    config(deps.storage).save(&State {
-        raising_access_controller: new_rac,
+    config(deps.storage).update(|mut state| -> StdResult<_> {
+        state.raising_access_controller = new_rac;
+        Ok(state)
     })?;
     Ok(Response::new().add_attributes(vec![
         attr("action", "raising access controller updated"),
@@ -152,25 +248,52 @@ pub fn execute_set_raising_access_controller(
     ]))
 }
 
-pub fn get_flag(deps: Deps, subject: String) -> Result<bool, ContractError> {
+pub fn execute_set_lowering_access_controller(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    lac_address: Option<String>,
+) -> Result<Response, ContractError> {
+    validate_role(deps.as_ref(), &info, CONFIG_ADMIN)?;
+
+    let new_lac = lac_address
+        .as_ref()
+        .map(|lac_address| deps.api.addr_validate(lac_address))
+        .transpose()?;
+    let prev_lac = config_read(deps.storage).load()?.lowering_access_controller;
+    config(deps.storage).update(|mut state| -> StdResult<_> {
+        state.lowering_access_controller = new_lac;
+        Ok(state)
+    })?;
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "lowering access controller updated"),
+        attr("address", lac_address.unwrap_or_default()),
+        attr("previous", prev_lac.map(String::from).unwrap_or_default()),
+    ]))
+}
+
+pub fn get_flag(deps: Deps, env: Env, subject: String) -> Result<bool, ContractError> {
     check_access(deps)?;
     let subject = deps.api.addr_validate(&subject)?;
-    Ok(FLAGS.load(deps.storage, &subject)?)
+    let raised = FLAGS.load(deps.storage, &subject)?;
+    Ok(raised && !is_expired(deps.storage, &subject, env.block.time.seconds())?)
 }
 
-pub fn get_flags(deps: Deps, subjects: Vec<String>) -> Result<Vec<bool>, ContractError> {
+pub fn get_flags(deps: Deps, env: Env, subjects: Vec<String>) -> Result<Vec<bool>, ContractError> {
     check_access(deps)?;
 
     let subjects = subjects
         .iter()
         .map(|subject| deps.api.addr_validate(subject))
         .collect::<Result<Vec<Addr>, _>>()?;
+    let now = env.block.time.seconds();
 
     let flags = subjects
         .iter()
         .filter_map(|subject| {
             let flag = FLAGS.load(deps.storage, subject).ok()?;
-            Some(flag)
+            let expired = is_expired(deps.storage, subject, now).ok()?;
+            Some(flag && !expired)
         })
         .collect();
     Ok(flags)
@@ -181,10 +304,73 @@ pub fn get_rac(deps: Deps) -> Result<Addr, ContractError> {
     Ok(raising_access_controller)
 }
 
-fn validate_ownership(deps: Deps, _env: &Env, info: MessageInfo) -> Result<(), ContractError> {
-    let owner = get_owner(deps)?;
-    if info.sender != owner {
-        return Err(ContractError::NotOwner {});
+pub fn get_lac(deps: Deps) -> Result<Option<Addr>, ContractError> {
+    let lowering_access_controller = config_read(deps.storage).load()?.lowering_access_controller;
+    Ok(lowering_access_controller)
+}
+
+/// Raises the flag, never shortening the expiry of a flag that is already raised
+fn raise_flag(
+    storage: &mut dyn Storage,
+    subject: &Addr,
+    ttl: Option<u64>,
+    now: u64,
+) -> StdResult<()> {
+    let mut expiration = ttl.map(|ttl| now.saturating_add(ttl));
+    if FLAGS.may_load(storage, subject)? == Some(true) {
+        // raised flags without expiry stay permanent
+        expiration = match (FLAG_EXPIRATIONS.may_load(storage, subject)?, expiration) {
+            (Some(current), Some(new)) => Some(current.max(new)),
+            _ => None,
+        };
+    }
+    FLAGS.save(storage, subject, &true)?;
+    match expiration {
+        Some(expiration) => FLAG_EXPIRATIONS.save(storage, subject, &expiration),
+        None => {
+            FLAG_EXPIRATIONS.remove(storage, subject);
+            Ok(())
+        }
+    }
+}
+
+fn lower_flag(storage: &mut dyn Storage, subject: &Addr) -> StdResult<()> {
+    FLAGS.save(storage, subject, &false)?;
+    FLAG_EXPIRATIONS.remove(storage, subject);
+    Ok(())
+}
+
+fn is_expired(storage: &dyn Storage, subject: &Addr, now: u64) -> StdResult<bool> {
+    Ok(FLAG_EXPIRATIONS
+        .may_load(storage, subject)?
+        .map_or(false, |expiration| expiration <= now))
+}
+
+/// Lowers a raised flag whose TTL has run out, returning the expiry event
+fn expire_flag(storage: &mut dyn Storage, subject: &Addr, now: u64) -> StdResult<Option<Event>> {
+    if FLAGS.may_load(storage, subject)? != Some(true) || !is_expired(storage, subject, now)? {
+        return Ok(None);
+    }
+    let expiration = FLAG_EXPIRATIONS.load(storage, subject)?;
+    lower_flag(storage, subject)?;
+
+    Ok(Some(
+        Event::new("flag_expired")
+            .add_attribute("subject", subject)
+            .add_attribute("expired_at", expiration.to_string()),
+    ))
+}
+
+fn validate_lowering_access(
+    deps: Deps,
+    _env: &Env,
+    info: &MessageInfo,
+) -> Result<(), ContractError> {
+    let lowering_access_controller = config_read(deps.storage).load()?.lowering_access_controller;
+    if Some(&info.sender) != lowering_access_controller.as_ref()
+        && !has_role(deps, FLAG_LOWERER, &info.sender)?
+    {
+        return Err(ContractError::NoLoweringAccess {});
     }
     Ok(())
 }
@@ -207,7 +393,10 @@ mod tests {
     fn proper_initialization() {
         let mut deps = mock_dependencies(&[]);
 
-        let msg = InstantiateMsg {};
+        let msg = InstantiateMsg {
+            rac_address: "rac".to_string(),
+            lac_address: None,
+        };
         let info = mock_info("creator", &coins(1000, "earth"));
 
         // we can just call .unwrap() to assert this was a success
@@ -219,7 +408,10 @@ mod tests {
     fn raise_flag() {
         let mut deps = mock_dependencies(&[]);
 
-        let msg = InstantiateMsg {};
+        let msg = InstantiateMsg {
+            rac_address: "rac".to_string(),
+            lac_address: None,
+        };
         let info = mock_info("creator", &coins(1000, "earth"));
 
         // we can just call .unwrap() to assert this was a success
@@ -231,11 +423,12 @@ mod tests {
 
         let msg = ExecuteMsg::RaiseFlag {
             subject: sender.clone(),
+            ttl: None,
         };
 
         let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone());
 
-        let flag = get_flag(deps.as_ref(), sender.clone()).unwrap();
+        let flag = get_flag(deps.as_ref(), mock_env(), sender.clone()).unwrap();
         assert_eq!(true, flag);
 
         // trying to raise the flag when it's already raised
@@ -253,7 +446,10 @@ mod tests {
     fn raise_flags() {
         let mut deps = mock_dependencies(&[]);
 
-        let msg = InstantiateMsg {};
+        let msg = InstantiateMsg {
+            rac_address: "rac".to_string(),
+            lac_address: None,
+        };
         let info = mock_info("creator", &coins(1000, "earth"));
 
         // we can just call .unwrap() to assert this was a success
@@ -268,13 +464,15 @@ mod tests {
             mock_env(),
             info.clone(),
             vec![sender.clone()],
+            None,
         );
 
-        let flags = get_flags(deps.as_ref(), vec![sender.clone()]);
+        let flags = get_flags(deps.as_ref(), mock_env(), vec![sender.clone()]);
         assert_eq!(vec![true], flags.unwrap());
 
         let msg = ExecuteMsg::RaiseFlags {
             subjects: vec![sender.clone()],
+            ttl: None,
         };
         let res = execute(deps.as_mut(), mock_env(), info, msg.clone());
         assert_eq!(
@@ -285,4 +483,278 @@ mod tests {
             res.unwrap().attributes
         );
     }
+
+    #[test]
+    fn lower_flags() {
+        let mut deps = mock_dependencies(&[]);
+
+        let msg = InstantiateMsg {
+            rac_address: "rac".to_string(),
+            lac_address: Some("lac".to_string()),
+        };
+        let info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let subjects = vec!["human".to_string()];
+        let msg = ExecuteMsg::RaiseFlags {
+            subjects: subjects.clone(),
+            ttl: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("human", &[]), msg).unwrap();
+
+        // only owner or lowering access controller can lower flags
+        let msg = ExecuteMsg::LowerFlags {
+            subjects: subjects.clone(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("human", &[]),
+            msg.clone(),
+        );
+        assert!(matches!(err, Err(ContractError::NoLoweringAccess {})));
+
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("lac", &[]),
+            msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            vec![Event::new("flag_lowered")
+                .add_attribute("subject", "human")
+                .add_attribute("lowered_by", "lac")],
+            res.events
+        );
+        assert_eq!(
+            false,
+            get_flag(deps.as_ref(), mock_env(), "human".to_string()).unwrap()
+        );
+
+        // owner keeps the ability to lower flags
+        let msg = ExecuteMsg::RaiseFlags {
+            subjects: subjects.clone(),
+            ttl: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("human", &[]), msg).unwrap();
+        let msg = ExecuteMsg::LowerFlags { subjects };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(
+            false,
+            get_flag(deps.as_ref(), mock_env(), "human".to_string()).unwrap()
+        );
+    }
+
+    #[test]
+    fn set_lowering_access_controller() {
+        let mut deps = mock_dependencies(&[]);
+
+        let msg = InstantiateMsg {
+            rac_address: "rac".to_string(),
+            lac_address: None,
+        };
+        let info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        assert_eq!(None, get_lac(deps.as_ref()).unwrap());
+
+        let msg = ExecuteMsg::SetLoweringAccessController {
+            lac_address: Some("lac".to_string()),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("lac", &[]),
+            msg.clone(),
+        );
+        assert!(matches!(
+            err,
+            Err(ContractError::Owned(
+                owned::error::ContractError::MissingRole { .. }
+            ))
+        ));
+
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(
+            Some(Addr::unchecked("lac")),
+            get_lac(deps.as_ref()).unwrap()
+        );
+        // raising access controller is left untouched
+        assert_eq!(Addr::unchecked("rac"), get_rac(deps.as_ref()).unwrap());
+    }
+
+    #[test]
+    fn flag_lowerer_role() {
+        let mut deps = mock_dependencies(&[]);
+
+        let msg = InstantiateMsg {
+            rac_address: "rac".to_string(),
+            lac_address: None,
+        };
+        let info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let msg = ExecuteMsg::RaiseFlag {
+            subject: "human".to_string(),
+            ttl: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("rac", &[]), msg).unwrap();
+
+        let lower = ExecuteMsg::LowerFlags {
+            subjects: vec!["human".to_string()],
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("lowerer", &[]),
+            lower.clone(),
+        );
+        assert!(matches!(err, Err(ContractError::NoLoweringAccess {})));
+
+        let msg = ExecuteMsg::GrantRole {
+            role: FLAG_LOWERER.to_string(),
+            account: "lowerer".to_string(),
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        execute(deps.as_mut(), mock_env(), mock_info("lowerer", &[]), lower).unwrap();
+        assert!(!get_flag(deps.as_ref(), mock_env(), "human".to_string()).unwrap());
+    }
+
+    #[test]
+    fn flag_expires() {
+        let mut deps = mock_dependencies(&[]);
+
+        let msg = InstantiateMsg {
+            rac_address: "rac".to_string(),
+            lac_address: None,
+        };
+        let info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let msg = ExecuteMsg::RaiseFlag {
+            subject: "human".to_string(),
+            ttl: Some(100),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("human", &[]), msg).unwrap();
+        assert_eq!(
+            true,
+            get_flag(deps.as_ref(), mock_env(), "human".to_string()).unwrap()
+        );
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(100);
+        let expired_at = env.block.time.seconds();
+        // expired flags read back as lowered
+        assert_eq!(
+            false,
+            get_flag(deps.as_ref(), env.clone(), "human".to_string()).unwrap()
+        );
+        assert_eq!(
+            vec![false],
+            get_flags(deps.as_ref(), env.clone(), vec!["human".to_string()]).unwrap()
+        );
+
+        let msg = ExecuteMsg::ExpireFlags {
+            subjects: vec!["human".to_string()],
+        };
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            vec![Event::new("flag_expired")
+                .add_attribute("subject", "human")
+                .add_attribute("expired_at", expired_at.to_string())],
+            res.events
+        );
+        assert_eq!(
+            false,
+            FLAGS
+                .load(&deps.storage, &Addr::unchecked("human"))
+                .unwrap()
+        );
+
+        // nothing left to expire
+        let res = execute(deps.as_mut(), env, mock_info("anyone", &[]), msg).unwrap();
+        assert!(res.events.is_empty());
+    }
+
+    #[test]
+    fn reraise_keeps_later_expiry() {
+        let mut deps = mock_dependencies(&[]);
+
+        let msg = InstantiateMsg {
+            rac_address: "rac".to_string(),
+            lac_address: None,
+        };
+        let info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let raise = |subject: &str, ttl| ExecuteMsg::RaiseFlag {
+            subject: subject.to_string(),
+            ttl,
+        };
+        let expiration = |deps: &cosmwasm_std::OwnedDeps<_, _, _>, subject| {
+            FLAG_EXPIRATIONS
+                .may_load(&deps.storage, &Addr::unchecked(subject))
+                .unwrap()
+        };
+        let now = mock_env().block.time.seconds();
+        let info = mock_info("anyone", &[]);
+
+        // a permanent flag can't be made to expire
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            raise("human", None),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            raise("human", Some(0)),
+        )
+        .unwrap();
+        assert_eq!(expiration(&deps, "human"), None);
+        assert!(get_flag(deps.as_ref(), mock_env(), "human".to_string()).unwrap());
+
+        // nor can a flag's expiry be brought forward
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            raise("robot", Some(100)),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            raise("robot", Some(10)),
+        )
+        .unwrap();
+        assert_eq!(expiration(&deps, "robot"), Some(now + 100));
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            raise("robot", Some(200)),
+        )
+        .unwrap();
+        assert_eq!(expiration(&deps, "robot"), Some(now + 200));
+
+        // huge TTLs saturate instead of overflowing
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            raise("alien", Some(u64::MAX)),
+        )
+        .unwrap();
+        assert_eq!(expiration(&deps, "alien"), Some(u64::MAX));
+    }
 }
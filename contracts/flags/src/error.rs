@@ -9,10 +9,10 @@ pub enum ContractError {
     #[error("{0}")]
     Owned(#[from] owned::error::ContractError),
 
-    /// Only callable by owner
-    #[error("Only callable by owner")]
-    NotOwner {},
-
     #[error("No Access")]
     NoAccess {},
+
+    /// Only callable by owner or lowering access controller
+    #[error("Only callable by owner or lowering access controller")]
+    NoLoweringAccess {},
 }
@@ -3,7 +3,10 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
-    //pub rac_address: Addr,
+    /// Address of the raising access controller
+    pub rac_address: String,
+    /// Address allowed to lower flags alongside the owner
+    pub lac_address: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -14,21 +17,62 @@ pub enum ExecuteMsg {
     TransferOwnership {
         /// Address to transfer ownership to
         to: String,
+        /// Block time in seconds after which the transfer can no longer be accepted
+        expires_at: Option<u64>,
     },
     /// Finish contract ownership transfer. Can be used only by pending owner
     AcceptOwnership {},
+    /// Cancel the pending ownership transfer. Can be used only by owner
+    CancelOwnershipTransfer {},
+    /// Leave the contract without owner, disabling functionality restricted to the owner.
+    /// Can be used only by owner
+    RenounceOwnership {},
+    /// Grant a role, see `owned::roles`. Can be used only by owner
+    GrantRole {
+        role: String,
+        account: String,
+    },
+    /// Revoke a granted role. Can be used only by owner
+    RevokeRole {
+        role: String,
+        account: String,
+    },
+    /// Give up a role granted to the sender
+    RenounceRole {
+        role: String,
+    },
     RaiseFlag {
         subject: String,
+        /// Number of seconds after which the flag lowers itself.
+        /// The flag stays raised until lowered manually if omitted
+        /// Re-raising never shortens the expiry of a raised flag
+        ttl: Option<u64>,
     },
     RaiseFlags {
         subjects: Vec<String>,
+        /// Number of seconds after which the flags lower themselves.
+        /// The flags stay raised until lowered manually if omitted
+        /// Re-raising never shortens the expiry of a raised flag
+        ttl: Option<u64>,
     },
+    /// Lowers the flags of the given subjects.
+    /// Can be used only by owner or lowering access controller
     LowerFlags {
         subjects: Vec<String>,
     },
+    /// Clears the flags of the given subjects whose TTL has run out.
+    /// Can be used by anyone
+    ExpireFlags {
+        subjects: Vec<String>,
+    },
     SetRaisingAccessController {
         rac_address: String,
     },
+    /// Updates the address allowed to lower flags alongside the owner.
+    /// Can be used only by owner
+    SetLoweringAccessController {
+        lac_address: Option<String>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -37,11 +81,34 @@ pub enum QueryMsg {
     /// Returns contract owner's address
     /// Response [`Addr`]
     GetOwner {},
+    /// Returns the owner, the pending owner and the expiry of the pending transfer
+    /// Response [`owned::msg::OwnershipStateResponse`]
+    GetOwnershipState {},
+    /// Returns whether the account holds the role, which the owner always does
+    /// Response `bool`
+    HasRole {
+        role: String,
+        account: String,
+    },
+    /// Returns the accounts granted the role, not including the owner
+    /// Response [`owned::msg::RoleMembersResponse`]
+    GetRoleMembers {
+        role: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Expired flags read back as lowered
+    /// Response [`bool`]
     GetFlag {
         subject: String,
     },
+    /// Expired flags read back as lowered
+    /// Response [`Vec<bool>`]
     GetFlags {
         subjects: Vec<String>,
     },
     GetRac {},
+    /// Returns the lowering access controller's address
+    /// Response [`Option<Addr>`]
+    GetLac {},
 }
@@ -11,6 +11,8 @@ pub static FLAG_KEY: &[u8] = b"flags";
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct State {
     pub raising_access_controller: Addr,
+    /// Address allowed to lower flags alongside the owner
+    pub lowering_access_controller: Option<Addr>,
 }
 
 pub fn config(storage: &mut dyn Storage) -> Singleton<State> {
@@ -22,3 +24,5 @@ pub fn config_read(storage: &dyn Storage) -> ReadonlySingleton<State> {
 }
 
 pub const FLAGS: Map<&Addr, bool> = Map::new("flags");
+/// Block time (in seconds) at which a raised flag lowers itself
+pub const FLAG_EXPIRATIONS: Map<&Addr, u64> = Map::new("flag_expirations");
@@ -1,15 +1,24 @@
+use std::convert::TryInto;
+
 use chainlink_aggregator::{LatestAnswerResponse, QueryMsg::*, RoundDataResponse};
 use cosmwasm_std::{
-    attr, to_binary, Addr, Binary, Deps, DepsMut, Env, Event, MessageInfo, OverflowError,
-    OverflowOperation, Response, StdError, StdResult, Storage, Timestamp, Uint128, WasmMsg,
+    attr, to_binary, Addr, Binary, ContractResult, Deps, DepsMut, Env, Event, MessageInfo,
+    OverflowError, OverflowOperation, Reply, Response, StdError, StdResult, Storage, SubMsg,
+    Timestamp, Uint128, WasmMsg,
 };
 use cw20::{BalanceResponse, Cw20ReceiveMsg};
 use cw20_base::msg::{ExecuteMsg as LinkMsg, QueryMsg as LinkQuery};
 use deviation_flagging_validator::msg::ExecuteMsg as ValidatorMsg;
-use median::calculate_median;
+use median::{calculate_median, stats::OutlierFilter, weighted_median};
 use owned::contract::{
-    execute_accept_ownership, execute_transfer_ownership, get_owner, instantiate as owned_init,
+    execute_accept_ownership, execute_cancel_ownership_transfer, execute_renounce_ownership,
+    execute_transfer_ownership, get_owner, get_ownership_state, instantiate as owned_init,
+};
+use owned::roles::{
+    execute_grant_role, execute_renounce_role, execute_revoke_role, has_role, query_role_members,
+    validate_role, CONFIG_ADMIN, ORACLE_MANAGER,
 };
+use staking::msg::{ExecuteMsg as StakingMsg, QueryMsg as StakingQuery, StakeResponse};
 
 use crate::{error::*, msg::*, state::*};
 
@@ -19,6 +28,8 @@ static ROUND_MAX: u32 = u32::MAX;
 
 static VERSION: Uint128 = Uint128::new(3);
 
+const SLASH_REPLY_ID: u64 = 1;
+
 pub fn instantiate(
     mut deps: DepsMut,
     env: Env,
@@ -56,6 +67,9 @@ pub fn instantiate(
             description: msg.description.clone(),
             min_submission_value: msg.min_submission_value,
             max_submission_value: msg.max_submission_value,
+            outlier_filter: None,
+            min_total_weight: 0,
+            staking: None,
         },
     )?;
 
@@ -140,12 +154,47 @@ pub fn execute(
         ExecuteMsg::UpdateAvailableFunds {} => execute_update_available_funds(deps, env, info),
         ExecuteMsg::SetValidator { validator } => execute_set_validator(deps, env, info, validator),
         ExecuteMsg::Receive(receive_msg) => execute_receive(deps, env, info, receive_msg),
-        ExecuteMsg::TransferOwnership { to } => {
-            execute_transfer_ownership(deps, env, info, to).map_err(ContractError::from)
+        ExecuteMsg::SetOutlierFilter { filter } => {
+            execute_set_outlier_filter(deps, env, info, filter)
+        }
+        ExecuteMsg::SetOracleWeights {
+            oracles,
+            weights,
+            min_total_weight,
+        } => execute_set_oracle_weights(deps, env, info, oracles, weights, min_total_weight),
+        ExecuteMsg::SetStaking { config } => execute_set_staking(deps, env, info, config),
+        ExecuteMsg::SlashInactive { oracle } => execute_slash_inactive(deps, env, info, oracle),
+        ExecuteMsg::TransferOwnership { to, expires_at } => {
+            execute_transfer_ownership(deps, env, info, to, expires_at).map_err(ContractError::from)
         }
         ExecuteMsg::AcceptOwnership {} => {
             execute_accept_ownership(deps, env, info).map_err(ContractError::from)
         }
+        ExecuteMsg::CancelOwnershipTransfer {} => {
+            execute_cancel_ownership_transfer(deps, env, info).map_err(ContractError::from)
+        }
+        ExecuteMsg::RenounceOwnership {} => {
+            execute_renounce_ownership(deps, env, info).map_err(ContractError::from)
+        }
+        ExecuteMsg::GrantRole { role, account } => {
+            execute_grant_role(deps, env, info, role, account).map_err(ContractError::from)
+        }
+        ExecuteMsg::RevokeRole { role, account } => {
+            execute_revoke_role(deps, env, info, role, account).map_err(ContractError::from)
+        }
+        ExecuteMsg::RenounceRole { role } => {
+            execute_renounce_role(deps, env, info, role).map_err(ContractError::from)
+        }
+    }
+}
+
+/// Reports failed slashes, which don't revert the submission or call causing them
+pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.result {
+        ContractResult::Err(error) if msg.id == SLASH_REPLY_ID => {
+            Ok(Response::new().add_event(Event::new("slash_failed").add_attribute("error", error)))
+        }
+        _ => Ok(Response::default()),
     }
 }
 
@@ -165,6 +214,8 @@ pub fn execute_submit(
         timeout,
         payment_amount,
         validator,
+        outlier_filter,
+        min_total_weight,
         ..
     } = CONFIG.load(deps.storage)?;
     if submission < min_submission_value {
@@ -185,6 +236,7 @@ pub fn execute_submit(
         .may_load(deps.storage, round_id.into())?
         .unwrap_or_default();
     let mut round_details: RoundDetails;
+    let mut started = false;
 
     // if new round and delay requirement is met
     if round_id == rr_id + 1
@@ -213,10 +265,13 @@ pub fn execute_submit(
         REPORTING_ROUND_ID.save(deps.storage, &round_id)?;
         round_details = RoundDetails {
             submissions: vec![],
+            oracles: vec![],
+            weights: vec![],
             max_submissions: max_submission_count,
             min_submissions: min_submission_count,
             timeout,
             payment_amount,
+            excluded: vec![],
         };
         round.started_at = Some(timestamp);
         ROUNDS.save(deps.storage, round_id.into(), &round)?;
@@ -228,6 +283,7 @@ pub fn execute_submit(
         );
 
         oracle.last_started_round = Some(round_id);
+        started = true;
     } else {
         round_details = DETAILS.load(deps.storage, round_id.into())?;
     }
@@ -236,6 +292,8 @@ pub fn execute_submit(
         return Err(ContractError::NotAcceptingSubmissions {});
     }
     round_details.submissions.push(submission);
+    round_details.oracles.push(info.sender.clone());
+    round_details.weights.push(oracle.weight);
     oracle.last_reported_round = Some(round_id);
     oracle.latest_submission = Some(submission);
 
@@ -247,19 +305,26 @@ pub fn execute_submit(
     );
 
     // update round answer
-    if (round_details.submissions.len() as u32) >= round_details.min_submissions {
-        let mut submissions = round_details
-            .submissions
+    let answer = if (round_details.submissions.len() as u32) >= round_details.min_submissions {
+        aggregate(&round_details, outlier_filter, min_total_weight)?
+    } else {
+        None
+    };
+    let answered = answer.is_some();
+    if let Some((new_answer, outliers)) = answer {
+        // exclusions are only recorded once the round closes, later submissions move the median
+        let excluded = outliers
             .iter()
-            .map(|submission| submission.u128())
-            .collect::<Vec<u128>>();
-        let new_answer =
-            calculate_median(&mut submissions).map_err(|_| ContractError::NoSubmissions {})?;
+            .filter_map(|&index| round_details.oracle(index))
+            .map(|oracle| oracle.to_string())
+            .collect::<Vec<String>>();
+        round_details.excluded = outliers;
+
         ROUNDS.save(
             deps.storage,
             round_id.into(),
             &Round {
-                answer: Some(Uint128::new(new_answer)),
+                answer: Some(new_answer),
                 started_at: round.started_at,
                 updated_at: Some(timestamp),
                 answered_in_round: round_id,
@@ -268,8 +333,9 @@ pub fn execute_submit(
         LATEST_ROUND_ID.save(deps.storage, &round_id)?;
         response = response.add_event(
             Event::new("answer_updated")
-                .add_attribute("current", Uint128::new(new_answer))
-                .add_attribute("round_id", round_id.to_string()),
+                .add_attribute("current", new_answer)
+                .add_attribute("round_id", round_id.to_string())
+                .add_attribute("excluded", format!("{:?}", &excluded)),
         );
 
         let previous_round_id = prev_round_id(round_id)?;
@@ -281,7 +347,7 @@ pub fn execute_submit(
                 previous_round_id,
                 previous_answer: prev_round.answer.unwrap_or_default(),
                 round_id,
-                answer: Uint128::new(new_answer),
+                answer: new_answer,
             })?,
             funds: vec![],
         };
@@ -305,11 +371,160 @@ pub fn execute_submit(
         DETAILS.save(deps.storage, round_id.into(), &round_details)?;
     } else {
         DETAILS.remove(deps.storage, round_id.into());
+        response = close_round(deps.storage, round_id, &round_details, response)?;
+    }
+    // earlier rounds stop taking submissions once a later one is answered or the next one starts
+    if answered {
+        response = close_previous_round(deps.storage, round_id.checked_sub(1), response)?;
+    }
+    if started {
+        response = close_previous_round(deps.storage, round_id.checked_sub(2), response)?;
     }
 
     Ok(response)
 }
 
+fn close_previous_round(
+    storage: &mut dyn Storage,
+    round_id: Option<u32>,
+    response: Response,
+) -> Result<Response, ContractError> {
+    let round_id = match round_id {
+        Some(round_id) => round_id,
+        None => return Ok(response),
+    };
+    match DETAILS.may_load(storage, round_id.into())? {
+        Some(details) => {
+            DETAILS.remove(storage, round_id.into());
+            close_round(storage, round_id, &details, response)
+        }
+        None => Ok(response),
+    }
+}
+
+/// Records the outliers excluded from the final answer of a closed round and slashes them
+fn close_round(
+    storage: &mut dyn Storage,
+    round_id: u32,
+    details: &RoundDetails,
+    mut response: Response,
+) -> Result<Response, ContractError> {
+    let staking = CONFIG.load(storage)?.staking;
+    for &index in details.excluded.iter() {
+        let excluded_oracle = match details.oracle(index) {
+            Some(oracle) => oracle,
+            None => continue,
+        };
+        // removed oracles have no stats left
+        let mut status = match ORACLES.may_load(storage, excluded_oracle)? {
+            Some(status) => status,
+            None => continue,
+        };
+        status.excluded_count += 1;
+        status.last_excluded_round = Some(round_id);
+        ORACLES.save(storage, excluded_oracle, &status)?;
+        match &staking {
+            Some(staking) if !staking.outlier_penalty.is_zero() => {
+                response = response.add_submessage(slash_msg(
+                    staking,
+                    excluded_oracle,
+                    staking.outlier_penalty,
+                    format!("outlier in round {}", round_id),
+                )?);
+            }
+            _ => {}
+        }
+    }
+    Ok(response)
+}
+
+/// Answer of the round and the outliers left out of it, unless the rest weighs too little
+fn aggregate(
+    details: &RoundDetails,
+    outlier_filter: Option<OutlierFilter>,
+    min_total_weight: u64,
+) -> Result<Option<(Uint128, Vec<usize>)>, ContractError> {
+    let outliers = match outlier_filter {
+        Some(filter) => filter
+            .outliers(&details.submissions)
+            .map_err(|_| ContractError::NoSubmissions {})?,
+        None => vec![],
+    };
+    let mut entries = details
+        .submissions
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !outliers.contains(index))
+        .map(|(index, &submission)| (submission, details.weight(index)))
+        // oracles without weight don't take part in the answer
+        .filter(|&(_, weight)| weight != 0)
+        .collect::<Vec<(Uint128, u64)>>();
+    let total_weight: u128 = entries.iter().map(|&(_, weight)| u128::from(weight)).sum();
+    if entries.is_empty() || total_weight < u128::from(min_total_weight) {
+        return Ok(None);
+    }
+
+    let uniform = entries.windows(2).all(|pair| pair[0].1 == pair[1].1);
+    let answer = if uniform {
+        let mut submissions = entries
+            .iter()
+            .map(|&(submission, _)| submission)
+            .collect::<Vec<Uint128>>();
+        calculate_median(&mut submissions)
+    } else {
+        weighted_median(&mut entries)
+    }
+    .map_err(|_| ContractError::NoSubmissions {})?;
+
+    Ok(Some((answer, outliers)))
+}
+
+fn slash_msg(
+    staking: &StakingConfig,
+    oracle: &Addr,
+    amount: Uint128,
+    reason: String,
+) -> StdResult<SubMsg> {
+    let msg = WasmMsg::Execute {
+        contract_addr: staking.staking.to_string(),
+        msg: to_binary(&StakingMsg::Slash {
+            oracle: oracle.to_string(),
+            amount,
+            reason,
+        })?,
+        funds: vec![],
+    };
+    Ok(SubMsg::reply_on_error(msg, SLASH_REPLY_ID))
+}
+
+fn lock_msg(staking: &StakingConfig, oracle: &Addr, amount: Uint128) -> StdResult<WasmMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: staking.staking.to_string(),
+        msg: to_binary(&StakingMsg::Lock {
+            oracle: oracle.to_string(),
+            amount,
+        })?,
+        funds: vec![],
+    })
+}
+
+fn validate_stake(deps: Deps, oracle: &Addr) -> Result<(), ContractError> {
+    let staking = match CONFIG.load(deps.storage)?.staking {
+        Some(staking) if !staking.min_stake.is_zero() => staking,
+        _ => return Ok(()),
+    };
+    let StakeResponse { bonded, .. } = deps.querier.query_wasm_smart(
+        staking.staking,
+        &StakingQuery::GetStake {
+            oracle: oracle.to_string(),
+        },
+    )?;
+    if bonded < staking.min_stake {
+        return Err(ContractError::InsufficientStake {});
+    }
+    Ok(())
+}
+
 fn validate_oracle_round(
     storage: &dyn Storage,
     oracle: &OracleStatus,
@@ -389,10 +604,13 @@ fn initialize_new_round(storage: &mut dyn Storage, round_id: u32, timestamp: u64
         round_id.into(),
         &RoundDetails {
             submissions: vec![],
+            oracles: vec![],
+            weights: vec![],
             max_submissions: max_submission_count,
             min_submissions: min_submission_count,
             timeout,
             payment_amount,
+            excluded: vec![],
         },
     )?;
     ROUNDS.update(storage, round_id.into(), |round| -> StdResult<_> {
@@ -435,13 +653,20 @@ pub fn execute_change_oracles(
     max_submissions: u32,
     restart_delay: u32,
 ) -> Result<Response, ContractError> {
-    validate_ownership(deps.as_ref(), &info)?;
+    validate_role(deps.as_ref(), &info, ORACLE_MANAGER)?;
 
     let mut response = Response::new();
+    let staking = CONFIG
+        .load(deps.storage)?
+        .staking
+        .filter(|staking| !staking.min_stake.is_zero());
 
     for oracle in removed.iter() {
         let oracle = deps.api.addr_validate(oracle)?;
-        remove_oracle(deps.storage, oracle)?;
+        remove_oracle(deps.storage, oracle.clone())?;
+        if let Some(staking) = &staking {
+            response = response.add_message(lock_msg(staking, &oracle, Uint128::zero())?);
+        }
     }
 
     if added.len() != added_admins.len() {
@@ -456,19 +681,27 @@ pub fn execute_change_oracles(
     for (oracle, admin) in added.iter().zip(added_admins) {
         let oracle = deps.api.addr_validate(oracle)?;
         let admin = deps.api.addr_validate(&admin)?;
-        add_oracle(deps.storage, oracle, admin)?;
+        validate_stake(deps.as_ref(), &oracle)?;
+        add_oracle(deps.storage, oracle.clone(), admin)?;
+        // the minimum stake stays bonded while the oracle is active
+        if let Some(staking) = &staking {
+            response = response.add_message(lock_msg(staking, &oracle, staking.min_stake)?);
+        }
     }
 
     let Config {
         payment_amount,
         timeout,
+        min_total_weight,
         ..
     } = CONFIG.load(deps.storage)?;
+    if u128::from(min_total_weight) > total_weight(deps.storage)? {
+        return Err(ContractError::MinTotalWeightUnreachable {});
+    }
 
-    let res = execute_update_future_rounds(
+    let res = update_future_rounds(
         deps,
         env,
-        info,
         payment_amount,
         min_submissions,
         max_submissions,
@@ -489,6 +722,15 @@ pub fn execute_change_oracles(
     Ok(response)
 }
 
+/// Weight of all active oracles
+fn total_weight(storage: &dyn Storage) -> StdResult<u128> {
+    let mut total_weight = 0u128;
+    for oracle in ORACLE_ADDRESSES.load(storage)? {
+        total_weight += u128::from(ORACLES.load(storage, &oracle)?.weight);
+    }
+    Ok(total_weight)
+}
+
 fn remove_oracle(storage: &mut dyn Storage, oracle: Addr) -> Result<(), ContractError> {
     // TODO: is this needed?
     // let reporting_round = REPORTING_ROUND_ID_read.load()?;
@@ -531,6 +773,10 @@ fn add_oracle(storage: &mut dyn Storage, oracle: Addr, admin: Addr) -> Result<()
                 index: 0,
                 admin: admin.clone(),
                 pending_admin: None,
+                weight: 1,
+                excluded_count: 0,
+                last_excluded_round: None,
+                inactivity_slashed_round: None,
             })
         })?;
 
@@ -644,6 +890,8 @@ pub fn execute_request_new_round(
     }
 
     initialize_new_round(deps.storage, new_round_id, timestamp)?;
+    let response =
+        close_previous_round(deps.storage, new_round_id.checked_sub(2), Response::new())?;
 
     REQUESTERS.save(
         deps.storage,
@@ -655,7 +903,7 @@ pub fn execute_request_new_round(
     )?;
 
     let round_id_serialized = to_binary(&new_round_id)?;
-    Ok(Response::new()
+    Ok(response
         .add_event(
             Event::new("new_round")
                 .add_attribute("round_id", new_round_id.to_string())
@@ -804,7 +1052,7 @@ pub fn execute_set_requester_permissions(
     authorized: bool,
     delay: u32,
 ) -> Result<Response, ContractError> {
-    validate_ownership(deps.as_ref(), &info)?;
+    validate_role(deps.as_ref(), &info, CONFIG_ADMIN)?;
 
     let requester_addr = deps.api.addr_validate(&requester)?;
     let curr_requester = REQUESTERS
@@ -846,8 +1094,28 @@ pub fn execute_update_future_rounds(
     restart_delay: u32,
     timeout: u32,
 ) -> Result<Response, ContractError> {
-    validate_ownership(deps.as_ref(), &info)?;
+    validate_role(deps.as_ref(), &info, CONFIG_ADMIN)?;
+
+    update_future_rounds(
+        deps,
+        env,
+        payment_amount,
+        min_submissions,
+        max_submissions,
+        restart_delay,
+        timeout,
+    )
+}
 
+fn update_future_rounds(
+    deps: DepsMut,
+    env: Env,
+    payment_amount: Uint128,
+    min_submissions: u32,
+    max_submissions: u32,
+    restart_delay: u32,
+    timeout: u32,
+) -> Result<Response, ContractError> {
     let oracle_count = get_oracle_count(deps.as_ref(), env)?;
 
     if min_submissions > max_submissions {
@@ -894,7 +1162,7 @@ pub fn execute_set_validator(
     info: MessageInfo,
     validator: String,
 ) -> Result<Response, ContractError> {
-    validate_ownership(deps.as_ref(), &info)?;
+    validate_role(deps.as_ref(), &info, CONFIG_ADMIN)?;
 
     let validator_addr = deps.api.addr_validate(&validator)?;
     let old_validator = CONFIG.load(deps.storage)?.validator;
@@ -915,6 +1183,163 @@ pub fn execute_set_validator(
         .add_attribute("new", validator))
 }
 
+pub fn execute_set_outlier_filter(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    filter: Option<OutlierFilter>,
+) -> Result<Response, ContractError> {
+    validate_role(deps.as_ref(), &info, CONFIG_ADMIN)?;
+
+    if let Some(filter) = filter {
+        if filter.denominator == 0 || filter.numerator < filter.denominator {
+            return Err(ContractError::InvalidOutlierFilter {});
+        }
+    }
+    CONFIG.update(deps.storage, |config| -> StdResult<_> {
+        Ok(Config {
+            outlier_filter: filter,
+            ..config
+        })
+    })?;
+
+    let (numerator, denominator) =
+        filter.map_or((0, 0), |filter| (filter.numerator, filter.denominator));
+    Ok(Response::new().add_event(
+        Event::new("outlier_filter_updated")
+            .add_attribute("numerator", numerator.to_string())
+            .add_attribute("denominator", denominator.to_string()),
+    ))
+}
+
+pub fn execute_set_oracle_weights(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    oracles: Vec<String>,
+    weights: Vec<u64>,
+    min_total_weight: u64,
+) -> Result<Response, ContractError> {
+    validate_role(deps.as_ref(), &info, ORACLE_MANAGER)?;
+
+    if oracles.len() != weights.len() {
+        return Err(ContractError::OracleWeightCountMismatch {});
+    }
+    for (oracle, &weight) in oracles.iter().zip(&weights) {
+        let oracle = deps.api.addr_validate(oracle)?;
+        let mut oracle_status = ORACLES
+            .may_load(deps.storage, &oracle)?
+            .ok_or(ContractError::OracleNotEnabled {})?;
+        oracle_status.weight = weight;
+        ORACLES.save(deps.storage, &oracle, &oracle_status)?;
+    }
+
+    if u128::from(min_total_weight) > total_weight(deps.storage)? {
+        return Err(ContractError::MinTotalWeightUnreachable {});
+    }
+    CONFIG.update(deps.storage, |config| -> StdResult<_> {
+        Ok(Config {
+            min_total_weight,
+            ..config
+        })
+    })?;
+
+    Ok(Response::new().add_event(
+        Event::new("oracle_weights_updated")
+            .add_attribute("oracles", format!("{:?}", &oracles))
+            .add_attribute("weights", format!("{:?}", &weights))
+            .add_attribute("min_total_weight", min_total_weight.to_string()),
+    ))
+}
+
+pub fn execute_set_staking(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    config: Option<StakingConfig>,
+) -> Result<Response, ContractError> {
+    validate_role(deps.as_ref(), &info, CONFIG_ADMIN)?;
+
+    if let Some(config) = &config {
+        deps.api.addr_validate(config.staking.as_str())?;
+    }
+    let previous = CONFIG.load(deps.storage)?.staking;
+    CONFIG.update(deps.storage, |old| -> StdResult<_> {
+        Ok(Config {
+            staking: config.clone(),
+            ..old
+        })
+    })?;
+
+    // the locked minimum stake of active oracles follows the config
+    let locking = |staking: &Option<StakingConfig>| {
+        staking
+            .clone()
+            .filter(|staking| !staking.min_stake.is_zero())
+    };
+    let (previous, next) = (locking(&previous), locking(&config));
+    let mut messages = vec![];
+    for oracle in ORACLE_ADDRESSES.load(deps.storage)? {
+        if let Some(previous) = &previous {
+            messages.push(lock_msg(previous, &oracle, Uint128::zero())?);
+        }
+        if let Some(next) = &next {
+            messages.push(lock_msg(next, &oracle, next.min_stake)?);
+        }
+    }
+
+    let staking = config.map_or_else(String::new, |config| config.staking.to_string());
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("action", "staking_updated")
+        .add_attribute("staking", staking))
+}
+
+pub fn execute_slash_inactive(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    oracle: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let staking = match config.staking {
+        Some(staking) if !staking.inactivity_penalty.is_zero() => staking,
+        _ => return Err(ContractError::StakingDisabled {}),
+    };
+    let oracle_count = ORACLE_ADDRESSES.load(deps.storage)?.len();
+    if (config.max_submission_count as usize) < oracle_count {
+        return Err(ContractError::InactivityUndetectable {});
+    }
+    let oracle = deps.api.addr_validate(&oracle)?;
+    let mut oracle_status = ORACLES
+        .may_load(deps.storage, &oracle)?
+        .ok_or(ContractError::OracleNotEnabled {})?;
+
+    // rounds since the last submission, the last slashing or being added, whichever is latest
+    let rr_id = REPORTING_ROUND_ID.load(deps.storage)?;
+    let last_accounted = oracle_status
+        .last_reported_round
+        .max(oracle_status.inactivity_slashed_round)
+        .unwrap_or_else(|| oracle_status.starting_round.saturating_sub(1));
+    let missed = rr_id.saturating_sub(last_accounted);
+    if missed <= staking.max_missed_rounds {
+        return Err(ContractError::NotInactive {});
+    }
+    oracle_status.inactivity_slashed_round = Some(rr_id);
+    ORACLES.save(deps.storage, &oracle, &oracle_status)?;
+
+    Ok(Response::new()
+        .add_submessage(slash_msg(
+            &staking,
+            &oracle,
+            staking.inactivity_penalty,
+            format!("missed {} rounds", missed),
+        )?)
+        .add_attribute("action", "slash_inactive")
+        .add_attribute("oracle", oracle)
+        .add_attribute("missed", missed.to_string()))
+}
+
 pub fn execute_receive(
     deps: DepsMut,
     env: Env,
@@ -958,6 +1383,15 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::GetAdmin { oracle } => to_binary(&get_admin(deps, env, oracle)?),
         QueryMsg::GetOracleStatus { oracle } => to_binary(&get_oracle_status(deps, env, oracle)?),
         QueryMsg::GetOwner {} => to_binary(&get_owner(deps)?),
+        QueryMsg::GetOwnershipState {} => to_binary(&get_ownership_state(deps)?),
+        QueryMsg::HasRole { role, account } => {
+            to_binary(&has_role(deps, &role, &deps.api.addr_validate(&account)?)?)
+        }
+        QueryMsg::GetRoleMembers {
+            role,
+            start_after,
+            limit,
+        } => to_binary(&query_role_members(deps, role, start_after, limit)?),
         QueryMsg::AggregatorQuery(GetRoundData { round_id }) => {
             to_binary(&get_round_data(deps, env, round_id)?)
         }
@@ -1001,6 +1435,9 @@ pub fn get_aggregator_config(deps: Deps, _env: Env) -> StdResult<ConfigResponse>
         description: config.description,
         min_submission_value: config.min_submission_value,
         max_submission_value: config.max_submission_value,
+        outlier_filter: config.outlier_filter,
+        min_total_weight: config.min_total_weight,
+        staking: config.staking,
     })
 }
 
@@ -1032,24 +1469,28 @@ pub fn get_admin(deps: Deps, _env: Env, oracle: String) -> StdResult<Addr> {
     Ok(oracle.admin)
 }
 
-pub fn get_round_data(deps: Deps, _env: Env, round_id: u32) -> StdResult<RoundDataResponse> {
+pub fn get_round_data(deps: Deps, _env: Env, round_id: u64) -> StdResult<RoundDataResponse> {
+    // rounds of this aggregator never exceed u32
+    let round_id: u32 = round_id
+        .try_into()
+        .map_err(|_| StdError::generic_err(ContractError::NoData {}.to_string()))?;
     let round = ROUNDS.load(deps.storage, round_id.into())?;
     if round.answered_in_round == 0 {
         return Err(StdError::generic_err(ContractError::NoData {}.to_string()));
     }
     Ok(RoundDataResponse {
-        round_id,
+        round_id: round_id.into(),
         answer: round.answer,
         started_at: round.started_at,
         updated_at: round.updated_at,
-        answered_in_round: round.answered_in_round,
+        answered_in_round: round.answered_in_round.into(),
     })
 }
 
 pub fn get_latest_round_data(deps: Deps, env: Env) -> StdResult<RoundDataResponse> {
     let round_id = LATEST_ROUND_ID.load(deps.storage)?;
 
-    get_round_data(deps, env, round_id)
+    get_round_data(deps, env, round_id.into())
 }
 
 pub fn get_oracle_status(deps: Deps, _env: Env, oracle: String) -> StdResult<OracleStatus> {
@@ -1067,7 +1508,7 @@ fn validate_ownership(deps: Deps, info: &MessageInfo) -> Result<(), ContractErro
 
 #[cfg(test)]
 mod tests {
-    use cosmwasm_std::{OverflowError, OverflowOperation};
+    use cosmwasm_std::{from_slice, OverflowError, OverflowOperation};
 
     use super::*;
 
@@ -1083,4 +1524,60 @@ mod tests {
             )))
         );
     }
+
+    #[test]
+    fn legacy_state_loads() {
+        // stored before outlier exclusion, weights and staking
+        let config: Config = from_slice(
+            br#"{"link":"link","validator":"validator","payment_amount":"3","max_submission_count":3,
+            "min_submission_count":1,"restart_delay":0,"timeout":1800,"decimals":8,
+            "description":"LINK / USD","min_submission_value":"1","max_submission_value":"100"}"#,
+        )
+        .unwrap();
+        assert_eq!(config.outlier_filter, None);
+        assert_eq!(config.min_total_weight, 0);
+        assert_eq!(config.staking, None);
+
+        let oracle: OracleStatus = from_slice(
+            br#"{"withdrawable":"0","starting_round":1,"ending_round":4294967295,
+            "last_reported_round":2,"last_started_round":2,"latest_submission":"7","index":0,
+            "admin":"admin","pending_admin":null}"#,
+        )
+        .unwrap();
+        assert_eq!(oracle.weight, 1);
+        assert_eq!(oracle.excluded_count, 0);
+        assert_eq!(oracle.inactivity_slashed_round, None);
+
+        // submissions of rounds in progress keep counting with the default weight
+        let mut details: RoundDetails = from_slice(
+            br#"{"submissions":["5","9"],"max_submissions":3,"min_submissions":3,"timeout":1800,
+            "payment_amount":"3"}"#,
+        )
+        .unwrap();
+        details.submissions.push(Uint128::new(7));
+        details.oracles.push(Addr::unchecked("oracle"));
+        details.weights.push(5);
+        assert_eq!(details.oracle(2), None);
+        let (answer, _) = aggregate(&details, None, 3).unwrap().unwrap();
+        assert_eq!(answer, Uint128::new(7));
+    }
+
+    #[test]
+    fn aggregate_ignores_zero_weights() {
+        let details = |weights: Vec<u64>| RoundDetails {
+            submissions: vec![Uint128::new(5), Uint128::new(9), Uint128::new(7)],
+            oracles: vec![],
+            weights,
+            max_submissions: 3,
+            min_submissions: 1,
+            timeout: 0,
+            payment_amount: Uint128::zero(),
+            excluded: vec![],
+        };
+        assert_eq!(aggregate(&details(vec![0, 0, 0]), None, 0).unwrap(), None);
+        assert_eq!(
+            aggregate(&details(vec![0, 1, 0]), None, 0).unwrap(),
+            Some((Uint128::new(9), vec![]))
+        );
+    }
 }
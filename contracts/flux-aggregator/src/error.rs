@@ -124,4 +124,32 @@ pub enum ContractError {
     /// Unauthorized requester
     #[error("Unauthorized requester")]
     Unauthorized {},
+
+    /// Need same oracle and weight count
+    #[error("Need same oracle and weight count")]
+    OracleWeightCountMismatch {},
+
+    /// Min total weight cannot exceed the weight of all oracles
+    #[error("Min total weight cannot exceed the weight of all oracles")]
+    MinTotalWeightUnreachable {},
+
+    /// Oracle has less than the minimum stake bonded
+    #[error("Oracle has insufficient stake")]
+    InsufficientStake {},
+
+    /// No staking contract or penalty set
+    #[error("Staking is disabled")]
+    StakingDisabled {},
+
+    /// Oracle hasn't missed enough rounds to be slashed
+    #[error("Oracle has not missed enough rounds")]
+    NotInactive {},
+
+    /// Rounds can fill up before every oracle gets to submit, missing them isn't inactivity
+    #[error("Inactivity can't be told apart while max submissions are below the oracle count")]
+    InactivityUndetectable {},
+
+    /// Outlier filter would discard the majority of submissions
+    #[error("Outlier filter must have a non-zero denominator and keep k at least 1")]
+    InvalidOutlierFilter {},
 }
@@ -4,15 +4,17 @@ use chainlink_aggregator::{QueryMsg::*, RoundDataResponse};
 use cosmwasm_std::{
     attr, from_binary,
     testing::{mock_env, MockApi, MockStorage},
-    Addr, Attribute, Binary, Empty, Uint128,
+    to_binary, Addr, Attribute, Binary, Empty, Uint128,
 };
 use cw20::{BalanceResponse, Cw20Coin};
 use cw_multi_test::{App, BankKeeper, Contract, ContractWrapper, Executor};
+use median::stats::OutlierFilter;
 
 use crate::{
-    contract::{execute, instantiate, query},
+    contract::{execute, instantiate, query, reply},
     error::ContractError,
     msg::{ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg},
+    state::{OracleStatus, StakingConfig},
 };
 
 macro_rules! personas {
@@ -38,7 +40,7 @@ fn mock_app() -> App {
 }
 
 pub fn contract_flux_aggregator() -> Box<dyn Contract<Empty>> {
-    let contract = ContractWrapper::new(execute, instantiate, query);
+    let contract = ContractWrapper::new(execute, instantiate, query).with_reply(reply);
     Box::new(contract)
 }
 
@@ -56,7 +58,8 @@ pub fn contract_df_validator() -> Box<dyn Contract<Empty>> {
         deviation_flagging_validator::contract::execute,
         deviation_flagging_validator::contract::instantiate,
         deviation_flagging_validator::contract::query,
-    );
+    )
+    .with_reply(deviation_flagging_validator::contract::reply);
     Box::new(contract)
 }
 
@@ -124,6 +127,23 @@ fn default_init() -> (App, Addr, Addr, Addr) {
         )
         .unwrap();
 
+    // Validator only accepts registered aggregators
+    router
+        .execute_contract(
+            owner.clone(),
+            validator_addr,
+            &deviation_flagging_validator::msg::ExecuteMsg::SetAggregatorConfig {
+                aggregator: contract.to_string(),
+                config: Some(deviation_flagging_validator::state::AggregatorConfig {
+                    relative_threshold: None,
+                    absolute_threshold: None,
+                    recovery: None,
+                }),
+            },
+            &[],
+        )
+        .unwrap();
+
     // Supply contract with funds
     router
         .execute_contract(
@@ -365,6 +385,578 @@ fn submit_complete_round() {
     assert_eq!(round.answer, Some(Uint128::new(150))); // (100 + 200) / 2
 }
 
+#[test]
+fn submit_excluding_outliers() {
+    let oracles = personas![Ned, Nelly, Neil, Nora, Nate];
+    let (mut router, owner, _link_addr, contract) = default_init();
+
+    let msg = ExecuteMsg::ChangeOracles {
+        removed: vec![],
+        added: oracles.clone(),
+        added_admins: oracles.clone(),
+        min_submissions: 4,
+        max_submissions: 5,
+        restart_delay: RESTART_DELAY,
+    };
+    router
+        .execute_contract(owner.clone(), contract.clone(), &msg, &[])
+        .unwrap();
+
+    // k has to keep at least the majority of submissions
+    let filter = OutlierFilter {
+        numerator: 1,
+        denominator: 2,
+        tolerance_bps: 10,
+    };
+    let res = router.execute_contract(
+        owner.clone(),
+        contract.clone(),
+        &ExecuteMsg::SetOutlierFilter {
+            filter: Some(filter),
+        },
+        &[],
+    );
+    assert_eq!(
+        res.unwrap_err(),
+        ContractError::InvalidOutlierFilter {}.to_string()
+    );
+    let filter = OutlierFilter {
+        numerator: 3,
+        denominator: 1,
+        tolerance_bps: 10,
+    };
+    let msg = ExecuteMsg::SetOutlierFilter {
+        filter: Some(filter),
+    };
+    let res = router.execute_contract(Addr::unchecked("Ned"), contract.clone(), &msg, &[]);
+    assert_eq!(
+        res.unwrap_err(),
+        owned::error::ContractError::MissingRole {
+            role: owned::roles::CONFIG_ADMIN.to_owned()
+        }
+        .to_string()
+    );
+    router
+        .execute_contract(owner, contract.clone(), &msg, &[])
+        .unwrap();
+    let config: ConfigResponse = router
+        .wrap()
+        .query_wasm_smart(contract.clone(), &QueryMsg::GetAggregatorConfig {})
+        .unwrap();
+    assert_eq!(config.outlier_filter, Some(filter));
+
+    let submissions = [100_u128, 102, 98, 150, 101];
+    let mut excluded = vec![];
+    for (oracle, submission) in oracles.iter().zip(submissions.iter()) {
+        let res = router
+            .execute_contract(
+                Addr::unchecked(oracle),
+                contract.clone(),
+                &ExecuteMsg::Submit {
+                    round_id: 1,
+                    submission: Uint128::new(*submission),
+                },
+                &[],
+            )
+            .unwrap();
+        excluded.extend(
+            res.events
+                .iter()
+                .flat_map(|event| event.attributes.iter())
+                .filter(|attribute| attribute.key == "excluded")
+                .map(|attribute| attribute.value.clone()),
+        );
+    }
+    // the answer was updated by the last two submissions, both without Nora's
+    assert_eq!(excluded, vec!["[\"Nora\"]", "[\"Nora\"]"]);
+
+    let round: RoundDataResponse = router
+        .wrap()
+        .query_wasm_smart(
+            contract.clone(),
+            &QueryMsg::AggregatorQuery(GetLatestRoundData {}),
+        )
+        .unwrap();
+    assert_eq!(round.answer, Some(Uint128::new(100))); // (100 + 101) / 2
+
+    let status: OracleStatus = router
+        .wrap()
+        .query_wasm_smart(
+            contract.clone(),
+            &QueryMsg::GetOracleStatus {
+                oracle: "Nora".to_owned(),
+            },
+        )
+        .unwrap();
+    assert_eq!(status.excluded_count, 1);
+    assert_eq!(status.last_excluded_round, Some(1));
+    let status: OracleStatus = router
+        .wrap()
+        .query_wasm_smart(
+            contract.clone(),
+            &QueryMsg::GetOracleStatus {
+                oracle: "Nate".to_owned(),
+            },
+        )
+        .unwrap();
+    assert_eq!(status.excluded_count, 0);
+
+    // Nora is an outlier of the first answer, but not of the final one
+    let submissions = [98_u128, 100, 102, 112, 110];
+    let mut excluded = vec![];
+    for (oracle, submission) in oracles.iter().zip(submissions.iter()) {
+        let res = router
+            .execute_contract(
+                Addr::unchecked(oracle),
+                contract.clone(),
+                &ExecuteMsg::Submit {
+                    round_id: 2,
+                    submission: Uint128::new(*submission),
+                },
+                &[],
+            )
+            .unwrap();
+        excluded.extend(
+            res.events
+                .iter()
+                .flat_map(|event| event.attributes.iter())
+                .filter(|attribute| attribute.key == "excluded")
+                .map(|attribute| attribute.value.clone()),
+        );
+    }
+    assert_eq!(excluded, vec!["[\"Nora\"]", "[]"]);
+
+    let status: OracleStatus = router
+        .wrap()
+        .query_wasm_smart(
+            contract,
+            &QueryMsg::GetOracleStatus {
+                oracle: "Nora".to_owned(),
+            },
+        )
+        .unwrap();
+    assert_eq!(status.excluded_count, 1);
+    assert_eq!(status.last_excluded_round, Some(1));
+}
+
+#[test]
+fn submit_weighted() {
+    let oracles = personas![Ned, Nelly, Neil];
+    let (mut router, owner, _link_addr, contract) = default_init();
+
+    let msg = ExecuteMsg::ChangeOracles {
+        removed: vec![],
+        added: oracles.clone(),
+        added_admins: oracles.clone(),
+        min_submissions: 1,
+        max_submissions: oracles.len() as u32,
+        restart_delay: RESTART_DELAY,
+    };
+    router
+        .execute_contract(owner.clone(), contract.clone(), &msg, &[])
+        .unwrap();
+
+    let set_weights = |weights: Vec<u64>, min_total_weight| ExecuteMsg::SetOracleWeights {
+        oracles: personas![Ned, Nelly, Neil],
+        weights,
+        min_total_weight,
+    };
+    let res = router.execute_contract(
+        owner.clone(),
+        contract.clone(),
+        &set_weights(vec![1, 1], 0),
+        &[],
+    );
+    assert_eq!(
+        res.unwrap_err(),
+        ContractError::OracleWeightCountMismatch {}.to_string()
+    );
+    let res = router.execute_contract(
+        owner.clone(),
+        contract.clone(),
+        &set_weights(vec![1, 1, 4], 7),
+        &[],
+    );
+    assert_eq!(
+        res.unwrap_err(),
+        ContractError::MinTotalWeightUnreachable {}.to_string()
+    );
+    let res = router.execute_contract(
+        Addr::unchecked("Ned"),
+        contract.clone(),
+        &set_weights(vec![1, 1, 4], 5),
+        &[],
+    );
+    assert_eq!(
+        res.unwrap_err(),
+        owned::error::ContractError::MissingRole {
+            role: owned::roles::ORACLE_MANAGER.to_owned()
+        }
+        .to_string()
+    );
+    router
+        .execute_contract(
+            owner.clone(),
+            contract.clone(),
+            &set_weights(vec![1, 1, 4], 5),
+            &[],
+        )
+        .unwrap();
+    let status: OracleStatus = router
+        .wrap()
+        .query_wasm_smart(
+            contract.clone(),
+            &QueryMsg::GetOracleStatus {
+                oracle: "Neil".to_owned(),
+            },
+        )
+        .unwrap();
+    assert_eq!(status.weight, 4);
+
+    let submit = |router: &mut App, oracle: &str, submission: u128| {
+        router
+            .execute_contract(
+                Addr::unchecked(oracle),
+                contract.clone(),
+                &ExecuteMsg::Submit {
+                    round_id: 1,
+                    submission: Uint128::new(submission),
+                },
+                &[],
+            )
+            .unwrap();
+        let round: RoundDataResponse = router
+            .wrap()
+            .query_wasm_smart(
+                contract.clone(),
+                &QueryMsg::AggregatorQuery(GetLatestRoundData {}),
+            )
+            .unwrap();
+        round.answer
+    };
+    // a single light submission doesn't answer the round
+    assert_eq!(submit(&mut router, "Ned", 100), None);
+    assert_eq!(submit(&mut router, "Neil", 200), Some(Uint128::new(200)));
+    // Neil still outweighs the others
+    assert_eq!(submit(&mut router, "Nelly", 110), Some(Uint128::new(200)));
+
+    // the rest of the oracles wouldn't reach the minimum total weight
+    let remove = ExecuteMsg::ChangeOracles {
+        removed: vec!["Neil".to_owned()],
+        added: vec![],
+        added_admins: vec![],
+        min_submissions: 1,
+        max_submissions: 2,
+        restart_delay: RESTART_DELAY,
+    };
+    let res = router.execute_contract(owner.clone(), contract.clone(), &remove, &[]);
+    assert_eq!(
+        res.unwrap_err(),
+        ContractError::MinTotalWeightUnreachable {}.to_string()
+    );
+    router
+        .execute_contract(
+            owner.clone(),
+            contract.clone(),
+            &set_weights(vec![1, 1, 4], 2),
+            &[],
+        )
+        .unwrap();
+    router
+        .execute_contract(owner, contract, &remove, &[])
+        .unwrap();
+}
+
+pub fn contract_staking() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        staking::contract::execute,
+        staking::contract::instantiate,
+        staking::contract::query,
+    );
+    Box::new(contract)
+}
+
+#[test]
+fn staking() {
+    let oracles = personas![Ned, Nelly, Neil, Nora, Nate];
+    let (mut router, owner, link_addr, contract) = default_init();
+
+    let id = router.store_code(contract_staking());
+    let staking_addr = router
+        .instantiate_contract(
+            id,
+            owner.clone(),
+            &staking::msg::InstantiateMsg {
+                link: link_addr.to_string(),
+                unbonding_period: 3600,
+            },
+            &[],
+            "Staking",
+            None,
+        )
+        .unwrap();
+    router
+        .execute_contract(
+            owner.clone(),
+            staking_addr.clone(),
+            &staking::msg::ExecuteMsg::SetSlasher {
+                slasher: contract.to_string(),
+                max_amount: Some(Uint128::new(20)),
+            },
+            &[],
+        )
+        .unwrap();
+    router
+        .execute_contract(
+            owner.clone(),
+            staking_addr.clone(),
+            &staking::msg::ExecuteMsg::SetSlashableOracles {
+                slasher: contract.to_string(),
+                added: oracles.clone(),
+                removed: vec![],
+            },
+            &[],
+        )
+        .unwrap();
+
+    let slash_inactive = ExecuteMsg::SlashInactive {
+        oracle: "Nate".to_owned(),
+    };
+    let res = router.execute_contract(owner.clone(), contract.clone(), &slash_inactive, &[]);
+    assert_eq!(
+        res.unwrap_err(),
+        ContractError::StakingDisabled {}.to_string()
+    );
+
+    let staking_config = StakingConfig {
+        staking: staking_addr.clone(),
+        min_stake: Uint128::new(50),
+        outlier_penalty: Uint128::new(10),
+        inactivity_penalty: Uint128::new(20),
+        max_missed_rounds: 1,
+    };
+    router
+        .execute_contract(
+            owner.clone(),
+            contract.clone(),
+            &ExecuteMsg::SetStaking {
+                config: Some(staking_config.clone()),
+            },
+            &[],
+        )
+        .unwrap();
+    let config: ConfigResponse = router
+        .wrap()
+        .query_wasm_smart(contract.clone(), &QueryMsg::GetAggregatorConfig {})
+        .unwrap();
+    assert_eq!(config.staking, Some(staking_config));
+
+    // oracles can't be added before staking
+    let msg = ExecuteMsg::ChangeOracles {
+        removed: vec![],
+        added: oracles.clone(),
+        added_admins: oracles.clone(),
+        min_submissions: 4,
+        max_submissions: 5,
+        restart_delay: RESTART_DELAY,
+    };
+    let res = router.execute_contract(owner.clone(), contract.clone(), &msg, &[]);
+    assert_eq!(
+        res.unwrap_err(),
+        ContractError::InsufficientStake {}.to_string()
+    );
+    for oracle in oracles.iter() {
+        router
+            .execute_contract(
+                owner.clone(),
+                staking_addr.clone(),
+                &staking::msg::ExecuteMsg::SetAdmin {
+                    oracle: oracle.clone(),
+                    admin: owner.to_string(),
+                },
+                &[],
+            )
+            .unwrap();
+        router
+            .execute_contract(
+                owner.clone(),
+                link_addr.clone(),
+                &cw20_base::msg::ExecuteMsg::Send {
+                    contract: staking_addr.to_string(),
+                    amount: Uint128::new(100),
+                    msg: to_binary(&staking::msg::ReceiveMsg::Stake {
+                        oracle: oracle.clone(),
+                    })
+                    .unwrap(),
+                },
+                &[],
+            )
+            .unwrap();
+    }
+    router
+        .execute_contract(owner.clone(), contract.clone(), &msg, &[])
+        .unwrap();
+    let stake = |router: &App, oracle: &str| -> staking::msg::StakeResponse {
+        router
+            .wrap()
+            .query_wasm_smart(
+                staking_addr.clone(),
+                &staking::msg::QueryMsg::GetStake {
+                    oracle: oracle.to_owned(),
+                },
+            )
+            .unwrap()
+    };
+    // the minimum stake can't be unbonded while active
+    assert_eq!(stake(&router, "Nate").locked, Uint128::new(50));
+    router
+        .execute_contract(
+            owner.clone(),
+            contract.clone(),
+            &ExecuteMsg::SetOutlierFilter {
+                filter: Some(OutlierFilter {
+                    numerator: 3,
+                    denominator: 1,
+                    tolerance_bps: 10,
+                }),
+            },
+            &[],
+        )
+        .unwrap();
+
+    let submit = |router: &mut App, oracle: &str, round_id: u32, submission: u128| {
+        router
+            .execute_contract(
+                Addr::unchecked(oracle),
+                contract.clone(),
+                &ExecuteMsg::Submit {
+                    round_id,
+                    submission: Uint128::new(submission),
+                },
+                &[],
+            )
+            .unwrap();
+    };
+    let bonded = |router: &App, oracle: &str| stake(router, oracle).bonded;
+
+    // Nora is an outlier of the first answer of the round, but not of the final one
+    for (oracle, submission) in oracles.iter().zip([98_u128, 100, 102, 112, 110].iter()) {
+        submit(&mut router, oracle, 1, *submission);
+    }
+    assert_eq!(bonded(&router, "Nora"), Uint128::new(100));
+    assert_eq!(bonded(&router, "Nate"), Uint128::new(100));
+
+    // Nate misses the following rounds
+    for round_id in 2..=3 {
+        let submissions = match round_id {
+            2 => [100_u128, 102, 98, 150],
+            _ => [100; 4],
+        };
+        for (oracle, submission) in oracles.iter().zip(submissions.iter()) {
+            submit(&mut router, oracle, round_id, *submission);
+        }
+        if round_id == 2 {
+            // Nora's exclusion is final once the next round is answered
+            assert_eq!(bonded(&router, "Nora"), Uint128::new(100));
+            let res =
+                router.execute_contract(owner.clone(), contract.clone(), &slash_inactive, &[]);
+            assert_eq!(res.unwrap_err(), ContractError::NotInactive {}.to_string());
+            // the filter the answer was computed with decides, not the one when it closes
+            router
+                .execute_contract(
+                    owner.clone(),
+                    contract.clone(),
+                    &ExecuteMsg::SetOutlierFilter { filter: None },
+                    &[],
+                )
+                .unwrap();
+        }
+    }
+    assert_eq!(bonded(&router, "Nora"), Uint128::new(90));
+    router
+        .execute_contract(owner.clone(), contract.clone(), &slash_inactive, &[])
+        .unwrap();
+    assert_eq!(bonded(&router, "Nate"), Uint128::new(80));
+    // the missed rounds are only slashed once
+    let res = router.execute_contract(owner.clone(), contract.clone(), &slash_inactive, &[]);
+    assert_eq!(res.unwrap_err(), ContractError::NotInactive {}.to_string());
+
+    let slashed: Uint128 = router
+        .wrap()
+        .query_wasm_smart(staking_addr.clone(), &staking::msg::QueryMsg::GetSlashed {})
+        .unwrap();
+    assert_eq!(slashed, Uint128::new(30));
+
+    // failing slashes are reported without reverting the call
+    router
+        .execute_contract(
+            owner.clone(),
+            staking_addr.clone(),
+            &staking::msg::ExecuteMsg::SetSlashableOracles {
+                slasher: contract.to_string(),
+                added: vec![],
+                removed: vec!["Nate".to_owned()],
+            },
+            &[],
+        )
+        .unwrap();
+    for round_id in 4..=5 {
+        for oracle in oracles.iter().take(4) {
+            submit(&mut router, oracle, round_id, 100);
+        }
+    }
+    let res = router
+        .execute_contract(owner.clone(), contract.clone(), &slash_inactive, &[])
+        .unwrap();
+    assert!(res
+        .events
+        .iter()
+        .any(|event| event.ty == "wasm-slash_failed"));
+    assert_eq!(bonded(&router, "Nate"), Uint128::new(80));
+
+    // missed rounds can't be told apart from full ones once max submissions are below the oracles
+    let msg = ExecuteMsg::ChangeOracles {
+        removed: vec![],
+        added: vec![],
+        added_admins: vec![],
+        min_submissions: 4,
+        max_submissions: 4,
+        restart_delay: RESTART_DELAY,
+    };
+    router
+        .execute_contract(owner.clone(), contract.clone(), &msg, &[])
+        .unwrap();
+    let res = router.execute_contract(owner.clone(), contract.clone(), &slash_inactive, &[]);
+    assert_eq!(
+        res.unwrap_err(),
+        ContractError::InactivityUndetectable {}.to_string()
+    );
+
+    // removed oracles and disabling staking release the locks
+    let msg = ExecuteMsg::ChangeOracles {
+        removed: vec!["Nate".to_owned()],
+        added: vec![],
+        added_admins: vec![],
+        min_submissions: 3,
+        max_submissions: 4,
+        restart_delay: RESTART_DELAY,
+    };
+    router
+        .execute_contract(owner.clone(), contract.clone(), &msg, &[])
+        .unwrap();
+    assert_eq!(stake(&router, "Nate").locked, Uint128::zero());
+    assert_eq!(stake(&router, "Ned").locked, Uint128::new(50));
+    router
+        .execute_contract(
+            owner,
+            contract,
+            &ExecuteMsg::SetStaking { config: None },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(stake(&router, "Ned").locked, Uint128::zero());
+}
+
 #[test]
 fn submit_twice() {
     let oracles = personas![Ned, Nelly];
@@ -634,9 +1226,62 @@ fn set_validator() {
         .unwrap();
     let attributes = res.events.last().unwrap().attributes.clone();
     assert_eq!(attributes.len(), 1);
-    // should only be usable by owner
+    // should only be usable by config admins
     let res = router.execute_contract(Addr::unchecked("Ned"), contract.clone(), &msg, &[]);
-    assert_eq!(res.unwrap_err(), ContractError::NotOwner {}.to_string());
+    assert_eq!(
+        res.unwrap_err(),
+        owned::error::ContractError::MissingRole {
+            role: owned::roles::CONFIG_ADMIN.to_owned()
+        }
+        .to_string()
+    );
+}
+
+#[test]
+fn oracle_manager_role() {
+    let (mut router, owner, _link_addr, contract) = default_init();
+    let manager = Addr::unchecked("Manager");
+
+    let msg = ExecuteMsg::ChangeOracles {
+        removed: vec![],
+        added: vec!["Oracle".into()],
+        added_admins: vec!["Admin".into()],
+        min_submissions: 1,
+        max_submissions: 1,
+        restart_delay: RESTART_DELAY,
+    };
+    let res = router.execute_contract(manager.clone(), contract.clone(), &msg, &[]);
+    assert_eq!(
+        res.unwrap_err(),
+        owned::error::ContractError::MissingRole {
+            role: owned::roles::ORACLE_MANAGER.to_owned()
+        }
+        .to_string()
+    );
+
+    let grant = ExecuteMsg::GrantRole {
+        role: owned::roles::ORACLE_MANAGER.to_owned(),
+        account: manager.to_string(),
+    };
+    router
+        .execute_contract(owner, contract.clone(), &grant, &[])
+        .unwrap();
+    router
+        .execute_contract(manager.clone(), contract.clone(), &msg, &[])
+        .unwrap();
+    let oracles: Vec<Addr> = router
+        .wrap()
+        .query_wasm_smart(contract.clone(), &QueryMsg::GetOracles {})
+        .unwrap();
+    assert_eq!(oracles, vec![Addr::unchecked("Oracle")]);
+
+    // the role doesn't extend to the rest of the configuration
+    let msg = ExecuteMsg::SetValidator {
+        validator: "new_validator".into(),
+    };
+    router
+        .execute_contract(manager, contract, &msg, &[])
+        .unwrap_err();
 }
 
 #[test]
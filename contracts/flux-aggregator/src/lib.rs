@@ -6,3 +6,12 @@ pub mod state;
 
 #[cfg(target_arch = "wasm32")]
 cosmwasm_std::create_entry_points!(contract);
+
+// not exported by `create_entry_points!`
+#[cfg(target_arch = "wasm32")]
+mod reply {
+    #[no_mangle]
+    extern "C" fn reply(env_ptr: u32, msg_ptr: u32) -> u32 {
+        cosmwasm_std::do_reply(&super::contract::reply, env_ptr, msg_ptr)
+    }
+}
@@ -1,5 +1,8 @@
 use cosmwasm_std::{Addr, Uint128};
 use cw20::Cw20ReceiveMsg;
+use median::stats::OutlierFilter;
+
+use crate::state::StakingConfig;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -34,9 +37,22 @@ pub enum ExecuteMsg {
     TransferOwnership {
         /// Address to transfer ownership to
         to: String,
+        /// Block time in seconds after which the transfer can no longer be accepted
+        expires_at: Option<u64>,
     },
     /// Finish contract ownership transfer. Can be used only by pending owner
     AcceptOwnership {},
+    /// Cancel the pending ownership transfer. Can be used only by owner
+    CancelOwnershipTransfer {},
+    /// Leave the contract without owner, disabling functionality restricted to the owner.
+    /// Can be used only by owner
+    RenounceOwnership {},
+    /// Grant a role, see `owned::roles`. Can be used only by owner
+    GrantRole { role: String, account: String },
+    /// Revoke a granted role. Can be used only by owner
+    RevokeRole { role: String, account: String },
+    /// Give up a role granted to the sender
+    RenounceRole { role: String },
     /// Invoked by oracles when they have witnessed a need to update
     Submit {
         /// ID of the round this submission pertains to
@@ -45,7 +61,8 @@ pub enum ExecuteMsg {
         submission: Uint128, // int256
     },
     /// Invoked by the owner to remove and add new oracles as well as
-    /// update the round related parameters that pertain to total oracle count
+    /// update the round related parameters that pertain to total oracle count.
+    /// Added oracles weigh 1, including removed ones added again
     ChangeOracles {
         /// Oracles to be removed
         removed: Vec<String>,
@@ -122,6 +139,31 @@ pub enum ExecuteMsg {
     },
     /// Handler for LINK token Send message
     Receive(Cw20ReceiveMsg),
+    /// Excludes submissions further than `k` median absolute deviations from the median
+    /// before aggregating, or stops excluding them when `None`. Callable only by config admin
+    SetOutlierFilter {
+        /// `k` must be at least 1, so that the majority of submissions is kept
+        filter: Option<OutlierFilter>,
+    },
+    /// Changes how much the oracles' submissions weigh in the answer, 1 by default.
+    /// Rounds are answered only once their submissions weigh at least `min_total_weight`.
+    /// Callable only by oracle manager
+    SetOracleWeights {
+        /// Oracles whose weight is changed
+        oracles: Vec<String>,
+        /// The new weights of the oracles
+        weights: Vec<u64>,
+        /// The new minimum total weight for each answer
+        min_total_weight: u64,
+    },
+    /// Sets the staking contract oracles need a minimum stake in, see `staking`,
+    /// or disables staking requirements and slashing if `None`.
+    /// Callable only by config admin
+    SetStaking { config: Option<StakingConfig> },
+    /// Slashes the oracle in the staking contract for missing too many rounds in a row.
+    /// Unavailable while rounds fill up before every oracle can submit.
+    /// Callable by anyone
+    SlashInactive { oracle: String },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -130,6 +172,22 @@ pub enum QueryMsg {
     /// Returns contract owner's address
     /// Response [`Addr`]
     GetOwner {},
+    /// Returns the owner, the pending owner and the expiry of the pending transfer
+    /// Response [`owned::msg::OwnershipStateResponse`]
+    GetOwnershipState {},
+    /// Returns whether the account holds the role, which the owner always does
+    /// Response `bool`
+    HasRole {
+        role: String,
+        account: String,
+    },
+    /// Returns the accounts granted the role, not including the owner
+    /// Response [`owned::msg::RoleMembersResponse`]
+    GetRoleMembers {
+        role: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     /// Returns the settings of the flux aggregator
     /// Response: [`ConfigResponse`]
     GetAggregatorConfig {},
@@ -179,6 +237,9 @@ pub struct ConfigResponse {
     pub description: String,
     pub min_submission_value: Uint128,
     pub max_submission_value: Uint128,
+    pub outlier_filter: Option<OutlierFilter>,
+    pub min_total_weight: u64,
+    pub staking: Option<StakingConfig>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
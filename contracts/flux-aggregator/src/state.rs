@@ -1,5 +1,6 @@
 use cosmwasm_std::{Addr, Uint128};
 use cw_storage_plus::{Item, Map, U32Key};
+use median::stats::OutlierFilter;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -18,6 +19,27 @@ pub struct Config {
 
     pub min_submission_value: Uint128,
     pub max_submission_value: Uint128,
+
+    #[serde(default)]
+    pub outlier_filter: Option<OutlierFilter>,
+    #[serde(default)]
+    pub min_total_weight: u64,
+    #[serde(default)]
+    pub staking: Option<StakingConfig>,
+}
+
+/// Stake required from oracles and the penalties slashed from it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakingConfig {
+    /// Staking contract, which must register this contract as slasher of its oracles
+    pub staking: Addr,
+    /// LINK an oracle must have bonded to be added
+    pub min_stake: Uint128,
+    /// LINK slashed for each round the oracle's submission is excluded as an outlier
+    pub outlier_penalty: Uint128,
+    /// LINK slashed once the oracle missed more than `max_missed_rounds` in a row
+    pub inactivity_penalty: Uint128,
+    pub max_missed_rounds: u32,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -31,6 +53,17 @@ pub struct OracleStatus {
     pub index: u16,
     pub admin: Addr,
     pub pending_admin: Option<Addr>,
+    /// Weight of the oracle's submissions in the answer
+    #[serde(default = "default_weight")]
+    pub weight: u64,
+    /// Number of closed rounds whose answer excluded the oracle's submission as an outlier
+    #[serde(default)]
+    pub excluded_count: u32,
+    #[serde(default)]
+    pub last_excluded_round: Option<u32>,
+    /// Round the oracle was last slashed in for missing rounds
+    #[serde(default)]
+    pub inactivity_slashed_round: Option<u32>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
@@ -44,10 +77,42 @@ pub struct Round {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct RoundDetails {
     pub submissions: Vec<Uint128>, // int256[]
+    /// Oracles in the order of their submissions
+    #[serde(default)]
+    pub oracles: Vec<Addr>,
+    /// Weights of the oracles at the time of their submissions
+    #[serde(default)]
+    pub weights: Vec<u64>,
     pub max_submissions: u32,
     pub min_submissions: u32,
     pub timeout: u32,
     pub payment_amount: Uint128,
+    /// Submissions left out of the latest answer, slashed once the round closes
+    #[serde(default)]
+    pub excluded: Vec<usize>,
+}
+
+impl RoundDetails {
+    /// Oracle of the submission, unknown in rounds started before oracles were recorded
+    pub fn oracle(&self, index: usize) -> Option<&Addr> {
+        if self.oracles.len() != self.submissions.len() {
+            return None;
+        }
+        self.oracles.get(index)
+    }
+
+    /// Weight of the submission, the default in rounds started before weights were recorded
+    pub fn weight(&self, index: usize) -> u64 {
+        if self.weights.len() != self.submissions.len() {
+            return default_weight();
+        }
+        self.weights[index]
+    }
+}
+
+/// Weight of oracles stored before weights were introduced
+fn default_weight() -> u64 {
+    1
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
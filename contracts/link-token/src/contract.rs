@@ -1,19 +1,30 @@
 use cosmwasm_std::{
-    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128,
+    attr, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128,
 };
-use cw20::{Cw20Coin, TokenInfoResponse};
+use cw2::set_contract_version;
+use cw20::{Cw20Coin, MinterResponse, TokenInfoResponse};
 use cw20_base::{
     allowances::{
-        execute_decrease_allowance, execute_increase_allowance, execute_transfer_from,
-        query_allowance,
+        execute_burn_from, execute_decrease_allowance, execute_increase_allowance,
+        execute_send_from, execute_transfer_from, query_allowance,
     },
-    contract::{create_accounts, execute_send, execute_transfer, query_balance},
-    ContractError,
+    contract::{
+        execute_burn, execute_mint, execute_send, execute_transfer, execute_update_marketing,
+        execute_upload_logo, query_balance, query_download_logo, query_marketing_info,
+        query_minter,
+    },
+    enumerable::{query_all_accounts, query_all_allowances},
+};
+use owned::contract::{
+    execute_accept_ownership, execute_cancel_ownership_transfer, execute_renounce_ownership,
+    execute_transfer_ownership, get_owner, get_ownership_state, instantiate as owned_init,
 };
 
 use crate::{
+    error::ContractError,
     msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
-    state::{TokenInfo, TOKEN_INFO},
+    permit::{execute_permit, query_permit_nonce},
+    state::TOKEN_INFO,
 };
 
 pub const TOKEN_NAME: &str = "Chainlink";
@@ -21,26 +32,51 @@ pub const TOKEN_SYMBOL: &str = "LINK";
 pub const DECIMALS: u8 = 18;
 pub const TOTAL_SUPPLY: u128 = 1_000_000_000;
 
+const CONTRACT_NAME: &str = "crates.io:link-token";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 pub fn instantiate(
     mut deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
-    _msg: InstantiateMsg,
-) -> StdResult<Response> {
-    let main_balance = Cw20Coin {
-        address: info.sender.into(),
-        amount: Uint128::from(TOTAL_SUPPLY),
-    };
-    let total_supply = create_accounts(&mut deps, &[main_balance])?;
-
-    // store token info
-    let data = TokenInfo {
-        name: TOKEN_NAME.to_string(),
-        symbol: TOKEN_SYMBOL.to_string(),
-        decimals: DECIMALS,
-        total_supply,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    // bridged tokens start without supply
+    let (initial_balances, mint) = match msg.bridged {
+        Some(bridged) => {
+            let mint = MinterResponse {
+                minter: bridged.minter,
+                cap: bridged.cap,
+            };
+            (vec![], Some(mint))
+        }
+        None => {
+            let main_balance = Cw20Coin {
+                address: info.sender.to_string(),
+                amount: Uint128::from(TOTAL_SUPPLY),
+            };
+            (vec![main_balance], None)
+        }
     };
-    TOKEN_INFO.save(deps.storage, &data)?;
+
+    // stores token info, minter and marketing info the way the cw20-base extensions expect
+    cw20_base::contract::instantiate(
+        deps.branch(),
+        env.clone(),
+        info.clone(),
+        cw20_base::msg::InstantiateMsg {
+            name: TOKEN_NAME.to_string(),
+            symbol: TOKEN_SYMBOL.to_string(),
+            decimals: DECIMALS,
+            initial_balances,
+            mint,
+            marketing: msg.marketing,
+        },
+    )?;
+    // replaces the version cw20-base stored as its own
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    owned_init(deps, env, info, owned::msg::InstantiateMsg {})?;
 
     Ok(Response::default())
 }
@@ -53,31 +89,99 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::Transfer { recipient, amount } => {
-            execute_transfer(deps, env, info, recipient, amount)
+            execute_transfer(deps, env, info, recipient, amount).map_err(ContractError::from)
         }
         ExecuteMsg::TransferFrom {
             owner,
             recipient,
             amount,
-        } => execute_transfer_from(deps, env, info, owner, recipient, amount),
+        } => execute_transfer_from(deps, env, info, owner, recipient, amount)
+            .map_err(ContractError::from),
         ExecuteMsg::Send {
             contract,
             amount,
             msg,
-        } => execute_send(deps, env, info, contract, amount, msg),
+        } => execute_send(deps, env, info, contract, amount, msg).map_err(ContractError::from),
         ExecuteMsg::IncreaseAllowance {
             spender,
             amount,
             expires,
-        } => execute_increase_allowance(deps, env, info, spender, amount, expires),
+        } => execute_increase_allowance(deps, env, info, spender, amount, expires)
+            .map_err(ContractError::from),
         ExecuteMsg::DecreaseAllowance {
             spender,
             amount,
             expires,
-        } => execute_decrease_allowance(deps, env, info, spender, amount, expires),
+        } => execute_decrease_allowance(deps, env, info, spender, amount, expires)
+            .map_err(ContractError::from),
+        ExecuteMsg::Mint { recipient, amount } => {
+            execute_mint(deps, env, info, recipient, amount).map_err(ContractError::from)
+        }
+        ExecuteMsg::Permit {
+            params,
+            pubkey,
+            signature,
+        } => execute_permit(deps, env, params, pubkey, signature),
+        ExecuteMsg::Burn { amount } => {
+            execute_burn(deps, env, info, amount).map_err(ContractError::from)
+        }
+        ExecuteMsg::BurnFrom { owner, amount } => {
+            validate_burner(deps.as_ref(), &info)?;
+            execute_burn_from(deps, env, info, owner, amount).map_err(ContractError::from)
+        }
+        ExecuteMsg::SendFrom {
+            owner,
+            contract,
+            amount,
+            msg,
+        } => execute_send_from(deps, env, info, owner, contract, amount, msg)
+            .map_err(ContractError::from),
+        ExecuteMsg::UpdateMarketing {
+            project,
+            description,
+            marketing,
+        } => execute_update_marketing(deps, env, info, project, description, marketing)
+            .map_err(ContractError::from),
+        ExecuteMsg::UploadLogo(logo) => {
+            execute_upload_logo(deps, env, info, logo).map_err(ContractError::from)
+        }
+        ExecuteMsg::UpdateMinter { minter } => execute_update_minter(deps, env, info, minter),
+        ExecuteMsg::TransferOwnership { to, expires_at } => {
+            execute_transfer_ownership(deps, env, info, to, expires_at).map_err(ContractError::from)
+        }
+        ExecuteMsg::AcceptOwnership {} => {
+            execute_accept_ownership(deps, env, info).map_err(ContractError::from)
+        }
+        ExecuteMsg::CancelOwnershipTransfer {} => {
+            execute_cancel_ownership_transfer(deps, env, info).map_err(ContractError::from)
+        }
+        ExecuteMsg::RenounceOwnership {} => {
+            execute_renounce_ownership(deps, env, info).map_err(ContractError::from)
+        }
     }
 }
 
+pub fn execute_update_minter(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    minter: String,
+) -> Result<Response, ContractError> {
+    validate_ownership(deps.as_ref(), &info)?;
+
+    let minter = deps.api.addr_validate(&minter)?;
+    let mut token_info = TOKEN_INFO.load(deps.storage)?;
+    let mint = token_info.mint.as_mut().ok_or(ContractError::NoMinter {})?;
+    let previous = std::mem::replace(&mut mint.minter, minter.clone());
+    TOKEN_INFO.save(deps.storage, &token_info)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_minter"),
+        attr("previous_minter", previous),
+        attr("minter", minter),
+    ]))
+}
+
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Balance { address } => to_binary(&query_balance(deps, address)?),
@@ -85,6 +189,20 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Allowance { owner, spender } => {
             to_binary(&query_allowance(deps, owner, spender)?)
         }
+        QueryMsg::PermitNonce { owner } => to_binary(&query_permit_nonce(deps, owner)?),
+        QueryMsg::AllAllowances {
+            owner,
+            start_after,
+            limit,
+        } => to_binary(&query_all_allowances(deps, owner, start_after, limit)?),
+        QueryMsg::AllAccounts { start_after, limit } => {
+            to_binary(&query_all_accounts(deps, start_after, limit)?)
+        }
+        QueryMsg::MarketingInfo {} => to_binary(&query_marketing_info(deps)?),
+        QueryMsg::DownloadLogo {} => to_binary(&query_download_logo(deps)?),
+        QueryMsg::Minter {} => to_binary(&query_minter(deps)?),
+        QueryMsg::GetOwner {} => to_binary(&get_owner(deps)?),
+        QueryMsg::GetOwnershipState {} => to_binary(&get_ownership_state(deps)?),
     }
 }
 
@@ -94,9 +212,28 @@ pub fn query_token_info(deps: Deps) -> StdResult<TokenInfoResponse> {
     Ok(info.into())
 }
 
+/// Only the minter burns bridged tokens of other accounts
+fn validate_burner(deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
+    match TOKEN_INFO.load(deps.storage)?.mint {
+        Some(mint) if mint.minter != info.sender => {
+            Err(cw20_base::ContractError::Unauthorized {}.into())
+        }
+        _ => Ok(()),
+    }
+}
+
+fn validate_ownership(deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
+    let owner = get_owner(deps)?;
+    if info.sender != owner {
+        return Err(ContractError::NotOwner {});
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::msg::BridgedMsg;
 
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
     use cosmwasm_std::{coins, Uint128};
@@ -107,7 +244,7 @@ mod tests {
 
         let env = mock_env();
         let info = mock_info(&"creator", &[]);
-        let _ = instantiate(deps.as_mut(), env, info, InstantiateMsg {}).unwrap();
+        let _ = instantiate(deps.as_mut(), env, info, InstantiateMsg::default()).unwrap();
 
         let query_res = query_token_info(deps.as_ref()).unwrap();
 
@@ -120,5 +257,69 @@ mod tests {
                 total_supply: Uint128::from(1_000_000_000_u128)
             }
         );
+
+        let version = cw2::get_contract_version(&deps.storage).unwrap();
+        assert_eq!(version.contract, CONTRACT_NAME);
+        assert_eq!(version.version, CONTRACT_VERSION);
+    }
+
+    #[test]
+    fn test_bridged_minter() {
+        let mut deps = mock_dependencies(&[]);
+        let msg = InstantiateMsg {
+            bridged: Some(BridgedMsg {
+                minter: "bridge".to_owned(),
+                cap: Some(Uint128::new(1000)),
+            }),
+            ..InstantiateMsg::default()
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+        assert_eq!(
+            query_token_info(deps.as_ref()).unwrap().total_supply,
+            Uint128::zero()
+        );
+
+        let update = ExecuteMsg::UpdateMinter {
+            minter: "new_bridge".to_owned(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bridge", &[]),
+            update.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::NotOwner {});
+        execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), update).unwrap();
+
+        let minter = query_minter(deps.as_ref()).unwrap().unwrap();
+        assert_eq!(minter.minter, "new_bridge");
+        assert_eq!(minter.cap, Some(Uint128::new(1000)));
+    }
+
+    #[test]
+    fn test_fixed_supply_has_no_minter() {
+        let mut deps = mock_dependencies(&[]);
+        let info = mock_info("creator", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        assert_eq!(query_minter(deps.as_ref()).unwrap(), None);
+
+        let update = ExecuteMsg::UpdateMinter {
+            minter: "bridge".to_owned(),
+        };
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), update).unwrap_err();
+        assert_eq!(err, ContractError::NoMinter {});
+        let mint = ExecuteMsg::Mint {
+            recipient: "creator".to_owned(),
+            amount: Uint128::new(1),
+        };
+        let err = execute(deps.as_mut(), mock_env(), info, mint).unwrap_err();
+        assert_eq!(err, cw20_base::ContractError::Unauthorized {}.into());
     }
 }
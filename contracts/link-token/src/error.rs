@@ -0,0 +1,40 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Base(#[from] cw20_base::ContractError),
+
+    #[error("{0}")]
+    Owned(#[from] owned::error::ContractError),
+
+    /// Only callable by owner
+    #[error("Only callable by owner")]
+    NotOwner {},
+
+    /// Token was instantiated with a fixed supply
+    #[error("Token has no minter")]
+    NoMinter {},
+
+    /// Permit was signed for another chain or contract
+    #[error("Invalid permit")]
+    InvalidPermit {},
+
+    #[error("Permit expired")]
+    PermitExpired {},
+
+    /// Nonce isn't the owner's next permit nonce
+    #[error("Invalid permit nonce")]
+    InvalidNonce {},
+
+    /// Public key doesn't belong to the owner
+    #[error("Invalid permit signer")]
+    InvalidSigner {},
+
+    #[error("Invalid permit signature")]
+    InvalidSignature {},
+}
@@ -5,15 +5,18 @@ mod receiver_mock;
 use crate::{
     contract::{execute, instantiate, query, DECIMALS, TOKEN_NAME, TOKEN_SYMBOL, TOTAL_SUPPLY},
     integration_tests::receiver_mock::{contract_receiver_mock, MockInstantiateMsg, PingMsg},
-    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    msg::{BridgedMsg, ExecuteMsg, InstantiateMsg, QueryMsg},
 };
 use cosmwasm_std::{
     attr,
     testing::{mock_env, MockApi, MockStorage, MOCK_CONTRACT_ADDR},
-    to_binary, Addr, Empty, OverflowError, OverflowOperation, StdError, Uint128,
+    to_binary, Addr, Binary, Empty, OverflowError, OverflowOperation, StdError, Uint128,
 };
-use cw20::{AllowanceResponse, BalanceResponse, TokenInfoResponse};
-use cw20_base::ContractError;
+use cw20::{
+    AllAccountsResponse, AllAllowancesResponse, AllowanceResponse, BalanceResponse,
+    DownloadLogoResponse, EmbeddedLogo, Logo, LogoInfo, MarketingInfoResponse, TokenInfoResponse,
+};
+use cw20_base::{msg::InstantiateMarketingInfo, ContractError};
 use cw_multi_test::{App, BankKeeper, Contract, ContractWrapper, Executor};
 
 fn mock_app() -> App {
@@ -35,7 +38,14 @@ fn test_successful_init() {
     let id = router.store_code(contract_link_token());
     let sender = Addr::unchecked("owner");
     let contract = router
-        .instantiate_contract(id, sender.clone(), &InstantiateMsg {}, &[], "LINK", None)
+        .instantiate_contract(
+            id,
+            sender.clone(),
+            &InstantiateMsg::default(),
+            &[],
+            "LINK",
+            None,
+        )
         .unwrap();
 
     let expected_state = TokenInfoResponse {
@@ -59,7 +69,14 @@ fn test_transfer_success() {
     let id = router.store_code(contract_link_token());
     let owner = Addr::unchecked("owner");
     let contract = router
-        .instantiate_contract(id, owner.clone(), &InstantiateMsg {}, &[], "LINK", None)
+        .instantiate_contract(
+            id,
+            owner.clone(),
+            &InstantiateMsg::default(),
+            &[],
+            "LINK",
+            None,
+        )
         .unwrap();
     let recipient_addr = MOCK_CONTRACT_ADDR;
 
@@ -86,7 +103,14 @@ fn test_transfer_underflow() {
     let id = router.store_code(contract_link_token());
     let owner = Addr::unchecked("owner");
     let contract = router
-        .instantiate_contract(id, owner.clone(), &InstantiateMsg {}, &[], "LINK", None)
+        .instantiate_contract(
+            id,
+            owner.clone(),
+            &InstantiateMsg::default(),
+            &[],
+            "LINK",
+            None,
+        )
         .unwrap();
 
     let balance = Uint128::new(TOTAL_SUPPLY);
@@ -118,7 +142,14 @@ fn test_queries() {
     let id = router.store_code(contract_link_token());
     let owner = Addr::unchecked("owner");
     let contract = router
-        .instantiate_contract(id, owner.clone(), &InstantiateMsg {}, &[], "LINK", None)
+        .instantiate_contract(
+            id,
+            owner.clone(),
+            &InstantiateMsg::default(),
+            &[],
+            "LINK",
+            None,
+        )
         .unwrap();
 
     let balance_res: BalanceResponse = router
@@ -164,7 +195,14 @@ fn test_modify_allowance() {
     let id = router.store_code(contract_link_token());
     let owner = Addr::unchecked("owner");
     let contract = router
-        .instantiate_contract(id, owner.clone(), &InstantiateMsg {}, &[], "LINK", None)
+        .instantiate_contract(
+            id,
+            owner.clone(),
+            &InstantiateMsg::default(),
+            &[],
+            "LINK",
+            None,
+        )
         .unwrap();
 
     let spender_addr = "spender";
@@ -216,7 +254,14 @@ fn test_transfer_from() {
     let id = router.store_code(contract_link_token());
     let owner = Addr::unchecked("owner");
     let contract = router
-        .instantiate_contract(id, owner.clone(), &InstantiateMsg {}, &[], "LINK", None)
+        .instantiate_contract(
+            id,
+            owner.clone(),
+            &InstantiateMsg::default(),
+            &[],
+            "LINK",
+            None,
+        )
         .unwrap();
 
     let spender = Addr::unchecked("spender");
@@ -282,7 +327,14 @@ fn test_transfer_from_without_allowance() {
     let id = router.store_code(contract_link_token());
     let owner = Addr::unchecked("owner");
     let contract = router
-        .instantiate_contract(id, owner.clone(), &InstantiateMsg {}, &[], "LINK", None)
+        .instantiate_contract(
+            id,
+            owner.clone(),
+            &InstantiateMsg::default(),
+            &[],
+            "LINK",
+            None,
+        )
         .unwrap();
 
     let recipient = "recipient";
@@ -313,7 +365,14 @@ fn test_change_allowance_self() {
     let id = router.store_code(contract_link_token());
     let owner = Addr::unchecked("owner");
     let contract = router
-        .instantiate_contract(id, owner.clone(), &InstantiateMsg {}, &[], "LINK", None)
+        .instantiate_contract(
+            id,
+            owner.clone(),
+            &InstantiateMsg::default(),
+            &[],
+            "LINK",
+            None,
+        )
         .unwrap();
 
     let msg = ExecuteMsg::IncreaseAllowance {
@@ -334,7 +393,14 @@ fn test_send() {
     let id = router.store_code(contract_link_token());
     let owner = Addr::unchecked("owner");
     let contract = router
-        .instantiate_contract(id, owner.clone(), &InstantiateMsg {}, &[], "LINK", None)
+        .instantiate_contract(
+            id,
+            owner.clone(),
+            &InstantiateMsg::default(),
+            &[],
+            "LINK",
+            None,
+        )
         .unwrap();
 
     let id = router.store_code(contract_receiver_mock());
@@ -393,3 +459,324 @@ fn test_send() {
         .unwrap();
     assert_eq!(receiver_balance.balance, amount);
 }
+
+fn bridged_link(router: &mut App, cap: Option<Uint128>) -> Addr {
+    let id = router.store_code(contract_link_token());
+    let msg = InstantiateMsg {
+        bridged: Some(BridgedMsg {
+            minter: "bridge".to_owned(),
+            cap,
+        }),
+        ..InstantiateMsg::default()
+    };
+    router
+        .instantiate_contract(id, Addr::unchecked("owner"), &msg, &[], "LINK", None)
+        .unwrap()
+}
+
+#[test]
+fn test_bridged_mint_within_cap() {
+    let mut router = mock_app();
+    let contract = bridged_link(&mut router, Some(Uint128::new(1000)));
+    let bridge = Addr::unchecked("bridge");
+
+    let mint = |amount| ExecuteMsg::Mint {
+        recipient: "user".to_owned(),
+        amount: Uint128::new(amount),
+    };
+    let res = router.execute_contract(Addr::unchecked("owner"), contract.clone(), &mint(1), &[]);
+    assert_eq!(res.unwrap_err(), ContractError::Unauthorized {}.to_string());
+
+    router
+        .execute_contract(bridge.clone(), contract.clone(), &mint(600), &[])
+        .unwrap();
+    let res = router.execute_contract(bridge.clone(), contract.clone(), &mint(401), &[]);
+    assert_eq!(
+        res.unwrap_err(),
+        ContractError::CannotExceedCap {}.to_string()
+    );
+    router
+        .execute_contract(bridge, contract.clone(), &mint(400), &[])
+        .unwrap();
+
+    let info: TokenInfoResponse = router
+        .wrap()
+        .query_wasm_smart(contract.clone(), &QueryMsg::TokenInfo {})
+        .unwrap();
+    assert_eq!(info.total_supply, Uint128::new(1000));
+    let balance: BalanceResponse = router
+        .wrap()
+        .query_wasm_smart(
+            contract,
+            &QueryMsg::Balance {
+                address: "user".to_owned(),
+            },
+        )
+        .unwrap();
+    assert_eq!(balance.balance, Uint128::new(1000));
+}
+
+#[test]
+fn test_bridged_burn_from() {
+    let mut router = mock_app();
+    let contract = bridged_link(&mut router, None);
+    let bridge = Addr::unchecked("bridge");
+    let user = Addr::unchecked("user");
+
+    let msg = ExecuteMsg::Mint {
+        recipient: user.to_string(),
+        amount: Uint128::new(500),
+    };
+    router
+        .execute_contract(bridge.clone(), contract.clone(), &msg, &[])
+        .unwrap();
+
+    let burn = ExecuteMsg::BurnFrom {
+        owner: user.to_string(),
+        amount: Uint128::new(200),
+    };
+    let res = router.execute_contract(bridge.clone(), contract.clone(), &burn, &[]);
+    assert_eq!(res.unwrap_err(), ContractError::NoAllowance {}.to_string());
+
+    let msg = ExecuteMsg::IncreaseAllowance {
+        spender: bridge.to_string(),
+        amount: Uint128::new(300),
+        expires: None,
+    };
+    router
+        .execute_contract(user.clone(), contract.clone(), &msg, &[])
+        .unwrap();
+    // Only the minter burns, even with an allowance
+    let msg = ExecuteMsg::IncreaseAllowance {
+        spender: "spender".to_owned(),
+        amount: Uint128::new(300),
+        expires: None,
+    };
+    router
+        .execute_contract(user.clone(), contract.clone(), &msg, &[])
+        .unwrap();
+    let res = router.execute_contract(Addr::unchecked("spender"), contract.clone(), &burn, &[]);
+    assert_eq!(res.unwrap_err(), ContractError::Unauthorized {}.to_string());
+    router
+        .execute_contract(bridge.clone(), contract.clone(), &burn, &[])
+        .unwrap();
+    // Allowance left is 100
+    let res = router.execute_contract(bridge, contract.clone(), &burn, &[]);
+    assert!(res.is_err());
+
+    // Rotating the minter hands over burning
+    let msg = ExecuteMsg::UpdateMinter {
+        minter: "spender".to_owned(),
+    };
+    router
+        .execute_contract(Addr::unchecked("owner"), contract.clone(), &msg, &[])
+        .unwrap();
+    router
+        .execute_contract(Addr::unchecked("spender"), contract.clone(), &burn, &[])
+        .unwrap();
+
+    let msg = ExecuteMsg::Burn {
+        amount: Uint128::new(50),
+    };
+    router
+        .execute_contract(user, contract.clone(), &msg, &[])
+        .unwrap();
+
+    let info: TokenInfoResponse = router
+        .wrap()
+        .query_wasm_smart(contract, &QueryMsg::TokenInfo {})
+        .unwrap();
+    assert_eq!(info.total_supply, Uint128::new(50));
+}
+
+#[test]
+fn test_send_from() {
+    let mut router = mock_app();
+    let id = router.store_code(contract_link_token());
+    let owner = Addr::unchecked("owner");
+    let contract = router
+        .instantiate_contract(
+            id,
+            owner.clone(),
+            &InstantiateMsg::default(),
+            &[],
+            "LINK",
+            None,
+        )
+        .unwrap();
+    let id = router.store_code(contract_receiver_mock());
+    let receiver = router
+        .instantiate_contract(
+            id,
+            owner.clone(),
+            &MockInstantiateMsg {},
+            &[],
+            "Receiver",
+            None,
+        )
+        .unwrap();
+
+    let spender = Addr::unchecked("spender");
+    let msg = ExecuteMsg::IncreaseAllowance {
+        spender: spender.to_string(),
+        amount: Uint128::new(100),
+        expires: None,
+    };
+    router
+        .execute_contract(owner.clone(), contract.clone(), &msg, &[])
+        .unwrap();
+
+    let msg = ExecuteMsg::SendFrom {
+        owner: owner.to_string(),
+        contract: receiver.to_string(),
+        amount: Uint128::new(100),
+        msg: to_binary(&PingMsg {
+            payload: "test_data".to_string(),
+        })
+        .unwrap(),
+    };
+    let res = router
+        .execute_contract(spender, contract.clone(), &msg, &[])
+        .unwrap();
+    let attribute = res.events.last().unwrap().attributes.last().unwrap();
+    assert_eq!(attribute, &attr("action", "pong"));
+
+    let balance: BalanceResponse = router
+        .wrap()
+        .query_wasm_smart(
+            contract,
+            &QueryMsg::Balance {
+                address: receiver.to_string(),
+            },
+        )
+        .unwrap();
+    assert_eq!(balance.balance, Uint128::new(100));
+}
+
+#[test]
+fn test_enumeration() {
+    let mut router = mock_app();
+    let id = router.store_code(contract_link_token());
+    let owner = Addr::unchecked("owner");
+    let contract = router
+        .instantiate_contract(
+            id,
+            owner.clone(),
+            &InstantiateMsg::default(),
+            &[],
+            "LINK",
+            None,
+        )
+        .unwrap();
+
+    for recipient in &["alice", "bob"] {
+        let msg = ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::new(10),
+        };
+        router
+            .execute_contract(owner.clone(), contract.clone(), &msg, &[])
+            .unwrap();
+        let msg = ExecuteMsg::IncreaseAllowance {
+            spender: recipient.to_string(),
+            amount: Uint128::new(5),
+            expires: None,
+        };
+        router
+            .execute_contract(owner.clone(), contract.clone(), &msg, &[])
+            .unwrap();
+    }
+
+    let accounts: AllAccountsResponse = router
+        .wrap()
+        .query_wasm_smart(
+            contract.clone(),
+            &QueryMsg::AllAccounts {
+                start_after: Some("alice".to_owned()),
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(accounts.accounts, vec!["bob", "owner"]);
+
+    let allowances: AllAllowancesResponse = router
+        .wrap()
+        .query_wasm_smart(
+            contract,
+            &QueryMsg::AllAllowances {
+                owner: owner.to_string(),
+                start_after: None,
+                limit: Some(1),
+            },
+        )
+        .unwrap();
+    assert_eq!(allowances.allowances.len(), 1);
+    assert_eq!(allowances.allowances[0].spender, "alice");
+    assert_eq!(allowances.allowances[0].allowance, Uint128::new(5));
+}
+
+#[test]
+fn test_marketing() {
+    let mut router = mock_app();
+    let id = router.store_code(contract_link_token());
+    let owner = Addr::unchecked("owner");
+    let marketing = InstantiateMarketingInfo {
+        project: Some("https://chain.link".to_owned()),
+        description: None,
+        marketing: Some("marketing".to_owned()),
+        logo: Some(Logo::Url("https://example.com/link.svg".to_owned())),
+    };
+    let contract = router
+        .instantiate_contract(
+            id,
+            owner.clone(),
+            &InstantiateMsg {
+                marketing: Some(marketing),
+                ..InstantiateMsg::default()
+            },
+            &[],
+            "LINK",
+            None,
+        )
+        .unwrap();
+
+    let info: MarketingInfoResponse = router
+        .wrap()
+        .query_wasm_smart(contract.clone(), &QueryMsg::MarketingInfo {})
+        .unwrap();
+    assert_eq!(info.project, Some("https://chain.link".to_owned()));
+    assert_eq!(
+        info.logo,
+        Some(LogoInfo::Url("https://example.com/link.svg".to_owned()))
+    );
+
+    let update = ExecuteMsg::UpdateMarketing {
+        project: None,
+        description: Some("Chainlink token".to_owned()),
+        marketing: None,
+    };
+    let res = router.execute_contract(owner, contract.clone(), &update, &[]);
+    assert_eq!(res.unwrap_err(), ContractError::Unauthorized {}.to_string());
+    router
+        .execute_contract(Addr::unchecked("marketing"), contract.clone(), &update, &[])
+        .unwrap();
+
+    let svg = b"<?xml version=\"1.0\"?><svg></svg>".to_vec();
+    let upload = ExecuteMsg::UploadLogo(Logo::Embedded(EmbeddedLogo::Svg(svg.clone().into())));
+    router
+        .execute_contract(Addr::unchecked("marketing"), contract.clone(), &upload, &[])
+        .unwrap();
+
+    let info: MarketingInfoResponse = router
+        .wrap()
+        .query_wasm_smart(contract.clone(), &QueryMsg::MarketingInfo {})
+        .unwrap();
+    assert_eq!(info.description, Some("Chainlink token".to_owned()));
+    assert_eq!(info.logo, Some(LogoInfo::Embedded));
+    let logo: DownloadLogoResponse = router
+        .wrap()
+        .query_wasm_smart(contract, &QueryMsg::DownloadLogo {})
+        .unwrap();
+    assert_eq!(logo.mime_type, "image/svg+xml");
+    assert_eq!(logo.data, Binary::from(svg));
+}
@@ -1,10 +1,40 @@
 use cosmwasm_std::{Binary, Uint128};
-use cw20::Expiration;
+use cw20::{Expiration, Logo};
+use cw20_base::msg::InstantiateMarketingInfo;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// Instantiates a bridged token, whose supply starts at zero. The fixed total supply
+    /// is minted to the sender if not set
+    pub bridged: Option<BridgedMsg>,
+    /// Project URL, description, logo and the account allowed to update them
+    pub marketing: Option<InstantiateMarketingInfo>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct InstantiateMsg {}
+pub struct BridgedMsg {
+    /// Account allowed to `Mint`, usually the bridge
+    pub minter: String,
+    /// Maximum total supply
+    pub cap: Option<Uint128>,
+}
+
+/// Allowance signed off-chain by `owner`, see `ExecuteMsg::Permit`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PermitParams {
+    pub owner: String,
+    pub spender: String,
+    pub amount: Uint128,
+    /// Expiration of the permit and of the allowance it sets
+    pub expires: Expiration,
+    /// The owner's next permit nonce, see `QueryMsg::PermitNonce`
+    pub nonce: u64,
+    pub chain_id: String,
+    /// Address of this contract
+    pub contract: String,
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -40,6 +70,57 @@ pub enum ExecuteMsg {
         amount: Uint128,
         expires: Option<Expiration>,
     },
+    /// Sets the spender's allowance from a permit signed by the owner, so the owner doesn't
+    /// need to send a transaction. `signature` is the 64 byte secp256k1 signature over the
+    /// SHA-256 of the JSON serialized params, `pubkey` the owner's compressed public key
+    Permit {
+        params: PermitParams,
+        pubkey: Binary,
+        signature: Binary,
+    },
+    /// Burn is a base message to destroy tokens forever
+    Burn { amount: Uint128 },
+    /// Destroys tokens forever if `env.sender` has sufficient pre-approval.
+    /// Bridges burn LINK leaving the chain this way, only the minter can burn bridged tokens
+    BurnFrom { owner: String, amount: Uint128 },
+    /// Sends amount tokens from owner -> contract if `env.sender` has sufficient pre-approval.
+    SendFrom {
+        owner: String,
+        contract: String,
+        amount: Uint128,
+        msg: Binary,
+    },
+    /// Creates `amount` new tokens for the recipient. Can be used only by the minter of a
+    /// bridged token, within its cap
+    Mint { recipient: String, amount: Uint128 },
+    /// Replaces the minter of a bridged token, keeping the cap. Can be used only by owner
+    UpdateMinter { minter: String },
+    /// Initiate contract ownership transfer to another address.
+    /// Can be used only by owner
+    TransferOwnership {
+        to: String,
+        /// Block time in seconds after which the transfer can no longer be accepted
+        expires_at: Option<u64>,
+    },
+    /// Finish contract ownership transfer. Can be used only by pending owner
+    AcceptOwnership {},
+    /// Cancel the pending ownership transfer. Can be used only by owner
+    CancelOwnershipTransfer {},
+    /// Leave the contract without owner, which freezes the minter.
+    /// Can be used only by owner
+    RenounceOwnership {},
+    /// If set as the "marketing" role on the contract, updates the marketing metadata.
+    /// Setting a parameter to `""` unsets it, `None` keeps it unchanged
+    UpdateMarketing {
+        /// A URL pointing to the project behind this token
+        project: Option<String>,
+        /// A longer description of the token and it's utility
+        description: Option<String>,
+        /// The address (if any) who can update this data structure
+        marketing: Option<String>,
+    },
+    /// If set as the "marketing" role on the contract, upload a new URL, SVG, or PNG for the token
+    UploadLogo(Logo),
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -54,4 +135,35 @@ pub enum QueryMsg {
     /// Returns how much spender can use from owner account, 0 if unset.
     /// Return type: AllowanceResponse.
     Allowance { owner: String, spender: String },
+    /// Returns the nonce the owner's next permit has to be signed with.
+    /// Return type: u64.
+    PermitNonce { owner: String },
+    /// Returns all allowances this owner has approved, with pagination.
+    /// Return type: AllAllowancesResponse.
+    AllAllowances {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns all accounts that have balances, with pagination.
+    /// Return type: AllAccountsResponse.
+    AllAccounts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Returns the project URL, description, marketing account and logo type.
+    /// Return type: MarketingInfoResponse.
+    MarketingInfo {},
+    /// Returns an embedded logo as mime type and data, failing for logo URLs.
+    /// Return type: DownloadLogoResponse.
+    DownloadLogo {},
+    /// Returns the minter and cap of a bridged token, `None` for a fixed supply.
+    /// Return type: Option<MinterResponse>.
+    Minter {},
+    /// Returns contract owner's address
+    /// Return type: Addr.
+    GetOwner {},
+    /// Returns the owner, the pending owner and the expiry of the pending transfer
+    /// Return type: owned::msg::OwnershipStateResponse.
+    GetOwnershipState {},
 }
@@ -0,0 +1,234 @@
+use bech32::{ToBase32, Variant};
+use cosmwasm_std::{attr, to_vec, Addr, Binary, Deps, DepsMut, Env, Response, StdResult};
+use cw20::AllowanceResponse;
+use cw20_base::state::ALLOWANCES;
+use cw_storage_plus::Map;
+use ripemd160::Ripemd160;
+use sha2::{Digest, Sha256};
+
+use crate::error::ContractError;
+use crate::msg::PermitParams;
+
+/// Next permit nonce of each owner
+pub const PERMIT_NONCES: Map<&Addr, u64> = Map::new("permit_nonces");
+
+/// Sets the allowance signed off-chain by the owner, whose address is derived from `pubkey`
+pub fn execute_permit(
+    deps: DepsMut,
+    env: Env,
+    params: PermitParams,
+    pubkey: Binary,
+    signature: Binary,
+) -> Result<Response, ContractError> {
+    let owner = deps.api.addr_validate(&params.owner)?;
+    let spender = deps.api.addr_validate(&params.spender)?;
+    if spender == owner {
+        return Err(cw20_base::ContractError::CannotSetOwnAccount {}.into());
+    }
+    if params.chain_id != env.block.chain_id || params.contract != env.contract.address.as_str() {
+        return Err(ContractError::InvalidPermit {});
+    }
+    if params.expires.is_expired(&env.block) {
+        return Err(ContractError::PermitExpired {});
+    }
+    let nonce = query_permit_nonce(deps.as_ref(), params.owner.clone())?;
+    if params.nonce != nonce {
+        return Err(ContractError::InvalidNonce {});
+    }
+    if pubkey_address(&pubkey, &params.owner)?.as_deref() != Some(params.owner.as_str()) {
+        return Err(ContractError::InvalidSigner {});
+    }
+    let hash = permit_hash(&params)?;
+    if !matches!(
+        deps.api.secp256k1_verify(&hash, &signature, &pubkey),
+        Ok(true)
+    ) {
+        return Err(ContractError::InvalidSignature {});
+    }
+
+    PERMIT_NONCES.save(deps.storage, &owner, &(nonce + 1))?;
+    let allowance = AllowanceResponse {
+        allowance: params.amount,
+        expires: params.expires,
+    };
+    ALLOWANCES.save(deps.storage, (&owner, &spender), &allowance)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "permit"),
+        attr("owner", owner),
+        attr("spender", spender),
+        attr("amount", params.amount),
+        attr("nonce", nonce.to_string()),
+    ]))
+}
+
+pub fn query_permit_nonce(deps: Deps, owner: String) -> StdResult<u64> {
+    let owner = deps.api.addr_validate(&owner)?;
+    Ok(PERMIT_NONCES
+        .may_load(deps.storage, &owner)?
+        .unwrap_or_default())
+}
+
+/// SHA-256 of the JSON serialized params, which is what the owner signs
+pub fn permit_hash(params: &PermitParams) -> StdResult<[u8; 32]> {
+    Ok(Sha256::digest(&to_vec(params)?).into())
+}
+
+/// Account address of a compressed secp256k1 public key, `bech32(ripemd160(sha256(pubkey)))`,
+/// with the same prefix as `like`. `None` if `like` isn't a bech32 address
+pub fn pubkey_address(pubkey: &[u8], like: &str) -> StdResult<Option<String>> {
+    let prefix = match like.rsplit_once('1') {
+        Some((prefix, _)) if !prefix.is_empty() => prefix,
+        _ => return Ok(None),
+    };
+    let hash = Ripemd160::digest(&Sha256::digest(pubkey));
+    Ok(bech32::encode(prefix, hash.to_base32(), Variant::Bech32).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contract::{execute, instantiate, query};
+    use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{from_binary, Uint128};
+    use cw20::Expiration;
+    use k256::ecdsa::{signature::Signer, Signature, SigningKey};
+
+    fn signing_key() -> SigningKey {
+        SigningKey::from_bytes(&[0x11; 32]).unwrap()
+    }
+
+    fn pubkey(key: &SigningKey) -> Binary {
+        Binary::from(key.verifying_key().to_bytes().to_vec())
+    }
+
+    fn owner(key: &SigningKey) -> String {
+        pubkey_address(&pubkey(key), "terra1").unwrap().unwrap()
+    }
+
+    fn params(owner: &str, nonce: u64) -> PermitParams {
+        let env = mock_env();
+        PermitParams {
+            owner: owner.to_owned(),
+            spender: "aggregator".to_owned(),
+            amount: Uint128::new(500),
+            expires: Expiration::AtHeight(env.block.height + 10),
+            nonce,
+            chain_id: env.block.chain_id,
+            contract: env.contract.address.to_string(),
+        }
+    }
+
+    fn permit(key: &SigningKey, params: PermitParams) -> ExecuteMsg {
+        let signature: Signature = key.sign(&to_vec(&params).unwrap());
+        ExecuteMsg::Permit {
+            params,
+            pubkey: pubkey(key),
+            signature: Binary::from(signature.as_ref()),
+        }
+    }
+
+    fn run(deps: DepsMut, env: Env, msg: ExecuteMsg) -> Result<Response, ContractError> {
+        execute(deps, env, mock_info("relayer", &[]), msg)
+    }
+
+    #[test]
+    fn pubkey_addresses() {
+        let key = signing_key();
+        let address = owner(&key);
+        assert!(address.starts_with("terra1"));
+        assert_eq!(address.len(), 44);
+        let wasm = pubkey_address(&pubkey(&key), "wasm1abc").unwrap().unwrap();
+        // Same data, different prefix and checksum
+        assert_eq!(&wasm[5..wasm.len() - 6], &address[6..address.len() - 6]);
+        assert_eq!(pubkey_address(&pubkey(&key), "owner").unwrap(), None);
+    }
+
+    #[test]
+    fn permit_sets_allowance() {
+        let mut deps = mock_dependencies(&[]);
+        let env = mock_env();
+        instantiate(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("creator", &[]),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        let key = signing_key();
+        let owner = owner(&key);
+
+        run(deps.as_mut(), env.clone(), permit(&key, params(&owner, 0))).unwrap();
+        let allowance = cw20_base::allowances::query_allowance(
+            deps.as_ref(),
+            owner.clone(),
+            "aggregator".to_owned(),
+        )
+        .unwrap();
+        assert_eq!(allowance.allowance, Uint128::new(500));
+        assert_eq!(
+            allowance.expires,
+            Expiration::AtHeight(env.block.height + 10)
+        );
+        let msg = QueryMsg::PermitNonce {
+            owner: owner.clone(),
+        };
+        let nonce: u64 = from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+        assert_eq!(nonce, 1);
+
+        // Replaying the permit fails
+        let err = run(deps.as_mut(), env.clone(), permit(&key, params(&owner, 0))).unwrap_err();
+        assert_eq!(err, ContractError::InvalidNonce {});
+
+        // A later permit replaces the allowance
+        let mut next = params(&owner, 1);
+        next.amount = Uint128::new(100);
+        run(deps.as_mut(), env, permit(&key, next)).unwrap();
+        let allowance =
+            cw20_base::allowances::query_allowance(deps.as_ref(), owner, "aggregator".to_owned())
+                .unwrap();
+        assert_eq!(allowance.allowance, Uint128::new(100));
+    }
+
+    #[test]
+    fn invalid_permits() {
+        let mut deps = mock_dependencies(&[]);
+        let env = mock_env();
+        let key = signing_key();
+        let owner = owner(&key);
+
+        let mut other_chain = params(&owner, 0);
+        other_chain.chain_id = "other-chain".to_owned();
+        let err = run(deps.as_mut(), env.clone(), permit(&key, other_chain)).unwrap_err();
+        assert_eq!(err, ContractError::InvalidPermit {});
+
+        let mut other_contract = params(&owner, 0);
+        other_contract.contract = "other_contract".to_owned();
+        let err = run(deps.as_mut(), env.clone(), permit(&key, other_contract)).unwrap_err();
+        assert_eq!(err, ContractError::InvalidPermit {});
+
+        let mut expired = env.clone();
+        expired.block.height += 10;
+        let err = run(deps.as_mut(), expired, permit(&key, params(&owner, 0))).unwrap_err();
+        assert_eq!(err, ContractError::PermitExpired {});
+
+        // Signed by another key
+        let other_key = SigningKey::from_bytes(&[0x22; 32]).unwrap();
+        let err = run(
+            deps.as_mut(),
+            env.clone(),
+            permit(&other_key, params(&owner, 0)),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidSigner {});
+
+        // Params changed after signing
+        let mut msg = permit(&key, params(&owner, 0));
+        if let ExecuteMsg::Permit { params, .. } = &mut msg {
+            params.amount = Uint128::new(1_000_000);
+        }
+        let err = run(deps.as_mut(), env, msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidSignature {});
+    }
+}
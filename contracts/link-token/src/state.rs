@@ -1,4 +1,5 @@
 use cw20::TokenInfoResponse;
+use cw20_base::state::MinterData;
 use cw_storage_plus::Item;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -12,6 +13,9 @@ pub struct TokenInfo {
     pub symbol: String,
     pub decimals: u8,
     pub total_supply: Uint128,
+    /// Minter of a bridged token, laid out like `cw20_base::state::TokenInfo` so cw20-base
+    /// minting applies
+    pub mint: Option<MinterData>,
 }
 
 impl From<TokenInfo> for TokenInfoResponse {
@@ -0,0 +1,316 @@
+use chainlink_aggregator::{LatestAnswerResponse, QueryMsg::*, RoundDataResponse};
+use cosmwasm_std::{
+    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Storage,
+    Uint128,
+};
+
+use crate::error::ContractError;
+use crate::msg::*;
+use crate::state::*;
+
+use owned::contract::{
+    execute_accept_ownership, execute_cancel_ownership_transfer, execute_renounce_ownership,
+    execute_transfer_ownership, get_owner, get_ownership_state, instantiate as owned_init,
+};
+
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            decimals: msg.decimals,
+            description: msg.description,
+            version: Uint128::zero(),
+        },
+    )?;
+    LATEST_ROUND.save(deps.storage, &0)?;
+    if let Some(answer) = msg.initial_answer {
+        update_answer(deps.storage, &env, answer)?;
+    }
+    owned_init(deps, env, info, owned::msg::InstantiateMsg {})?;
+
+    Ok(Response::default())
+}
+
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::TransferOwnership { to, expires_at } => {
+            execute_transfer_ownership(deps, env, info, to, expires_at).map_err(ContractError::from)
+        }
+        ExecuteMsg::AcceptOwnership {} => {
+            execute_accept_ownership(deps, env, info).map_err(ContractError::from)
+        }
+        ExecuteMsg::CancelOwnershipTransfer {} => {
+            execute_cancel_ownership_transfer(deps, env, info).map_err(ContractError::from)
+        }
+        ExecuteMsg::RenounceOwnership {} => {
+            execute_renounce_ownership(deps, env, info).map_err(ContractError::from)
+        }
+        ExecuteMsg::UpdateAnswer { answer } => {
+            validate_ownership(deps.as_ref(), &info)?;
+            update_answer(deps.storage, &env, answer)?;
+            Ok(Response::default())
+        }
+        ExecuteMsg::UpdateRoundData {
+            round_id,
+            answer,
+            started_at,
+            updated_at,
+            answered_in_round,
+        } => {
+            validate_ownership(deps.as_ref(), &info)?;
+            let round = RoundDataResponse {
+                round_id,
+                answer,
+                started_at,
+                updated_at,
+                answered_in_round: answered_in_round.unwrap_or(round_id),
+            };
+            save_round(deps.storage, round)?;
+            Ok(Response::default())
+        }
+        ExecuteMsg::SetLatestRound { round_id } => {
+            validate_ownership(deps.as_ref(), &info)?;
+            LATEST_ROUND.save(deps.storage, &round_id)?;
+            Ok(Response::default())
+        }
+        ExecuteMsg::SetDecimals { decimals } => {
+            update_config(deps, &info, |config| Config { decimals, ..config })
+        }
+        ExecuteMsg::SetDescription { description } => update_config(deps, &info, |config| Config {
+            description,
+            ..config
+        }),
+        ExecuteMsg::SetVersion { version } => {
+            update_config(deps, &info, |config| Config { version, ..config })
+        }
+    }
+}
+
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::GetOwner {} => to_binary(&get_owner(deps)?),
+        QueryMsg::GetOwnershipState {} => to_binary(&get_ownership_state(deps)?),
+        QueryMsg::AggregatorQuery(GetRoundData { round_id }) => {
+            to_binary(&get_round_data(deps, round_id)?)
+        }
+        QueryMsg::AggregatorQuery(GetLatestRoundData {}) => {
+            to_binary(&get_latest_round_data(deps)?)
+        }
+        QueryMsg::AggregatorQuery(GetDecimals {}) => {
+            to_binary(&CONFIG.load(deps.storage)?.decimals)
+        }
+        QueryMsg::AggregatorQuery(GetDescription {}) => {
+            to_binary(&CONFIG.load(deps.storage)?.description)
+        }
+        QueryMsg::AggregatorQuery(GetVersion {}) => to_binary(&CONFIG.load(deps.storage)?.version),
+        QueryMsg::AggregatorQuery(GetLatestAnswer {}) => {
+            to_binary(&LatestAnswerResponse(get_latest_round_data(deps)?.answer))
+        }
+    }
+}
+
+pub fn get_round_data(deps: Deps, round_id: u64) -> StdResult<RoundDataResponse> {
+    ROUNDS
+        .may_load(deps.storage, round_id.into())?
+        .ok_or_else(|| StdError::generic_err(ContractError::NoData {}.to_string()))
+}
+
+/// Like the flux aggregator, an empty round is returned if the latest round has no data
+pub fn get_latest_round_data(deps: Deps) -> StdResult<RoundDataResponse> {
+    let round_id = LATEST_ROUND.load(deps.storage)?;
+    Ok(ROUNDS
+        .may_load(deps.storage, round_id.into())?
+        .unwrap_or(RoundDataResponse {
+            round_id,
+            answer: None,
+            started_at: None,
+            updated_at: None,
+            answered_in_round: 0,
+        }))
+}
+
+fn update_config(
+    deps: DepsMut,
+    info: &MessageInfo,
+    update: impl FnOnce(Config) -> Config,
+) -> Result<Response, ContractError> {
+    validate_ownership(deps.as_ref(), info)?;
+    CONFIG.update(deps.storage, |config| -> StdResult<_> {
+        Ok(update(config))
+    })?;
+    Ok(Response::default())
+}
+
+fn update_answer(storage: &mut dyn Storage, env: &Env, answer: Uint128) -> StdResult<()> {
+    let round_id = LATEST_ROUND.load(storage)? + 1;
+    let now = env.block.time.seconds();
+    save_round(
+        storage,
+        RoundDataResponse {
+            round_id,
+            answer: Some(answer),
+            started_at: Some(now),
+            updated_at: Some(now),
+            answered_in_round: round_id,
+        },
+    )
+}
+
+fn save_round(storage: &mut dyn Storage, round: RoundDataResponse) -> StdResult<()> {
+    if round.round_id >= LATEST_ROUND.load(storage)? {
+        LATEST_ROUND.save(storage, &round.round_id)?;
+    }
+    ROUNDS.save(storage, round.round_id.into(), &round)
+}
+
+fn validate_ownership(deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
+    let owner = get_owner(deps)?;
+    if info.sender != owner {
+        return Err(ContractError::NotOwner {});
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::from_binary;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+
+    fn latest(deps: Deps) -> RoundDataResponse {
+        let msg = QueryMsg::AggregatorQuery(GetLatestRoundData {});
+        from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn update_answer() {
+        let mut deps = mock_dependencies(&[]);
+        let msg = InstantiateMsg {
+            decimals: 8,
+            description: "LUNA / USD".to_owned(),
+            initial_answer: None,
+        };
+        let info = mock_info("owner", &[]);
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        assert_eq!(latest(deps.as_ref()).round_id, 0);
+        assert_eq!(latest(deps.as_ref()).answer, None);
+
+        let msg = ExecuteMsg::UpdateAnswer {
+            answer: Uint128::new(100),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("stranger", &[]),
+            msg.clone(),
+        );
+        assert!(matches!(err, Err(ContractError::NotOwner {})));
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::UpdateAnswer {
+                answer: Uint128::new(200),
+            },
+        )
+        .unwrap();
+
+        let now = mock_env().block.time.seconds();
+        assert_eq!(
+            latest(deps.as_ref()),
+            RoundDataResponse {
+                round_id: 2,
+                answer: Some(Uint128::new(200)),
+                started_at: Some(now),
+                updated_at: Some(now),
+                answered_in_round: 2,
+            }
+        );
+        let res = get_round_data(deps.as_ref(), 1).unwrap();
+        assert_eq!(res.answer, Some(Uint128::new(100)));
+        assert!(get_round_data(deps.as_ref(), 3).is_err());
+    }
+
+    #[test]
+    fn update_round_data() {
+        let mut deps = mock_dependencies(&[]);
+        let msg = InstantiateMsg {
+            decimals: 8,
+            description: "LUNA / USD".to_owned(),
+            initial_answer: Some(Uint128::new(100)),
+        };
+        let info = mock_info("owner", &[]);
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        assert_eq!(latest(deps.as_ref()).answer, Some(Uint128::new(100)));
+
+        let msg = ExecuteMsg::UpdateRoundData {
+            round_id: 10,
+            answer: Some(Uint128::new(300)),
+            started_at: Some(5),
+            updated_at: Some(6),
+            answered_in_round: Some(9),
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        assert_eq!(
+            latest(deps.as_ref()),
+            RoundDataResponse {
+                round_id: 10,
+                answer: Some(Uint128::new(300)),
+                started_at: Some(5),
+                updated_at: Some(6),
+                answered_in_round: 9,
+            }
+        );
+
+        // earlier rounds don't replace the latest one
+        let msg = ExecuteMsg::UpdateRoundData {
+            round_id: 5,
+            answer: Some(Uint128::new(50)),
+            started_at: None,
+            updated_at: None,
+            answered_in_round: None,
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        assert_eq!(latest(deps.as_ref()).round_id, 10);
+        assert_eq!(
+            get_round_data(deps.as_ref(), 5).unwrap().answered_in_round,
+            5
+        );
+
+        let msg = ExecuteMsg::SetLatestRound { round_id: 5 };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        assert_eq!(latest(deps.as_ref()).answer, Some(Uint128::new(50)));
+
+        for msg in [
+            ExecuteMsg::SetDecimals { decimals: 18 },
+            ExecuteMsg::SetDescription {
+                description: "LUNA / EUR".to_owned(),
+            },
+            ExecuteMsg::SetVersion {
+                version: Uint128::new(3),
+            },
+        ] {
+            execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        }
+        let config = CONFIG.load(&deps.storage).unwrap();
+        assert_eq!(
+            config,
+            Config {
+                decimals: 18,
+                description: "LUNA / EUR".to_owned(),
+                version: Uint128::new(3),
+            }
+        );
+    }
+}
@@ -0,0 +1,16 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+    /// Only callable by owner
+    #[error("Only callable by owner")]
+    NotOwner {},
+    /// No data present for the round
+    #[error("No data present")]
+    NoData {},
+    #[error("{0}")]
+    OwnedError(#[from] owned::error::ContractError),
+}
@@ -0,0 +1,66 @@
+use cosmwasm_std::Uint128;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub decimals: u8,
+    pub description: String,
+    /// Answer of round 1, there are no rounds if omitted
+    pub initial_answer: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Initiate contract ownership transfer to another address.
+    /// Can be used only by owner
+    TransferOwnership {
+        /// Address to transfer ownership to
+        to: String,
+        /// Block time in seconds after which the transfer can no longer be accepted
+        expires_at: Option<u64>,
+    },
+    /// Finish contract ownership transfer. Can be used only by pending owner
+    AcceptOwnership {},
+    /// Cancel the pending ownership transfer. Can be used only by owner
+    CancelOwnershipTransfer {},
+    /// Leave the contract without owner, disabling functionality restricted to the owner.
+    /// Can be used only by owner
+    RenounceOwnership {},
+    /// Starts and answers a new round at the current block time
+    /// Can be used only by owner
+    UpdateAnswer { answer: Uint128 },
+    /// Sets a round's data as is. The round becomes the latest one unless a
+    /// later round exists
+    /// Can be used only by owner
+    UpdateRoundData {
+        round_id: u64,
+        answer: Option<Uint128>,
+        started_at: Option<u64>,
+        updated_at: Option<u64>,
+        /// Defaults to `round_id`
+        answered_in_round: Option<u64>,
+    },
+    /// Sets the round returned as the latest one, whether or not it exists
+    /// Can be used only by owner
+    SetLatestRound { round_id: u64 },
+    /// Can be used only by owner
+    SetDecimals { decimals: u8 },
+    /// Can be used only by owner
+    SetDescription { description: String },
+    /// Can be used only by owner
+    SetVersion { version: Uint128 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Query the contract's owner
+    /// Response: [`cosmwasm_std::Addr`]
+    GetOwner {},
+    /// Returns the owner, the pending owner and the expiry of the pending transfer
+    /// Response: [`owned::msg::OwnershipStateResponse`]
+    GetOwnershipState {},
+    AggregatorQuery(chainlink_aggregator::QueryMsg),
+}
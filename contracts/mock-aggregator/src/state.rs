@@ -0,0 +1,17 @@
+use chainlink_aggregator::RoundDataResponse;
+use cosmwasm_std::Uint128;
+use cw_storage_plus::{Item, Map, U64Key};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub decimals: u8,
+    pub description: String,
+    pub version: Uint128,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+/// Round returned as the latest one
+pub const LATEST_ROUND: Item<u64> = Item::new("latest_round");
+pub const ROUNDS: Map<U64Key, RoundDataResponse> = Map::new("rounds");
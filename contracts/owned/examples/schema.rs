@@ -3,7 +3,9 @@ use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
-use owned::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use owned::msg::{
+    ExecuteMsg, InstantiateMsg, OwnershipStateResponse, QueryMsg, RoleMembersResponse,
+};
 use owned::state::State;
 
 fn main() {
@@ -16,4 +18,6 @@ fn main() {
     export_schema(&schema_for!(ExecuteMsg), &out_dir);
     export_schema(&schema_for!(QueryMsg), &out_dir);
     export_schema(&schema_for!(State), &out_dir);
+    export_schema(&schema_for!(OwnershipStateResponse), &out_dir);
+    export_schema(&schema_for!(RoleMembersResponse), &out_dir);
 }
@@ -1,10 +1,13 @@
 use cosmwasm_std::{
     attr, to_binary, Addr, Attribute, Deps, DepsMut, Env, MessageInfo, QueryResponse, Response,
-    StdResult,
+    StdError, StdResult,
 };
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::msg::{ExecuteMsg, InstantiateMsg, OwnershipStateResponse, QueryMsg};
+use crate::roles::{
+    execute_grant_role, execute_renounce_role, execute_revoke_role, has_role, query_role_members,
+};
 use crate::state::{owner, owner_read, State};
 
 pub fn instantiate(
@@ -14,8 +17,9 @@ pub fn instantiate(
     _msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     let state = State {
-        owner: info.sender,
+        owner: Some(info.sender),
         pending_owner: None,
+        pending_expiry: None,
     };
 
     owner(deps.storage).save(&state)?;
@@ -30,15 +34,46 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::TransferOwnership { to } => execute_transfer_ownership(deps, env, info, to),
+        ExecuteMsg::TransferOwnership { to, expires_at } => {
+            execute_transfer_ownership(deps, env, info, to, expires_at)
+        }
         ExecuteMsg::AcceptOwnership {} => execute_accept_ownership(deps, env, info),
+        ExecuteMsg::CancelOwnershipTransfer {} => {
+            execute_cancel_ownership_transfer(deps, env, info)
+        }
+        ExecuteMsg::RenounceOwnership {} => execute_renounce_ownership(deps, env, info),
+        ExecuteMsg::GrantRole { role, account } => {
+            execute_grant_role(deps, env, info, role, account)
+        }
+        ExecuteMsg::RevokeRole { role, account } => {
+            execute_revoke_role(deps, env, info, role, account)
+        }
+        ExecuteMsg::RenounceRole { role } => execute_renounce_role(deps, env, info, role),
     }
 }
 
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<QueryResponse> {
     match msg {
         QueryMsg::GetOwner {} => to_binary(&get_owner(deps)?),
+        QueryMsg::GetOwnershipState {} => to_binary(&get_ownership_state(deps)?),
+        QueryMsg::HasRole { role, account } => {
+            let account = deps.api.addr_validate(&account)?;
+            to_binary(&has_role(deps, &role, &account)?)
+        }
+        QueryMsg::GetRoleMembers {
+            role,
+            start_after,
+            limit,
+        } => to_binary(&query_role_members(deps, role, start_after, limit)?),
+    }
+}
+
+pub(crate) fn validate_ownership(deps: Deps, info: &MessageInfo) -> Result<State, ContractError> {
+    let state = owner_read(deps.storage).load()?;
+    if state.owner.as_ref() != Some(&info.sender) {
+        return Err(ContractError::OnlyOwner {});
     }
+    Ok(state)
 }
 
 pub fn execute_transfer_ownership(
@@ -46,30 +81,40 @@ pub fn execute_transfer_ownership(
     env: Env,
     info: MessageInfo,
     to: String,
+    expires_at: Option<u64>,
 ) -> Result<Response, ContractError> {
     let to = deps.api.addr_validate(&to)?;
-    let sender = info.sender;
-    let owner = owner_read(deps.storage).load()?.owner;
-    if sender != owner {
-        return Err(ContractError::OnlyOwner {});
+    validate_ownership(deps.as_ref(), &info)?;
+    if matches!(expires_at, Some(expires_at) if expires_at <= env.block.time.seconds()) {
+        return Err(ContractError::InvalidExpiry {});
     }
 
-    let attributes = transfer_ownership(deps, env, to)?;
+    let attributes = transfer_ownership(deps, env, to, expires_at)?;
 
     Ok(Response::new().add_attributes(attributes))
 }
 
-fn transfer_ownership(deps: DepsMut, _env: Env, to: Addr) -> Result<Vec<Attribute>, ContractError> {
+fn transfer_ownership(
+    deps: DepsMut,
+    _env: Env,
+    to: Addr,
+    expires_at: Option<u64>,
+) -> Result<Vec<Attribute>, ContractError> {
     owner(deps.storage).update(|mut state| -> StdResult<_> {
         state.pending_owner = Some(to.clone());
+        state.pending_expiry = expires_at;
 
         Ok(state)
     })?;
 
-    Ok(vec![
+    let mut attributes = vec![
         attr("action", "ownership transferred"),
         attr("pending_owner", to),
-    ])
+    ];
+    if let Some(expires_at) = expires_at {
+        attributes.push(attr("expires_at", expires_at.to_string()));
+    }
+    Ok(attributes)
 }
 
 pub fn execute_accept_ownership(
@@ -77,12 +122,17 @@ pub fn execute_accept_ownership(
     env: Env,
     info: MessageInfo,
 ) -> Result<Response, ContractError> {
-    let sender = info.clone().sender;
-    let pending_owner = owner_read(deps.storage).load()?.pending_owner;
+    let state = owner_read(deps.storage).load()?;
+    let pending_owner = state
+        .pending_owner
+        .ok_or(ContractError::NoPendingOwner {})?;
 
-    if sender != pending_owner.unwrap() {
+    if info.sender != pending_owner {
         return Err(ContractError::MustBeProposed {});
     }
+    if matches!(state.pending_expiry, Some(expires_at) if env.block.time.seconds() >= expires_at) {
+        return Err(ContractError::OwnershipTransferExpired {});
+    }
 
     let logs = accept_ownership(deps, env, info)?;
 
@@ -93,8 +143,9 @@ fn accept_ownership(deps: DepsMut, _env: Env, info: MessageInfo) -> StdResult<Ve
     let sender = info.sender;
 
     owner(deps.storage).update(|mut state| -> StdResult<_> {
-        state.owner = sender.clone();
+        state.owner = Some(sender.clone());
         state.pending_owner = None;
+        state.pending_expiry = None;
 
         Ok(state)
     })?;
@@ -105,10 +156,61 @@ fn accept_ownership(deps: DepsMut, _env: Env, info: MessageInfo) -> StdResult<Ve
     ])
 }
 
+pub fn execute_cancel_ownership_transfer(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let mut state = validate_ownership(deps.as_ref(), &info)?;
+    let pending_owner = state
+        .pending_owner
+        .take()
+        .ok_or(ContractError::NoPendingOwner {})?;
+    state.pending_expiry = None;
+    owner(deps.storage).save(&state)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "ownership transfer cancelled"),
+        attr("pending_owner", pending_owner),
+    ]))
+}
+
+/// Leaves the contract without an owner, also cancelling any pending transfer.
+/// Owner-only functionality of the contract becomes permanently unavailable
+pub fn execute_renounce_ownership(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    validate_ownership(deps.as_ref(), &info)?;
+    owner(deps.storage).save(&State {
+        owner: None,
+        pending_owner: None,
+        pending_expiry: None,
+    })?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "ownership renounced"),
+        attr("previous_owner", info.sender),
+    ]))
+}
+
+/// Fails once ownership is renounced
 pub fn get_owner(deps: Deps) -> StdResult<Addr> {
-    let owner = owner_read(deps.storage).load()?.owner;
+    owner_read(deps.storage)
+        .load()?
+        .owner
+        .ok_or_else(|| StdError::generic_err("Ownership renounced"))
+}
+
+pub fn get_ownership_state(deps: Deps) -> StdResult<OwnershipStateResponse> {
+    let state = owner_read(deps.storage).load()?;
 
-    Ok(owner)
+    Ok(OwnershipStateResponse {
+        owner: state.owner,
+        pending_owner: state.pending_owner,
+        pending_expiry: state.pending_expiry,
+    })
 }
 
 #[cfg(test)]
@@ -151,6 +253,7 @@ mod tests {
             mock_env(),
             info,
             MOCK_CONTRACT_ADDR.to_owned(),
+            None,
         )
         .unwrap();
         assert_eq!(0, res.messages.len());
@@ -180,6 +283,7 @@ mod tests {
             mock_env(),
             info,
             MOCK_CONTRACT_ADDR.to_owned(),
+            None,
         )
         .unwrap();
         assert_eq!(0, res.messages.len());
@@ -196,9 +300,137 @@ mod tests {
         let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
         assert_eq!(0, res.messages.len());
 
-        let res = owner_read(deps.as_ref().storage).load().unwrap().owner;
+        let res = get_owner(deps.as_ref()).unwrap();
         assert_eq!(MOCK_CONTRACT_ADDR, String::from(res));
         let new_pending_owner = owner_read(&deps.storage).load().unwrap().pending_owner;
         assert_eq!(true, new_pending_owner.is_none());
     }
+
+    #[test]
+    fn accept_without_pending_transfer() {
+        let mut deps = mock_dependencies(&[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            InstantiateMsg {},
+        )
+        .unwrap();
+
+        let msg = ExecuteMsg::AcceptOwnership {};
+        let err = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::NoPendingOwner {});
+    }
+
+    #[test]
+    fn cancel_ownership_transfer() {
+        let mut deps = mock_dependencies(&[]);
+        let info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), mock_env(), info.clone(), InstantiateMsg {}).unwrap();
+
+        let msg = ExecuteMsg::CancelOwnershipTransfer {};
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap_err();
+        assert_eq!(err, ContractError::NoPendingOwner {});
+
+        let transfer = ExecuteMsg::TransferOwnership {
+            to: "new_owner".to_owned(),
+            expires_at: None,
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), transfer).unwrap();
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("new_owner", &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::OnlyOwner {});
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let msg = ExecuteMsg::AcceptOwnership {};
+        let err = execute(deps.as_mut(), mock_env(), mock_info("new_owner", &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::NoPendingOwner {});
+        assert_eq!(get_owner(deps.as_ref()).unwrap(), "creator");
+    }
+
+    #[test]
+    fn ownership_transfer_expiry() {
+        let mut deps = mock_dependencies(&[]);
+        let info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), mock_env(), info.clone(), InstantiateMsg {}).unwrap();
+        let now = mock_env().block.time.seconds();
+
+        let msg = ExecuteMsg::TransferOwnership {
+            to: "new_owner".to_owned(),
+            expires_at: Some(now),
+        };
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidExpiry {});
+
+        let msg = ExecuteMsg::TransferOwnership {
+            to: "new_owner".to_owned(),
+            expires_at: Some(now + 100),
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(
+            get_ownership_state(deps.as_ref()).unwrap(),
+            OwnershipStateResponse {
+                owner: Some(Addr::unchecked("creator")),
+                pending_owner: Some(Addr::unchecked("new_owner")),
+                pending_expiry: Some(now + 100),
+            }
+        );
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(100);
+        let msg = ExecuteMsg::AcceptOwnership {};
+        let err =
+            execute(deps.as_mut(), env, mock_info("new_owner", &[]), msg.clone()).unwrap_err();
+        assert_eq!(err, ContractError::OwnershipTransferExpired {});
+
+        execute(deps.as_mut(), mock_env(), mock_info("new_owner", &[]), msg).unwrap();
+        assert_eq!(
+            get_ownership_state(deps.as_ref()).unwrap(),
+            OwnershipStateResponse {
+                owner: Some(Addr::unchecked("new_owner")),
+                pending_owner: None,
+                pending_expiry: None,
+            }
+        );
+    }
+
+    #[test]
+    fn renounce_ownership() {
+        let mut deps = mock_dependencies(&[]);
+        let info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), mock_env(), info.clone(), InstantiateMsg {}).unwrap();
+        let transfer = ExecuteMsg::TransferOwnership {
+            to: "new_owner".to_owned(),
+            expires_at: None,
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), transfer.clone()).unwrap();
+
+        let msg = ExecuteMsg::RenounceOwnership {};
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("new_owner", &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::OnlyOwner {});
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        assert_eq!(
+            get_ownership_state(deps.as_ref()).unwrap(),
+            OwnershipStateResponse {
+                owner: None,
+                pending_owner: None,
+                pending_expiry: None,
+            }
+        );
+        assert!(get_owner(deps.as_ref()).is_err());
+        let err = execute(deps.as_mut(), mock_env(), info, transfer).unwrap_err();
+        assert_eq!(err, ContractError::OnlyOwner {});
+    }
 }
@@ -15,4 +15,19 @@ pub enum ContractError {
 
     #[error("Only callable by owner")]
     OnlyOwner {},
+
+    #[error("No pending ownership transfer")]
+    NoPendingOwner {},
+
+    #[error("Ownership transfer expired")]
+    OwnershipTransferExpired {},
+
+    #[error("Expiry must be in the future")]
+    InvalidExpiry {},
+
+    #[error("Missing role {role}")]
+    MissingRole { role: String },
+
+    #[error("Role not granted to the account")]
+    RoleNotGranted {},
 }
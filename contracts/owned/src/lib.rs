@@ -1,6 +1,7 @@
 pub mod contract;
 pub mod error;
 pub mod msg;
+pub mod roles;
 pub mod state;
 
 // TODO! convert owned to package
@@ -1,3 +1,4 @@
+use cosmwasm_std::Addr;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -7,12 +8,55 @@ pub struct InstantiateMsg {}
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    TransferOwnership { to: String },
+    TransferOwnership {
+        to: String,
+        /// Block time in seconds after which the transfer can no longer be accepted
+        expires_at: Option<u64>,
+    },
     AcceptOwnership {},
+    CancelOwnershipTransfer {},
+    RenounceOwnership {},
+    GrantRole {
+        role: String,
+        account: String,
+    },
+    RevokeRole {
+        role: String,
+        account: String,
+    },
+    /// Gives up a role held by the sender
+    RenounceRole {
+        role: String,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     GetOwner {},
+    GetOwnershipState {},
+    /// Response: `bool`, true for the owner
+    HasRole {
+        role: String,
+        account: String,
+    },
+    /// Accounts granted the role, not including the owner
+    GetRoleMembers {
+        role: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OwnershipStateResponse {
+    /// `None` once ownership is renounced
+    pub owner: Option<Addr>,
+    pub pending_owner: Option<Addr>,
+    pub pending_expiry: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoleMembersResponse {
+    pub members: Vec<Addr>,
 }
@@ -0,0 +1,240 @@
+use cosmwasm_std::{
+    attr, Addr, Deps, DepsMut, Empty, Env, MessageInfo, Order, Response, StdResult,
+};
+use cw_storage_plus::Bound;
+
+use crate::contract::validate_ownership;
+use crate::error::ContractError;
+use crate::msg::RoleMembersResponse;
+use crate::state::{owner_read, ROLE_MEMBERS};
+
+/// May change the configuration of the contract
+pub const CONFIG_ADMIN: &str = "config_admin";
+/// May add and remove oracles
+pub const ORACLE_MANAGER: &str = "oracle_manager";
+/// May lower raised flags
+pub const FLAG_LOWERER: &str = "flag_lowerer";
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+/// Whether the account holds the role. The owner implicitly holds every role
+pub fn has_role(deps: Deps, role: &str, account: &Addr) -> StdResult<bool> {
+    if owner_read(deps.storage).load()?.owner.as_ref() == Some(account) {
+        return Ok(true);
+    }
+    Ok(ROLE_MEMBERS.has(deps.storage, (role, account)))
+}
+
+/// Fails with [`ContractError::MissingRole`] unless the sender holds the role
+pub fn validate_role(deps: Deps, info: &MessageInfo, role: &str) -> Result<(), ContractError> {
+    if !has_role(deps, role, &info.sender)? {
+        return Err(ContractError::MissingRole {
+            role: role.to_owned(),
+        });
+    }
+    Ok(())
+}
+
+pub fn execute_grant_role(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    role: String,
+    account: String,
+) -> Result<Response, ContractError> {
+    let account = deps.api.addr_validate(&account)?;
+    validate_ownership(deps.as_ref(), &info)?;
+    ROLE_MEMBERS.save(deps.storage, (&role, &account), &Empty {})?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "role granted"),
+        attr("role", role),
+        attr("account", account),
+    ]))
+}
+
+pub fn execute_revoke_role(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    role: String,
+    account: String,
+) -> Result<Response, ContractError> {
+    let account = deps.api.addr_validate(&account)?;
+    validate_ownership(deps.as_ref(), &info)?;
+    remove_member(deps, &role, &account)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "role revoked"),
+        attr("role", role),
+        attr("account", account),
+    ]))
+}
+
+pub fn execute_renounce_role(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    role: String,
+) -> Result<Response, ContractError> {
+    remove_member(deps, &role, &info.sender)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "role renounced"),
+        attr("role", role),
+        attr("account", info.sender),
+    ]))
+}
+
+fn remove_member(deps: DepsMut, role: &str, account: &Addr) -> Result<(), ContractError> {
+    if !ROLE_MEMBERS.has(deps.storage, (role, account)) {
+        return Err(ContractError::RoleNotGranted {});
+    }
+    ROLE_MEMBERS.remove(deps.storage, (role, account));
+    Ok(())
+}
+
+/// Accounts granted the role, not including the owner
+pub fn query_role_members(
+    deps: Deps,
+    role: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<RoleMembersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let members = ROLE_MEMBERS
+        .prefix(&role)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|key| Ok(Addr::unchecked(String::from_utf8(key)?)))
+        .collect::<StdResult<_>>()?;
+
+    Ok(RoleMembersResponse { members })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contract::{execute, instantiate, query};
+    use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+    use cosmwasm_std::from_binary;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+
+    fn grant(
+        deps: DepsMut,
+        sender: &str,
+        role: &str,
+        account: &str,
+    ) -> Result<Response, ContractError> {
+        let msg = ExecuteMsg::GrantRole {
+            role: role.to_owned(),
+            account: account.to_owned(),
+        };
+        execute(deps, mock_env(), mock_info(sender, &[]), msg)
+    }
+
+    fn members(deps: Deps, role: &str, start_after: Option<&str>) -> Vec<Addr> {
+        let msg = QueryMsg::GetRoleMembers {
+            role: role.to_owned(),
+            start_after: start_after.map(str::to_owned),
+            limit: Some(2),
+        };
+        let res: RoleMembersResponse = from_binary(&query(deps, mock_env(), msg).unwrap()).unwrap();
+        res.members
+    }
+
+    #[test]
+    fn grant_and_revoke_roles() {
+        let mut deps = mock_dependencies(&[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {},
+        )
+        .unwrap();
+
+        let err = grant(deps.as_mut(), "admin", CONFIG_ADMIN, "admin").unwrap_err();
+        assert_eq!(err, ContractError::OnlyOwner {});
+
+        grant(deps.as_mut(), "owner", CONFIG_ADMIN, "admin").unwrap();
+        grant(deps.as_mut(), "owner", CONFIG_ADMIN, "admin2").unwrap();
+        grant(deps.as_mut(), "owner", CONFIG_ADMIN, "admin3").unwrap();
+        grant(deps.as_mut(), "owner", ORACLE_MANAGER, "manager").unwrap();
+
+        let admin = Addr::unchecked("admin");
+        assert!(has_role(deps.as_ref(), CONFIG_ADMIN, &admin).unwrap());
+        assert!(!has_role(deps.as_ref(), ORACLE_MANAGER, &admin).unwrap());
+        assert!(has_role(deps.as_ref(), ORACLE_MANAGER, &Addr::unchecked("owner")).unwrap());
+        let msg = QueryMsg::HasRole {
+            role: ORACLE_MANAGER.to_owned(),
+            account: "manager".to_owned(),
+        };
+        let res: bool = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        assert!(res);
+
+        assert_eq!(
+            members(deps.as_ref(), CONFIG_ADMIN, None),
+            vec![admin.clone(), Addr::unchecked("admin2")]
+        );
+        assert_eq!(
+            members(deps.as_ref(), CONFIG_ADMIN, Some("admin2")),
+            vec![Addr::unchecked("admin3")]
+        );
+
+        let revoke = ExecuteMsg::RevokeRole {
+            role: CONFIG_ADMIN.to_owned(),
+            account: "admin".to_owned(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin2", &[]),
+            revoke.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::OnlyOwner {});
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            revoke.clone(),
+        )
+        .unwrap();
+        assert!(!has_role(deps.as_ref(), CONFIG_ADMIN, &admin).unwrap());
+        let err = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), revoke).unwrap_err();
+        assert_eq!(err, ContractError::RoleNotGranted {});
+
+        let renounce = ExecuteMsg::RenounceRole {
+            role: ORACLE_MANAGER.to_owned(),
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("manager", &[]),
+            renounce.clone(),
+        )
+        .unwrap();
+        assert!(members(deps.as_ref(), ORACLE_MANAGER, None).is_empty());
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("manager", &[]),
+            renounce,
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::RoleNotGranted {});
+
+        let err =
+            validate_role(deps.as_ref(), &mock_info("manager", &[]), ORACLE_MANAGER).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::MissingRole {
+                role: ORACLE_MANAGER.to_owned()
+            }
+        );
+    }
+}
@@ -1,15 +1,20 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Storage};
+use cosmwasm_std::{Addr, Empty, Storage};
 use cosmwasm_storage::{singleton, singleton_read, ReadonlySingleton, Singleton};
+use cw_storage_plus::Map;
 
 pub static OWNER_KEY: &[u8] = b"owner";
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct State {
-    pub owner: Addr,
+    /// `None` once ownership is renounced
+    pub owner: Option<Addr>,
     pub pending_owner: Option<Addr>,
+    /// Block time in seconds after which the pending owner can no longer accept
+    #[serde(default)]
+    pub pending_expiry: Option<u64>,
 }
 
 pub fn owner(storage: &mut dyn Storage) -> Singleton<State> {
@@ -19,3 +24,6 @@ pub fn owner(storage: &mut dyn Storage) -> Singleton<State> {
 pub fn owner_read(storage: &dyn Storage) -> ReadonlySingleton<State> {
     singleton_read(storage, OWNER_KEY)
 }
+
+/// Accounts granted each role, keyed by role and account
+pub const ROLE_MEMBERS: Map<(&str, &Addr), Empty> = Map::new("role_members");
@@ -0,0 +1,547 @@
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, Event, MessageInfo, Order,
+    Response, StdResult, Storage, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use owned::contract::{
+    execute_accept_ownership, execute_cancel_ownership_transfer, execute_renounce_ownership,
+    execute_transfer_ownership, get_owner, get_ownership_state, instantiate as owned_init,
+};
+use owned::roles::{
+    execute_grant_role, execute_renounce_role, execute_revoke_role, has_role, query_role_members,
+    validate_role, CONFIG_ADMIN, ORACLE_MANAGER,
+};
+
+use crate::error::ContractError;
+use crate::msg::*;
+use crate::state::*;
+
+pub fn instantiate(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let link = deps.api.addr_validate(&msg.link)?;
+    owned_init(deps.branch(), env, info, owned::msg::InstantiateMsg {})?;
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            link,
+            unbonding_period: msg.unbonding_period,
+        },
+    )?;
+    SLASHED.save(deps.storage, &Uint128::zero())?;
+
+    Ok(Response::default())
+}
+
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::SetAdmin { oracle, admin } => execute_set_admin(deps, env, info, oracle, admin),
+        ExecuteMsg::TransferAdmin { oracle, new_admin } => {
+            execute_transfer_admin(deps, env, info, oracle, new_admin)
+        }
+        ExecuteMsg::AcceptAdmin { oracle } => execute_accept_admin(deps, env, info, oracle),
+        ExecuteMsg::Receive(receive_msg) => execute_receive(deps, env, info, receive_msg),
+        ExecuteMsg::Unbond { oracle, amount } => execute_unbond(deps, env, info, oracle, amount),
+        ExecuteMsg::Withdraw { oracle } => execute_withdraw(deps, env, info, oracle),
+        ExecuteMsg::Slash {
+            oracle,
+            amount,
+            reason,
+        } => execute_slash(deps, env, info, oracle, amount, reason),
+        ExecuteMsg::Lock { oracle, amount } => execute_lock(deps, env, info, oracle, amount),
+        ExecuteMsg::SetSlasher {
+            slasher,
+            max_amount,
+        } => execute_set_slasher(deps, env, info, slasher, max_amount),
+        ExecuteMsg::SetSlashableOracles {
+            slasher,
+            added,
+            removed,
+        } => execute_set_slashable_oracles(deps, env, info, slasher, added, removed),
+        ExecuteMsg::WithdrawSlashed { recipient, amount } => {
+            execute_withdraw_slashed(deps, env, info, recipient, amount)
+        }
+        ExecuteMsg::SetUnbondingPeriod { unbonding_period } => {
+            execute_set_unbonding_period(deps, env, info, unbonding_period)
+        }
+        ExecuteMsg::TransferOwnership { to, expires_at } => {
+            execute_transfer_ownership(deps, env, info, to, expires_at).map_err(ContractError::from)
+        }
+        ExecuteMsg::AcceptOwnership {} => {
+            execute_accept_ownership(deps, env, info).map_err(ContractError::from)
+        }
+        ExecuteMsg::CancelOwnershipTransfer {} => {
+            execute_cancel_ownership_transfer(deps, env, info).map_err(ContractError::from)
+        }
+        ExecuteMsg::RenounceOwnership {} => {
+            execute_renounce_ownership(deps, env, info).map_err(ContractError::from)
+        }
+        ExecuteMsg::GrantRole { role, account } => {
+            execute_grant_role(deps, env, info, role, account).map_err(ContractError::from)
+        }
+        ExecuteMsg::RevokeRole { role, account } => {
+            execute_revoke_role(deps, env, info, role, account).map_err(ContractError::from)
+        }
+        ExecuteMsg::RenounceRole { role } => {
+            execute_renounce_role(deps, env, info, role).map_err(ContractError::from)
+        }
+    }
+}
+
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::GetConfig {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::GetStake { oracle } => to_binary(&query_stake(deps, oracle)?),
+        QueryMsg::GetSlashed {} => to_binary(&SLASHED.load(deps.storage)?),
+        QueryMsg::GetSlasher { slasher } => {
+            to_binary(&SLASHERS.may_load(deps.storage, &deps.api.addr_validate(&slasher)?)?)
+        }
+        QueryMsg::IsSlashable { slasher, oracle } => to_binary(&SLASHABLE.has(
+            deps.storage,
+            (
+                &deps.api.addr_validate(&slasher)?,
+                &deps.api.addr_validate(&oracle)?,
+            ),
+        )),
+        QueryMsg::GetOwner {} => to_binary(&get_owner(deps)?),
+        QueryMsg::GetOwnershipState {} => to_binary(&get_ownership_state(deps)?),
+        QueryMsg::HasRole { role, account } => {
+            to_binary(&has_role(deps, &role, &deps.api.addr_validate(&account)?)?)
+        }
+        QueryMsg::GetRoleMembers {
+            role,
+            start_after,
+            limit,
+        } => to_binary(&query_role_members(deps, role, start_after, limit)?),
+    }
+}
+
+pub fn execute_set_admin(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    oracle: String,
+    admin: String,
+) -> Result<Response, ContractError> {
+    validate_role(deps.as_ref(), &info, ORACLE_MANAGER)?;
+    let oracle = deps.api.addr_validate(&oracle)?;
+    let admin = deps.api.addr_validate(&admin)?;
+
+    // funded stakes change hands only through their admin's transfer
+    if let Some(stake) = STAKES.may_load(deps.storage, &oracle)? {
+        if !stake.bonded.is_zero() || !stake.unbonding.is_empty() {
+            return Err(ContractError::StakeNotEmpty {});
+        }
+    }
+    let stake = Stake {
+        admin: admin.clone(),
+        pending_admin: None,
+        bonded: Uint128::zero(),
+        unbonding: vec![],
+    };
+    STAKES.save(deps.storage, &oracle, &stake)?;
+
+    Ok(Response::new().add_event(
+        Event::new("admin_updated")
+            .add_attribute("oracle", oracle)
+            .add_attribute("admin", admin),
+    ))
+}
+
+pub fn execute_transfer_admin(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    oracle: String,
+    new_admin: String,
+) -> Result<Response, ContractError> {
+    let oracle = deps.api.addr_validate(&oracle)?;
+    let new_admin = deps.api.addr_validate(&new_admin)?;
+    let mut stake = load_as_admin(deps.as_ref(), &info, &oracle)?;
+    stake.pending_admin = Some(new_admin.clone());
+    STAKES.save(deps.storage, &oracle, &stake)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "transfer_admin")
+        .add_attribute("oracle", oracle)
+        .add_attribute("new_admin", new_admin))
+}
+
+pub fn execute_accept_admin(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    oracle: String,
+) -> Result<Response, ContractError> {
+    let oracle = deps.api.addr_validate(&oracle)?;
+    let mut stake = STAKES
+        .may_load(deps.storage, &oracle)?
+        .ok_or(ContractError::PendingAdminMissing {})?;
+    match stake.pending_admin.take() {
+        Some(pending_admin) if pending_admin == info.sender => stake.admin = pending_admin,
+        Some(_) => return Err(ContractError::NotPendingAdmin {}),
+        None => return Err(ContractError::PendingAdminMissing {}),
+    }
+    STAKES.save(deps.storage, &oracle, &stake)?;
+
+    Ok(Response::new().add_event(
+        Event::new("admin_updated")
+            .add_attribute("oracle", oracle)
+            .add_attribute("admin", info.sender),
+    ))
+}
+
+pub fn execute_receive(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    receive_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    if info.sender != CONFIG.load(deps.storage)?.link {
+        return Err(ContractError::InvalidToken {});
+    }
+    if receive_msg.amount.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+    let ReceiveMsg::Stake { oracle } = from_binary(&receive_msg.msg)?;
+    let oracle = deps.api.addr_validate(&oracle)?;
+    let staker = deps.api.addr_validate(&receive_msg.sender)?;
+
+    let mut stake = match STAKES.may_load(deps.storage, &oracle)? {
+        Some(stake) if stake.admin == staker => stake,
+        _ => return Err(ContractError::NotAdmin {}),
+    };
+    stake.bonded += receive_msg.amount;
+    STAKES.save(deps.storage, &oracle, &stake)?;
+
+    Ok(Response::new().add_event(
+        Event::new("staked")
+            .add_attribute("oracle", oracle)
+            .add_attribute("admin", staker)
+            .add_attribute("amount", receive_msg.amount)
+            .add_attribute("bonded", stake.bonded),
+    ))
+}
+
+pub fn execute_unbond(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    oracle: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let oracle = deps.api.addr_validate(&oracle)?;
+    let mut stake = load_as_admin(deps.as_ref(), &info, &oracle)?;
+    if amount.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+    stake.bonded = stake
+        .bonded
+        .checked_sub(amount)
+        .map_err(|_| ContractError::InsufficientStake {})?;
+    if stake.bonded < locked(deps.storage, &oracle)? {
+        return Err(ContractError::StakeLocked {});
+    }
+
+    let release_at = env
+        .block
+        .time
+        .seconds()
+        .checked_add(CONFIG.load(deps.storage)?.unbonding_period)
+        .ok_or(ContractError::UnbondingPeriodTooLong {})?;
+    stake.unbonding.push(Unbonding { amount, release_at });
+    STAKES.save(deps.storage, &oracle, &stake)?;
+
+    Ok(Response::new().add_event(
+        Event::new("unbonding_started")
+            .add_attribute("oracle", oracle)
+            .add_attribute("amount", amount)
+            .add_attribute("release_at", release_at.to_string()),
+    ))
+}
+
+pub fn execute_withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    oracle: String,
+) -> Result<Response, ContractError> {
+    let oracle = deps.api.addr_validate(&oracle)?;
+    let mut stake = load_as_admin(deps.as_ref(), &info, &oracle)?;
+
+    let now = env.block.time.seconds();
+    let (released, unbonding): (Vec<_>, Vec<_>) = stake
+        .unbonding
+        .into_iter()
+        .partition(|unbonding| unbonding.release_at <= now);
+    let amount: Uint128 = released.iter().map(|unbonding| unbonding.amount).sum();
+    if amount.is_zero() {
+        return Err(ContractError::NothingToWithdraw {});
+    }
+    stake.unbonding = unbonding;
+    STAKES.save(deps.storage, &oracle, &stake)?;
+
+    let link = CONFIG.load(deps.storage)?.link;
+    Ok(Response::new()
+        .add_message(transfer(link, &stake.admin, amount)?)
+        .add_event(
+            Event::new("withdrawn")
+                .add_attribute("oracle", oracle)
+                .add_attribute("recipient", stake.admin)
+                .add_attribute("amount", amount),
+        ))
+}
+
+pub fn execute_slash(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    oracle: String,
+    amount: Uint128,
+    reason: String,
+) -> Result<Response, ContractError> {
+    let max_amount = SLASHERS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::NotSlasher {})?;
+    let oracle = deps.api.addr_validate(&oracle)?;
+    if !SLASHABLE.has(deps.storage, (&info.sender, &oracle)) {
+        return Err(ContractError::NotSlashable {});
+    }
+    let amount = amount.min(max_amount);
+
+    // slashing never fails for lack of stake, so hooks can't block their caller
+    let mut slashed = Uint128::zero();
+    if let Some(mut stake) = STAKES.may_load(deps.storage, &oracle)? {
+        let mut take = |available: &mut Uint128| {
+            let taken = (amount - slashed).min(*available);
+            *available -= taken;
+            slashed += taken;
+        };
+        take(&mut stake.bonded);
+        // the unbondings furthest from release first
+        for unbonding in stake.unbonding.iter_mut().rev() {
+            take(&mut unbonding.amount);
+        }
+        stake
+            .unbonding
+            .retain(|unbonding| !unbonding.amount.is_zero());
+        STAKES.save(deps.storage, &oracle, &stake)?;
+    }
+    SLASHED.update(deps.storage, |total| -> StdResult<_> {
+        Ok(total + slashed)
+    })?;
+
+    Ok(Response::new().add_event(
+        Event::new("slashed")
+            .add_attribute("oracle", oracle)
+            .add_attribute("amount", slashed)
+            .add_attribute("reason", reason)
+            .add_attribute("slasher", info.sender),
+    ))
+}
+
+pub fn execute_lock(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    oracle: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let oracle = deps.api.addr_validate(&oracle)?;
+
+    // releasing a lock needs no permission, it only ever removes the sender's own
+    if amount.is_zero() {
+        LOCKS.remove(deps.storage, (&oracle, &info.sender));
+    } else {
+        if !SLASHERS.has(deps.storage, &info.sender) {
+            return Err(ContractError::NotSlasher {});
+        }
+        if !SLASHABLE.has(deps.storage, (&info.sender, &oracle)) {
+            return Err(ContractError::NotSlashable {});
+        }
+        LOCKS.save(deps.storage, (&oracle, &info.sender), &amount)?;
+    }
+
+    Ok(Response::new().add_event(
+        Event::new("locked")
+            .add_attribute("oracle", oracle)
+            .add_attribute("feed", info.sender)
+            .add_attribute("amount", amount),
+    ))
+}
+
+pub fn execute_set_slasher(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    slasher: String,
+    max_amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    validate_role(deps.as_ref(), &info, CONFIG_ADMIN)?;
+    let slasher = deps.api.addr_validate(&slasher)?;
+
+    let mut event = Event::new("slasher_updated").add_attribute("slasher", &slasher);
+    match max_amount {
+        Some(max_amount) => {
+            SLASHERS.save(deps.storage, &slasher, &max_amount)?;
+            event = event.add_attribute("max_amount", max_amount);
+        }
+        None => {
+            // removed slashers no longer lock their oracles
+            let oracles = SLASHABLE
+                .prefix(&slasher)
+                .keys(deps.storage, None, None, Order::Ascending)
+                .map(|key| Ok(Addr::unchecked(String::from_utf8(key)?)))
+                .collect::<StdResult<Vec<Addr>>>()?;
+            for oracle in oracles.iter() {
+                remove_slashable(deps.storage, &slasher, oracle);
+            }
+            SLASHERS.remove(deps.storage, &slasher);
+        }
+    }
+
+    Ok(Response::new().add_event(event))
+}
+
+pub fn execute_set_slashable_oracles(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    slasher: String,
+    added: Vec<String>,
+    removed: Vec<String>,
+) -> Result<Response, ContractError> {
+    validate_role(deps.as_ref(), &info, ORACLE_MANAGER)?;
+    let slasher = deps.api.addr_validate(&slasher)?;
+    if !SLASHERS.has(deps.storage, &slasher) {
+        return Err(ContractError::NotSlasher {});
+    }
+
+    for oracle in removed.iter() {
+        let oracle = deps.api.addr_validate(oracle)?;
+        remove_slashable(deps.storage, &slasher, &oracle);
+    }
+    for oracle in added.iter() {
+        let oracle = deps.api.addr_validate(oracle)?;
+        SLASHABLE.save(deps.storage, (&slasher, &oracle), &Empty {})?;
+    }
+
+    Ok(Response::new().add_event(
+        Event::new("slashable_oracles_updated")
+            .add_attribute("slasher", slasher)
+            .add_attribute("added", format!("{:?}", added))
+            .add_attribute("removed", format!("{:?}", removed)),
+    ))
+}
+
+pub fn execute_withdraw_slashed(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    recipient: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    validate_ownership(deps.as_ref(), &info)?;
+    let recipient = deps.api.addr_validate(&recipient)?;
+    SLASHED.update(deps.storage, |total| {
+        total
+            .checked_sub(amount)
+            .map_err(|_| ContractError::InsufficientSlashed {})
+    })?;
+
+    let link = CONFIG.load(deps.storage)?.link;
+    Ok(Response::new()
+        .add_message(transfer(link, &recipient, amount)?)
+        .add_attribute("action", "withdraw_slashed")
+        .add_attribute("recipient", recipient)
+        .add_attribute("amount", amount))
+}
+
+pub fn execute_set_unbonding_period(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    unbonding_period: u64,
+) -> Result<Response, ContractError> {
+    validate_role(deps.as_ref(), &info, CONFIG_ADMIN)?;
+
+    CONFIG.update(deps.storage, |config| -> StdResult<_> {
+        Ok(Config {
+            unbonding_period,
+            ..config
+        })
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "unbonding_period_updated")
+        .add_attribute("unbonding_period", unbonding_period.to_string()))
+}
+
+pub fn query_stake(deps: Deps, oracle: String) -> StdResult<StakeResponse> {
+    let oracle = deps.api.addr_validate(&oracle)?;
+    let locked = locked(deps.storage, &oracle)?;
+    Ok(match STAKES.may_load(deps.storage, &oracle)? {
+        Some(stake) => StakeResponse {
+            admin: Some(stake.admin),
+            pending_admin: stake.pending_admin,
+            bonded: stake.bonded,
+            locked,
+            unbonding: stake.unbonding,
+        },
+        None => StakeResponse {
+            admin: None,
+            pending_admin: None,
+            bonded: Uint128::zero(),
+            locked,
+            unbonding: vec![],
+        },
+    })
+}
+
+/// The most any feed requires to stay bonded for the oracle
+fn locked(storage: &dyn Storage, oracle: &Addr) -> StdResult<Uint128> {
+    LOCKS
+        .prefix(oracle)
+        .range(storage, None, None, Order::Ascending)
+        .try_fold(Uint128::zero(), |locked, lock| Ok(locked.max(lock?.1)))
+}
+
+fn remove_slashable(storage: &mut dyn Storage, slasher: &Addr, oracle: &Addr) {
+    SLASHABLE.remove(storage, (slasher, oracle));
+    LOCKS.remove(storage, (oracle, slasher));
+}
+
+fn validate_ownership(deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
+    let owner = get_owner(deps)?;
+    if info.sender != owner {
+        return Err(ContractError::NotOwner {});
+    }
+    Ok(())
+}
+
+fn load_as_admin(deps: Deps, info: &MessageInfo, oracle: &Addr) -> Result<Stake, ContractError> {
+    match STAKES.may_load(deps.storage, oracle)? {
+        Some(stake) if stake.admin == info.sender => Ok(stake),
+        _ => Err(ContractError::NotAdmin {}),
+    }
+}
+
+fn transfer(link: Addr, recipient: &Addr, amount: Uint128) -> StdResult<WasmMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: link.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount,
+        })?,
+        funds: vec![],
+    })
+}
@@ -0,0 +1,67 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Owned(#[from] owned::error::ContractError),
+
+    /// Only callable by owner
+    #[error("Only callable by owner")]
+    NotOwner {},
+
+    /// Tokens other than LINK were sent
+    #[error("Only LINK can be staked")]
+    InvalidToken {},
+
+    /// Only callable by the oracle's admin
+    #[error("Only callable by the oracle's admin")]
+    NotAdmin {},
+
+    /// Only callable by pending admin
+    #[error("Only callable by pending admin")]
+    NotPendingAdmin {},
+
+    /// No pending admin
+    #[error("No pending admin")]
+    PendingAdminMissing {},
+
+    /// Admins of staked LINK are only replaced by their own transfer
+    #[error("Stake is not empty, its admin has to transfer it")]
+    StakeNotEmpty {},
+
+    /// Amount is zero
+    #[error("Amount must be greater than 0")]
+    ZeroAmount {},
+
+    /// Unbonding more than is bonded
+    #[error("Insufficient bonded stake")]
+    InsufficientStake {},
+
+    /// Only callable by registered slashers
+    #[error("Only callable by slashers")]
+    NotSlasher {},
+
+    /// Oracle wasn't made slashable by the slasher
+    #[error("Oracle is not slashable by the sender")]
+    NotSlashable {},
+
+    /// Unbonding would leave less bonded than a feed the oracle is active in requires
+    #[error("Bonded stake is locked")]
+    StakeLocked {},
+
+    /// Release time of the unbonding overflows
+    #[error("Unbonding period too long")]
+    UnbondingPeriodTooLong {},
+
+    /// No unbonding period passed yet
+    #[error("Nothing to withdraw")]
+    NothingToWithdraw {},
+
+    /// Withdrawing more than was slashed
+    #[error("Insufficient slashed funds")]
+    InsufficientSlashed {},
+}
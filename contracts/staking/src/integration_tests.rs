@@ -0,0 +1,514 @@
+#![cfg(test)]
+
+use cosmwasm_std::{
+    testing::{mock_env, MockApi, MockStorage},
+    to_binary, Addr, Empty, Uint128,
+};
+use cw20::{BalanceResponse, Cw20Coin};
+use cw_multi_test::{App, BankKeeper, Contract, ContractWrapper, Executor};
+
+use crate::{
+    contract::{execute, instantiate, query},
+    error::ContractError,
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg, ReceiveMsg, StakeResponse},
+    state::Unbonding,
+};
+
+const UNBONDING_PERIOD: u64 = 7 * 24 * 3600;
+
+fn mock_app() -> App {
+    let env = mock_env();
+    let api = MockApi::default();
+    let bank = BankKeeper::new();
+    let storage = MockStorage::new();
+
+    App::new(api, env.block, bank, storage)
+}
+
+pub fn contract_staking() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(execute, instantiate, query);
+    Box::new(contract)
+}
+
+pub fn contract_link_token() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn instantiate_token(app: &mut App, symbol: &str, holder: &str) -> Addr {
+    let id = app.store_code(contract_link_token());
+    app.instantiate_contract(
+        id,
+        Addr::unchecked("owner"),
+        &cw20_base::msg::InstantiateMsg {
+            name: symbol.to_owned(),
+            symbol: symbol.to_owned(),
+            decimals: 18,
+            initial_balances: vec![Cw20Coin {
+                address: holder.to_owned(),
+                amount: Uint128::new(1000),
+            }],
+            mint: None,
+            marketing: None,
+        },
+        &[],
+        symbol,
+        None,
+    )
+    .unwrap()
+}
+
+/// LINK held by "admin" and the staking contract
+fn default_init() -> (App, Addr, Addr) {
+    let mut app = mock_app();
+    let link = instantiate_token(&mut app, "LINK", "admin");
+
+    let id = app.store_code(contract_staking());
+    let staking = app
+        .instantiate_contract(
+            id,
+            Addr::unchecked("owner"),
+            &InstantiateMsg {
+                link: link.to_string(),
+                unbonding_period: UNBONDING_PERIOD,
+            },
+            &[],
+            "Staking",
+            None,
+        )
+        .unwrap();
+
+    (app, link, staking)
+}
+
+fn stake(app: &mut App, token: &Addr, staking: &Addr, staker: &str, amount: u128) -> String {
+    app.execute_contract(
+        Addr::unchecked(staker),
+        token.clone(),
+        &cw20_base::msg::ExecuteMsg::Send {
+            contract: staking.to_string(),
+            amount: Uint128::new(amount),
+            msg: to_binary(&ReceiveMsg::Stake {
+                oracle: "oracle".to_owned(),
+            })
+            .unwrap(),
+        },
+        &[],
+    )
+    .map(|_| String::new())
+    .unwrap_or_else(|err| err)
+}
+
+fn set_admin(app: &mut App, staking: &Addr, admin: &str) {
+    app.execute_contract(
+        Addr::unchecked("owner"),
+        staking.clone(),
+        &ExecuteMsg::SetAdmin {
+            oracle: "oracle".to_owned(),
+            admin: admin.to_owned(),
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+/// Lets the slasher take `max_amount` at once from "oracle"
+fn set_slasher(app: &mut App, staking: &Addr, slasher: &str, max_amount: u128) {
+    let owner = Addr::unchecked("owner");
+    app.execute_contract(
+        owner.clone(),
+        staking.clone(),
+        &ExecuteMsg::SetSlasher {
+            slasher: slasher.to_owned(),
+            max_amount: Some(Uint128::new(max_amount)),
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        owner,
+        staking.clone(),
+        &ExecuteMsg::SetSlashableOracles {
+            slasher: slasher.to_owned(),
+            added: vec!["oracle".to_owned()],
+            removed: vec![],
+        },
+        &[],
+    )
+    .unwrap();
+}
+
+fn query_stake(app: &App, staking: &Addr) -> StakeResponse {
+    app.wrap()
+        .query_wasm_smart(
+            staking,
+            &QueryMsg::GetStake {
+                oracle: "oracle".to_owned(),
+            },
+        )
+        .unwrap()
+}
+
+fn balance(app: &App, link: &Addr, address: &str) -> Uint128 {
+    let BalanceResponse { balance } = app
+        .wrap()
+        .query_wasm_smart(
+            link,
+            &cw20_base::msg::QueryMsg::Balance {
+                address: address.to_owned(),
+            },
+        )
+        .unwrap();
+    balance
+}
+
+#[test]
+fn stake_unbond_withdraw() {
+    let (mut app, link, staking) = default_init();
+    let admin = Addr::unchecked("admin");
+
+    // nobody stakes before an admin is assigned
+    assert_eq!(
+        stake(&mut app, &link, &staking, "admin", 100),
+        ContractError::NotAdmin {}.to_string()
+    );
+    set_admin(&mut app, &staking, "admin");
+    assert_eq!(stake(&mut app, &link, &staking, "admin", 100), "");
+    assert_eq!(
+        query_stake(&app, &staking),
+        StakeResponse {
+            admin: Some(admin.clone()),
+            pending_admin: None,
+            bonded: Uint128::new(100),
+            locked: Uint128::zero(),
+            unbonding: vec![],
+        }
+    );
+
+    // only LINK can be staked, only by the oracle's admin
+    let fake = instantiate_token(&mut app, "FAKE", "admin");
+    assert_eq!(
+        stake(&mut app, &fake, &staking, "admin", 100),
+        ContractError::InvalidToken {}.to_string()
+    );
+    app.execute_contract(
+        admin.clone(),
+        link.clone(),
+        &cw20_base::msg::ExecuteMsg::Transfer {
+            recipient: "mallory".to_owned(),
+            amount: Uint128::new(10),
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        stake(&mut app, &link, &staking, "mallory", 10),
+        ContractError::NotAdmin {}.to_string()
+    );
+    let unbond = |amount| ExecuteMsg::Unbond {
+        oracle: "oracle".to_owned(),
+        amount: Uint128::new(amount),
+    };
+    let err = app
+        .execute_contract(
+            Addr::unchecked("mallory"),
+            staking.clone(),
+            &unbond(40),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotAdmin {}.to_string());
+    let err = app
+        .execute_contract(admin.clone(), staking.clone(), &unbond(150), &[])
+        .unwrap_err();
+    assert_eq!(err, ContractError::InsufficientStake {}.to_string());
+
+    app.execute_contract(admin.clone(), staking.clone(), &unbond(40), &[])
+        .unwrap();
+    let release_at = mock_env().block.time.seconds() + UNBONDING_PERIOD;
+    assert_eq!(
+        query_stake(&app, &staking),
+        StakeResponse {
+            admin: Some(admin.clone()),
+            pending_admin: None,
+            bonded: Uint128::new(60),
+            locked: Uint128::zero(),
+            unbonding: vec![Unbonding {
+                amount: Uint128::new(40),
+                release_at,
+            }],
+        }
+    );
+
+    let withdraw = ExecuteMsg::Withdraw {
+        oracle: "oracle".to_owned(),
+    };
+    let err = app
+        .execute_contract(admin.clone(), staking.clone(), &withdraw, &[])
+        .unwrap_err();
+    assert_eq!(err, ContractError::NothingToWithdraw {}.to_string());
+
+    app.update_block(|block| block.time = block.time.plus_seconds(UNBONDING_PERIOD));
+    let before = balance(&app, &link, "admin");
+    app.execute_contract(admin, staking.clone(), &withdraw, &[])
+        .unwrap();
+    assert_eq!(balance(&app, &link, "admin"), before + Uint128::new(40));
+    assert!(query_stake(&app, &staking).unbonding.is_empty());
+}
+
+#[test]
+fn slash() {
+    let (mut app, link, staking) = default_init();
+    let owner = Addr::unchecked("owner");
+    let slasher = Addr::unchecked("slasher");
+
+    set_admin(&mut app, &staking, "admin");
+    stake(&mut app, &link, &staking, "admin", 100);
+    app.execute_contract(
+        Addr::unchecked("admin"),
+        staking.clone(),
+        &ExecuteMsg::Unbond {
+            oracle: "oracle".to_owned(),
+            amount: Uint128::new(30),
+        },
+        &[],
+    )
+    .unwrap();
+
+    let slash = |oracle: &str, amount| ExecuteMsg::Slash {
+        oracle: oracle.to_owned(),
+        amount: Uint128::new(amount),
+        reason: "outlier".to_owned(),
+    };
+    let err = app
+        .execute_contract(slasher.clone(), staking.clone(), &slash("oracle", 80), &[])
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotSlasher {}.to_string());
+    set_slasher(&mut app, &staking, "slasher", 80);
+
+    // only oracles made slashable by an oracle manager
+    let err = app
+        .execute_contract(slasher.clone(), staking.clone(), &slash("other", 50), &[])
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotSlashable {}.to_string());
+    let is_slashable: bool = app
+        .wrap()
+        .query_wasm_smart(
+            &staking,
+            &QueryMsg::IsSlashable {
+                slasher: "slasher".to_owned(),
+                oracle: "oracle".to_owned(),
+            },
+        )
+        .unwrap();
+    assert!(is_slashable);
+
+    // bonded LINK is slashed first, then the unbonding, at most max_amount at once
+    app.execute_contract(slasher.clone(), staking.clone(), &slash("oracle", 90), &[])
+        .unwrap();
+    let stake = query_stake(&app, &staking);
+    assert_eq!(stake.bonded, Uint128::zero());
+    assert_eq!(stake.unbonding[0].amount, Uint128::new(20));
+    // never more than is staked
+    app.execute_contract(slasher, staking.clone(), &slash("oracle", 50), &[])
+        .unwrap();
+    assert!(query_stake(&app, &staking).unbonding.is_empty());
+
+    let slashed: Uint128 = app
+        .wrap()
+        .query_wasm_smart(&staking, &QueryMsg::GetSlashed {})
+        .unwrap();
+    assert_eq!(slashed, Uint128::new(100));
+
+    let withdraw = |amount| ExecuteMsg::WithdrawSlashed {
+        recipient: "treasury".to_owned(),
+        amount: Uint128::new(amount),
+    };
+    let err = app
+        .execute_contract(
+            Addr::unchecked("admin"),
+            staking.clone(),
+            &withdraw(100),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotOwner {}.to_string());
+    let err = app
+        .execute_contract(owner.clone(), staking.clone(), &withdraw(101), &[])
+        .unwrap_err();
+    assert_eq!(err, ContractError::InsufficientSlashed {}.to_string());
+    app.execute_contract(owner, staking, &withdraw(100), &[])
+        .unwrap();
+    assert_eq!(balance(&app, &link, "treasury"), Uint128::new(100));
+}
+
+#[test]
+fn admin() {
+    let (mut app, link, staking) = default_init();
+    let set = ExecuteMsg::SetAdmin {
+        oracle: "oracle".to_owned(),
+        admin: "mallory".to_owned(),
+    };
+    let err = app
+        .execute_contract(Addr::unchecked("mallory"), staking.clone(), &set, &[])
+        .unwrap_err();
+    assert_eq!(
+        err,
+        owned::error::ContractError::MissingRole {
+            role: owned::roles::ORACLE_MANAGER.to_owned()
+        }
+        .to_string()
+    );
+    set_admin(&mut app, &staking, "admin");
+    stake(&mut app, &link, &staking, "admin", 100);
+
+    let transfer = ExecuteMsg::TransferAdmin {
+        oracle: "oracle".to_owned(),
+        new_admin: "new_admin".to_owned(),
+    };
+    let err = app
+        .execute_contract(Addr::unchecked("mallory"), staking.clone(), &transfer, &[])
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotAdmin {}.to_string());
+    app.execute_contract(Addr::unchecked("admin"), staking.clone(), &transfer, &[])
+        .unwrap();
+    assert_eq!(
+        query_stake(&app, &staking).pending_admin,
+        Some(Addr::unchecked("new_admin"))
+    );
+
+    let accept = ExecuteMsg::AcceptAdmin {
+        oracle: "oracle".to_owned(),
+    };
+    let err = app
+        .execute_contract(Addr::unchecked("mallory"), staking.clone(), &accept, &[])
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotPendingAdmin {}.to_string());
+    app.execute_contract(Addr::unchecked("new_admin"), staking.clone(), &accept, &[])
+        .unwrap();
+    let stake = query_stake(&app, &staking);
+    assert_eq!(stake.admin, Some(Addr::unchecked("new_admin")));
+    assert_eq!(stake.pending_admin, None);
+    assert_eq!(stake.bonded, Uint128::new(100));
+    let err = app
+        .execute_contract(Addr::unchecked("new_admin"), staking.clone(), &accept, &[])
+        .unwrap_err();
+    assert_eq!(err, ContractError::PendingAdminMissing {}.to_string());
+
+    // oracle managers can't take over a funded stake
+    let err = app
+        .execute_contract(Addr::unchecked("owner"), staking.clone(), &set, &[])
+        .unwrap_err();
+    assert_eq!(err, ContractError::StakeNotEmpty {}.to_string());
+    assert_eq!(
+        query_stake(&app, &staking).admin,
+        Some(Addr::unchecked("new_admin"))
+    );
+}
+
+#[test]
+fn lock() {
+    let (mut app, link, staking) = default_init();
+    let admin = Addr::unchecked("admin");
+    set_admin(&mut app, &staking, "admin");
+    stake(&mut app, &link, &staking, "admin", 100);
+
+    let lock = |amount| ExecuteMsg::Lock {
+        oracle: "oracle".to_owned(),
+        amount: Uint128::new(amount),
+    };
+    let err = app
+        .execute_contract(Addr::unchecked("feed"), staking.clone(), &lock(50), &[])
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotSlasher {}.to_string());
+    for feed in ["feed", "other_feed", "third_feed"].iter() {
+        set_slasher(&mut app, &staking, feed, 10);
+    }
+    app.execute_contract(Addr::unchecked("feed"), staking.clone(), &lock(50), &[])
+        .unwrap();
+    app.execute_contract(
+        Addr::unchecked("other_feed"),
+        staking.clone(),
+        &lock(70),
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_stake(&app, &staking).locked, Uint128::new(70));
+
+    // oracles no longer slashable by a feed, or feeds removed, are released
+    app.execute_contract(
+        Addr::unchecked("third_feed"),
+        staking.clone(),
+        &lock(90),
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("owner"),
+        staking.clone(),
+        &ExecuteMsg::SetSlashableOracles {
+            slasher: "third_feed".to_owned(),
+            added: vec![],
+            removed: vec!["oracle".to_owned()],
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_stake(&app, &staking).locked, Uint128::new(70));
+    let err = app
+        .execute_contract(
+            Addr::unchecked("third_feed"),
+            staking.clone(),
+            &lock(90),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotSlashable {}.to_string());
+
+    // the most any feed requires stays bonded
+    let unbond = |amount| ExecuteMsg::Unbond {
+        oracle: "oracle".to_owned(),
+        amount: Uint128::new(amount),
+    };
+    let err = app
+        .execute_contract(admin.clone(), staking.clone(), &unbond(40), &[])
+        .unwrap_err();
+    assert_eq!(err, ContractError::StakeLocked {}.to_string());
+    app.execute_contract(admin.clone(), staking.clone(), &unbond(30), &[])
+        .unwrap();
+
+    app.execute_contract(
+        Addr::unchecked("owner"),
+        staking.clone(),
+        &ExecuteMsg::SetSlasher {
+            slasher: "other_feed".to_owned(),
+            max_amount: None,
+        },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(query_stake(&app, &staking).locked, Uint128::new(50));
+    app.execute_contract(admin.clone(), staking.clone(), &unbond(20), &[])
+        .unwrap();
+
+    // release times beyond the representable block time are refused
+    app.execute_contract(
+        Addr::unchecked("owner"),
+        staking.clone(),
+        &ExecuteMsg::SetUnbondingPeriod {
+            unbonding_period: u64::MAX,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(Addr::unchecked("feed"), staking.clone(), &lock(0), &[])
+        .unwrap();
+    let err = app
+        .execute_contract(admin, staking, &unbond(10), &[])
+        .unwrap_err();
+    assert_eq!(err, ContractError::UnbondingPeriodTooLong {}.to_string());
+}
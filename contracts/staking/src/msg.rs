@@ -0,0 +1,134 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw20::Cw20ReceiveMsg;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::state::Unbonding;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// LINK token address
+    pub link: String,
+    /// Seconds unbonding LINK stays slashable before it can be withdrawn
+    pub unbonding_period: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Initiate contract ownership transfer to another address.
+    /// Can be used only by owner
+    TransferOwnership {
+        /// Address to transfer ownership to
+        to: String,
+        /// Block time in seconds after which the transfer can no longer be accepted
+        expires_at: Option<u64>,
+    },
+    /// Finish contract ownership transfer. Can be used only by pending owner
+    AcceptOwnership {},
+    /// Cancel the pending ownership transfer. Can be used only by owner
+    CancelOwnershipTransfer {},
+    /// Leave the contract without owner, disabling functionality restricted to the owner.
+    /// Can be used only by owner
+    RenounceOwnership {},
+    /// Grant a role, see `owned::roles`. Can be used only by owner
+    GrantRole { role: String, account: String },
+    /// Revoke a granted role. Can be used only by owner
+    RevokeRole { role: String, account: String },
+    /// Give up a role granted to the sender
+    RenounceRole { role: String },
+    /// Assign the oracle's admin, replacing the current one while nothing is staked.
+    /// Can be used only by oracle managers, see `owned::roles::ORACLE_MANAGER`
+    SetAdmin { oracle: String, admin: String },
+    /// Transfer the oracle's admin. Can be used only by the oracle's admin
+    TransferAdmin { oracle: String, new_admin: String },
+    /// Accept the pending admin transfer for the oracle
+    AcceptAdmin { oracle: String },
+    /// Handler for LINK token Send message, carrying a [`ReceiveMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// Start unbonding LINK staked for the oracle. Can be used only by the oracle's admin
+    Unbond { oracle: String, amount: Uint128 },
+    /// Transfer the LINK whose unbonding period passed to the oracle's admin.
+    /// Can be used only by the oracle's admin
+    Withdraw { oracle: String },
+    /// Take up to `amount` of the LINK staked for the oracle, bonded before unbonding, and no
+    /// more than the slasher's `max_amount`. Can be used only by slashers the oracle is
+    /// slashable by
+    Slash {
+        oracle: String,
+        amount: Uint128,
+        reason: String,
+    },
+    /// Keep at least `amount` bonded for the oracle while it is active in the sender's feed,
+    /// zero releasing it. Can be used only by slashers the oracle is slashable by
+    Lock { oracle: String, amount: Uint128 },
+    /// Allow the slasher, usually a feed, to slash at most `max_amount` at once, `None`
+    /// removing it along with its locks. Can be used only by config admin
+    SetSlasher {
+        slasher: String,
+        max_amount: Option<Uint128>,
+    },
+    /// Change the oracles the slasher may slash and lock, removing releases their locks.
+    /// Can be used only by oracle managers, see `owned::roles::ORACLE_MANAGER`
+    SetSlashableOracles {
+        slasher: String,
+        added: Vec<String>,
+        removed: Vec<String>,
+    },
+    /// Transfer slashed LINK. Can be used only by owner
+    WithdrawSlashed { recipient: String, amount: Uint128 },
+    /// Applies to unbondings started afterwards. Can be used only by config admin
+    SetUnbondingPeriod { unbonding_period: u64 },
+}
+
+/// Payload of the LINK token Send message
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg {
+    /// Bond the sent LINK for the oracle. Can be used only by the oracle's admin
+    Stake { oracle: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns contract owner's address
+    /// Response [`Addr`]
+    GetOwner {},
+    /// Returns the owner, the pending owner and the expiry of the pending transfer
+    /// Response [`owned::msg::OwnershipStateResponse`]
+    GetOwnershipState {},
+    /// Returns whether the account holds the role, which the owner always does
+    /// Response `bool`
+    HasRole { role: String, account: String },
+    /// Returns the accounts granted the role, not including the owner
+    /// Response [`owned::msg::RoleMembersResponse`]
+    GetRoleMembers {
+        role: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Response: [`crate::state::Config`]
+    GetConfig {},
+    /// Response: [`StakeResponse`], empty for oracles without admin
+    GetStake { oracle: String },
+    /// Slashed LINK not yet withdrawn.
+    /// Response: [`Uint128`]
+    GetSlashed {},
+    /// The most the slasher takes at once, `None` for accounts that aren't slashers.
+    /// Response: `Option<Uint128>`
+    GetSlasher { slasher: String },
+    /// Whether the slasher may slash and lock the oracle.
+    /// Response: `bool`
+    IsSlashable { slasher: String, oracle: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakeResponse {
+    pub admin: Option<Addr>,
+    pub pending_admin: Option<Addr>,
+    pub bonded: Uint128,
+    /// Bonded LINK that can't be unbonded, the most any feed requires
+    pub locked: Uint128,
+    pub unbonding: Vec<Unbonding>,
+}
@@ -0,0 +1,40 @@
+use cosmwasm_std::{Addr, Empty, Uint128};
+use cw_storage_plus::{Item, Map};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// LINK token address
+    pub link: Addr,
+    /// Seconds unbonding LINK stays slashable before it can be withdrawn
+    pub unbonding_period: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Unbonding {
+    pub amount: Uint128,
+    /// Block time in seconds the LINK can be withdrawn at
+    pub release_at: u64,
+}
+
+/// LINK staked for an oracle
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Stake {
+    /// Account assigned by an oracle manager, the only one allowed to stake, unbond and withdraw
+    pub admin: Addr,
+    pub pending_admin: Option<Addr>,
+    pub bonded: Uint128,
+    pub unbonding: Vec<Unbonding>,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const STAKES: Map<&Addr, Stake> = Map::new("stakes");
+/// Feeds allowed to slash, with the most they take from an oracle at once
+pub const SLASHERS: Map<&Addr, Uint128> = Map::new("slashers");
+/// Oracles a slasher may slash and lock, by slasher and oracle
+pub const SLASHABLE: Map<(&Addr, &Addr), Empty> = Map::new("slashable");
+/// Bonded LINK an oracle needs while active in a feed, by oracle and feed
+pub const LOCKS: Map<(&Addr, &Addr), Uint128> = Map::new("locks");
+/// LINK taken by slashing, not yet withdrawn by the owner
+pub const SLASHED: Item<Uint128> = Item::new("slashed");
@@ -0,0 +1,86 @@
+use cosmwasm_std::{
+    to_binary, Addr, CosmosMsg, CustomQuery, QuerierWrapper, QueryRequest, StdResult, Uint128,
+    WasmMsg, WasmQuery,
+};
+use schemars::JsonSchema;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{LatestAnswerResponse, Price, QueryMsg, RoundDataResponse};
+
+/// AggregatorContract is a wrapper around Addr that provides helpers for working with
+/// any contract answering [`QueryMsg`] queries, such as an aggregator, aggregator proxy
+/// or derived feed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AggregatorContract(pub Addr);
+
+impl AggregatorContract {
+    pub fn addr(&self) -> Addr {
+        self.0.clone()
+    }
+
+    /// Wraps an execute message of the feed contract, e.g. a flux aggregator
+    /// submission, into a message without funds
+    pub fn call<T: Serialize>(&self, msg: T) -> StdResult<CosmosMsg> {
+        let msg = to_binary(&msg)?;
+        Ok(WasmMsg::Execute {
+            contract_addr: self.addr().into(),
+            msg,
+            funds: vec![],
+        }
+        .into())
+    }
+
+    /// Builds the smart query request of the feed for the given aggregator query
+    pub fn query_request<C: CustomQuery>(&self, msg: QueryMsg) -> StdResult<QueryRequest<C>> {
+        Ok(WasmQuery::Smart {
+            contract_addr: self.addr().into(),
+            msg: to_binary(&msg.wrap())?,
+        }
+        .into())
+    }
+
+    fn query<T: DeserializeOwned>(&self, querier: &QuerierWrapper, msg: QueryMsg) -> StdResult<T> {
+        querier.query(&self.query_request(msg)?)
+    }
+
+    pub fn round_data(
+        &self,
+        querier: &QuerierWrapper,
+        round_id: u64,
+    ) -> StdResult<RoundDataResponse> {
+        self.query(querier, QueryMsg::GetRoundData { round_id })
+    }
+
+    pub fn latest_round_data(&self, querier: &QuerierWrapper) -> StdResult<RoundDataResponse> {
+        self.query(querier, QueryMsg::GetLatestRoundData {})
+    }
+
+    pub fn latest_answer(&self, querier: &QuerierWrapper) -> StdResult<Option<Uint128>> {
+        let LatestAnswerResponse(answer) = self.query(querier, QueryMsg::GetLatestAnswer {})?;
+        Ok(answer)
+    }
+
+    pub fn decimals(&self, querier: &QuerierWrapper) -> StdResult<u8> {
+        self.query(querier, QueryMsg::GetDecimals {})
+    }
+
+    pub fn description(&self, querier: &QuerierWrapper) -> StdResult<String> {
+        self.query(querier, QueryMsg::GetDescription {})
+    }
+
+    pub fn version(&self, querier: &QuerierWrapper) -> StdResult<Uint128> {
+        self.query(querier, QueryMsg::GetVersion {})
+    }
+
+    /// See [`crate::FeedQuerier::get_price`]
+    pub fn price(
+        &self,
+        querier: &QuerierWrapper,
+        now: u64,
+        max_staleness: u64,
+    ) -> StdResult<Price> {
+        let round = self.latest_round_data(querier)?;
+        let decimals = self.decimals(querier)?;
+        Price::from_fresh_round(&round, decimals, now, max_staleness)
+    }
+}
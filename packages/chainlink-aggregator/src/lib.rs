@@ -1,10 +1,38 @@
+mod helpers;
+pub mod price;
 mod querier;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod testing;
 
 use cosmwasm_std::Uint128;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-pub use querier::FeedQuerier;
+pub use helpers::AggregatorContract;
+pub use price::{Price, Rounding};
+pub use querier::{FeedQuerier, FeedRegistryQuerier};
+
+/// Bit offset of the phase ID in round IDs of phased feeds such as the aggregator proxy.
+///
+/// Such round IDs are laid out as `phase_id << PHASE_OFFSET | aggregator_round_id`, keeping
+/// the 16 bit phase ID and the 32 bit round ID of the phase's aggregator below 2^53 so they
+/// are represented exactly as JSON numbers. Round IDs of plain aggregators have no phase bits.
+pub const PHASE_OFFSET: u32 = 32;
+
+/// Combines a phase ID and the round ID of the phase's aggregator into a proxied round ID
+pub fn compose_round_id(phase_id: u16, aggregator_round_id: u32) -> u64 {
+    (phase_id as u64) << PHASE_OFFSET | aggregator_round_id as u64
+}
+
+/// Splits a proxied round ID into the phase ID and the round ID of the phase's aggregator.
+/// Returns `None` if bits above the phase ID are set
+pub fn parse_round_id(round_id: u64) -> Option<(u16, u32)> {
+    let phase_id = round_id >> PHASE_OFFSET;
+    if phase_id > u16::MAX as u64 {
+        return None;
+    }
+    Some((phase_id as u16, round_id as u32))
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -12,8 +40,9 @@ pub enum QueryMsg {
     /// Query data for a specific round
     /// Response: [`RoundDataResponse`].
     GetRoundData {
-        /// The round ID to retrieve the round data for
-        round_id: u32,
+        /// The round ID to retrieve the round data for, phased feeds keep the phase ID
+        /// above `PHASE_OFFSET`
+        round_id: u64,
     },
     /// Query data for the latest round
     /// Response: [`RoundDataResponse`].
@@ -49,12 +78,29 @@ impl AggregatorQuery {
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct RoundDataResponse {
-    pub round_id: u32,           // uint80
+    pub round_id: u64,           // uint80
     pub answer: Option<Uint128>, // int256
     pub started_at: Option<u64>, // int256
     pub updated_at: Option<u64>, // uint256
-    pub answered_in_round: u32,  // uint80
+    pub answered_in_round: u64,  // uint80
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct LatestAnswerResponse(pub Option<Uint128>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_id_layout() {
+        assert_eq!(compose_round_id(0, 7), 7);
+        assert_eq!(compose_round_id(1, 7), (1 << 32) + 7);
+        assert_eq!(compose_round_id(u16::MAX, u32::MAX), (1 << 48) - 1);
+
+        assert_eq!(parse_round_id(7), Some((0, 7)));
+        assert_eq!(parse_round_id((2 << 32) + 7), Some((2, 7)));
+        assert_eq!(parse_round_id((1 << 48) - 1), Some((u16::MAX, u32::MAX)));
+        assert_eq!(parse_round_id(1 << 48), None);
+    }
+}
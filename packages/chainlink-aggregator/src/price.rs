@@ -0,0 +1,275 @@
+use std::convert::TryFrom;
+
+use cosmwasm_std::{
+    Decimal, OverflowError, OverflowOperation, StdError, StdResult, Uint128, Uint256,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::RoundDataResponse;
+
+/// Decimals of `cosmwasm_std::Decimal`
+const DECIMAL_PLACES: u8 = 18;
+
+/// How to round results that can't be represented exactly with the target decimals
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Rounding {
+    /// Towards zero
+    Down,
+    /// Away from zero
+    Up,
+    /// To the nearest value, halves away from zero
+    HalfUp,
+}
+
+/// Fixed-point answer of a feed, `value / 10^decimals`
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Price {
+    pub value: Uint128,
+    pub decimals: u8,
+}
+
+impl Price {
+    pub fn new(value: Uint128, decimals: u8) -> Self {
+        Price { value, decimals }
+    }
+
+    /// Price of a round answered by a feed with the given decimals. Fails if the round
+    /// has no answer
+    pub fn from_round(round: &RoundDataResponse, decimals: u8) -> StdResult<Self> {
+        round
+            .answer
+            .map(|value| Price::new(value, decimals))
+            .ok_or_else(|| StdError::generic_err("No data present"))
+    }
+
+    /// Like [`Price::from_round`], also failing if the round was carried over from an
+    /// earlier round or was updated more than `max_staleness` seconds before `now`
+    pub fn from_fresh_round(
+        round: &RoundDataResponse,
+        decimals: u8,
+        now: u64,
+        max_staleness: u64,
+    ) -> StdResult<Self> {
+        let price = Price::from_round(round, decimals)?;
+        if round.answered_in_round < round.round_id {
+            return Err(StdError::generic_err("Stale round"));
+        }
+        match round.updated_at {
+            Some(updated_at) if now.saturating_sub(updated_at) <= max_staleness => Ok(price),
+            _ => Err(StdError::generic_err("Stale answer")),
+        }
+    }
+
+    /// Fails if the value doesn't fit in a `Decimal` after rounding to its 18 decimals
+    pub fn to_decimal(&self, rounding: Rounding) -> StdResult<Decimal> {
+        let scaled = self.rescale(DECIMAL_PLACES, rounding)?.value;
+        Ok(Decimal::from_ratio(
+            scaled,
+            Uint128::new(10u128.pow(DECIMAL_PLACES as u32)),
+        ))
+    }
+
+    /// The same price with the given decimals
+    pub fn rescale(&self, decimals: u8, rounding: Rounding) -> StdResult<Self> {
+        let (numerator, denominator) = if decimals >= self.decimals {
+            (
+                pow10((decimals - self.decimals).into())?,
+                Uint256::from(1u8),
+            )
+        } else {
+            (
+                Uint256::from(1u8),
+                pow10((self.decimals - decimals).into())?,
+            )
+        };
+        let value = mul_div(self.value, numerator, denominator, rounding)?;
+        Ok(Price::new(value, decimals))
+    }
+
+    /// Price of the base asset of `self` in units of the base asset of `quote`, where both
+    /// prices share a quote asset. E.g. LUNA/EUR is the cross rate of LUNA/USD and EUR/USD
+    pub fn cross(&self, quote: &Price, decimals: u8, rounding: Rounding) -> StdResult<Self> {
+        // self.value * 10^(quote.decimals + decimals - self.decimals) / quote.value
+        let exponent = quote.decimals as i32 + decimals as i32 - self.decimals as i32;
+        let (numerator, denominator) = if exponent >= 0 {
+            (pow10(exponent as u32)?, Uint256::from(quote.value))
+        } else {
+            let scale = pow10(-exponent as u32)?;
+            (
+                Uint256::from(1u8),
+                scale.checked_mul(Uint256::from(quote.value))?,
+            )
+        };
+        let value = mul_div(self.value, numerator, denominator, rounding)?;
+        Ok(Price::new(value, decimals))
+    }
+}
+
+/// Cross rate of the answers of two feeds sharing a quote asset, see [`Price::cross`]
+pub fn cross_rate(
+    base: &RoundDataResponse,
+    base_decimals: u8,
+    quote: &RoundDataResponse,
+    quote_decimals: u8,
+    decimals: u8,
+    rounding: Rounding,
+) -> StdResult<Price> {
+    Price::from_round(base, base_decimals)?.cross(
+        &Price::from_round(quote, quote_decimals)?,
+        decimals,
+        rounding,
+    )
+}
+
+fn pow10(exponent: u32) -> StdResult<Uint256> {
+    Ok(Uint256::from(10u8).checked_pow(exponent)?)
+}
+
+/// `value * numerator / denominator` without intermediate overflow
+fn mul_div(
+    value: Uint128,
+    numerator: Uint256,
+    denominator: Uint256,
+    rounding: Rounding,
+) -> StdResult<Uint128> {
+    let product = Uint256::from(value).checked_mul(numerator)?;
+    let quotient = product.checked_div(denominator)?;
+    let remainder = product.checked_rem(denominator)?;
+    let round_up = match rounding {
+        Rounding::Down => false,
+        Rounding::Up => !remainder.is_zero(),
+        Rounding::HalfUp => remainder >= denominator - remainder,
+    };
+    let result = if round_up {
+        quotient.checked_add(Uint256::from(1u8))?
+    } else {
+        quotient
+    };
+    Uint128::try_from(result).map_err(|_| {
+        OverflowError::new(OverflowOperation::Mul, value, numerator.to_string()).into()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn round(
+        answer: u128,
+        round_id: u64,
+        answered_in_round: u64,
+        updated_at: u64,
+    ) -> RoundDataResponse {
+        RoundDataResponse {
+            round_id,
+            answer: Some(Uint128::new(answer)),
+            started_at: Some(updated_at),
+            updated_at: Some(updated_at),
+            answered_in_round,
+        }
+    }
+
+    #[test]
+    fn rescale() {
+        let price = Price::new(Uint128::new(123456789), 8);
+        assert_eq!(
+            price.rescale(10, Rounding::Down).unwrap(),
+            Price::new(Uint128::new(12345678900), 10)
+        );
+        assert_eq!(
+            price.rescale(4, Rounding::Down).unwrap(),
+            Price::new(Uint128::new(12345), 4)
+        );
+        assert_eq!(price.rescale(4, Rounding::Up).unwrap().value.u128(), 12346);
+        assert_eq!(
+            price.rescale(4, Rounding::HalfUp).unwrap().value.u128(),
+            12346
+        );
+        assert_eq!(
+            price.rescale(3, Rounding::HalfUp).unwrap().value.u128(),
+            1235
+        );
+        assert_eq!(
+            price.rescale(2, Rounding::HalfUp).unwrap().value.u128(),
+            123
+        );
+        assert_eq!(
+            Price::new(Uint128::new(125), 2)
+                .rescale(1, Rounding::HalfUp)
+                .unwrap()
+                .value
+                .u128(),
+            13
+        );
+        assert_eq!(price.rescale(0, Rounding::Up).unwrap().value.u128(), 2);
+        assert!(price.rescale(40, Rounding::Down).is_err());
+    }
+
+    #[test]
+    fn to_decimal() {
+        let price = Price::new(Uint128::new(123456789), 8);
+        assert_eq!(
+            price.to_decimal(Rounding::Down).unwrap(),
+            Decimal::from_str("1.23456789").unwrap()
+        );
+        let precise = Price::new(Uint128::new(15), 19);
+        assert_eq!(
+            precise.to_decimal(Rounding::Down).unwrap(),
+            Decimal::from_str("0.000000000000000001").unwrap()
+        );
+        assert_eq!(
+            precise.to_decimal(Rounding::HalfUp).unwrap(),
+            Decimal::from_str("0.000000000000000002").unwrap()
+        );
+        assert!(Price::new(Uint128::MAX, 0)
+            .to_decimal(Rounding::Down)
+            .is_err());
+    }
+
+    #[test]
+    fn cross() {
+        // LUNA/USD = 50.0 with 8 decimals, EUR/USD = 1.25 with 6 decimals
+        let luna = round(5000000000, 1, 1, 100);
+        let eur = round(1250000, 1, 1, 100);
+        assert_eq!(
+            cross_rate(&luna, 8, &eur, 6, 8, Rounding::Down).unwrap(),
+            Price::new(Uint128::new(4000000000), 8)
+        );
+        // EUR/LUNA = 0.025
+        assert_eq!(
+            cross_rate(&eur, 6, &luna, 8, 2, Rounding::Down).unwrap(),
+            Price::new(Uint128::new(2), 2)
+        );
+        assert_eq!(
+            cross_rate(&eur, 6, &luna, 8, 2, Rounding::HalfUp).unwrap(),
+            Price::new(Uint128::new(3), 2)
+        );
+        // 1/3 with no decimals
+        let third =
+            Price::new(Uint128::new(1), 0).cross(&Price::new(Uint128::new(3), 0), 0, Rounding::Up);
+        assert_eq!(third.unwrap().value.u128(), 1);
+
+        let zero = round(0, 1, 1, 100);
+        assert!(cross_rate(&luna, 8, &zero, 8, 8, Rounding::Down).is_err());
+        let empty = RoundDataResponse {
+            answer: None,
+            ..luna.clone()
+        };
+        assert!(cross_rate(&luna, 8, &empty, 8, 8, Rounding::Down).is_err());
+    }
+
+    #[test]
+    fn fresh_round() {
+        let fresh = round(100, 2, 2, 90);
+        assert_eq!(
+            Price::from_fresh_round(&fresh, 8, 100, 10).unwrap(),
+            Price::new(Uint128::new(100), 8)
+        );
+        assert!(Price::from_fresh_round(&fresh, 8, 101, 10).is_err());
+        let carried_over = round(100, 2, 1, 90);
+        assert!(Price::from_fresh_round(&carried_over, 8, 100, 10).is_err());
+    }
+}
@@ -1,11 +1,12 @@
 use cosmwasm_std::{Addr, QuerierWrapper, StdResult, Uint128};
+use serde::{de::DeserializeOwned, Serialize};
 
-use crate::{LatestAnswerResponse, QueryMsg, RoundDataResponse};
+use crate::{LatestAnswerResponse, Price, QueryMsg, RoundDataResponse};
 
 pub trait FeedQuerier {
     fn get_latest_answer(&self, feed_address: Addr) -> StdResult<LatestAnswerResponse>;
 
-    fn get_round_data(&self, feed_address: Addr, round_id: u32) -> StdResult<RoundDataResponse>;
+    fn get_round_data(&self, feed_address: Addr, round_id: u64) -> StdResult<RoundDataResponse>;
 
     fn get_latest_round_data(&self, feed_address: Addr) -> StdResult<RoundDataResponse>;
 
@@ -14,6 +15,10 @@ pub trait FeedQuerier {
     fn get_decimals(&self, feed_address: Addr) -> StdResult<u8>;
 
     fn get_version(&self, feed_address: Addr) -> StdResult<Uint128>;
+
+    /// Latest answer with the feed's decimals, failing if it is older than `max_staleness`
+    /// seconds at `now`, see [`Price::from_fresh_round`]
+    fn get_price(&self, feed_address: Addr, now: u64, max_staleness: u64) -> StdResult<Price>;
 }
 
 impl<'a> FeedQuerier for QuerierWrapper<'a> {
@@ -21,7 +26,7 @@ impl<'a> FeedQuerier for QuerierWrapper<'a> {
         self.query_wasm_smart(feed_address, &QueryMsg::GetLatestAnswer {}.wrap())
     }
 
-    fn get_round_data(&self, feed_address: Addr, round_id: u32) -> StdResult<RoundDataResponse> {
+    fn get_round_data(&self, feed_address: Addr, round_id: u64) -> StdResult<RoundDataResponse> {
         self.query_wasm_smart(feed_address, &QueryMsg::GetRoundData { round_id }.wrap())
     }
 
@@ -40,4 +45,152 @@ impl<'a> FeedQuerier for QuerierWrapper<'a> {
     fn get_version(&self, feed_address: Addr) -> StdResult<Uint128> {
         self.query_wasm_smart(feed_address, &QueryMsg::GetVersion {}.wrap())
     }
+
+    fn get_price(&self, feed_address: Addr, now: u64, max_staleness: u64) -> StdResult<Price> {
+        let round = self.get_latest_round_data(feed_address.clone())?;
+        let decimals = self.get_decimals(feed_address)?;
+        Price::from_fresh_round(&round, decimals, now, max_staleness)
+    }
+}
+
+/// Queries of a feed registry, mirroring [`FeedQuerier`] with feeds looked up by
+/// `(base, quote)` pair
+pub trait FeedRegistryQuerier {
+    fn get_feed(&self, registry: Addr, base: &str, quote: &str) -> StdResult<Addr>;
+
+    fn get_latest_answer_by_pair(
+        &self,
+        registry: Addr,
+        base: &str,
+        quote: &str,
+    ) -> StdResult<LatestAnswerResponse>;
+
+    fn get_round_data_by_pair(
+        &self,
+        registry: Addr,
+        base: &str,
+        quote: &str,
+        round_id: u64,
+    ) -> StdResult<RoundDataResponse>;
+
+    fn get_latest_round_data_by_pair(
+        &self,
+        registry: Addr,
+        base: &str,
+        quote: &str,
+    ) -> StdResult<RoundDataResponse>;
+
+    fn get_description_by_pair(&self, registry: Addr, base: &str, quote: &str)
+        -> StdResult<String>;
+
+    fn get_decimals_by_pair(&self, registry: Addr, base: &str, quote: &str) -> StdResult<u8>;
+
+    fn get_version_by_pair(&self, registry: Addr, base: &str, quote: &str) -> StdResult<Uint128>;
+
+    fn get_price_by_pair(
+        &self,
+        registry: Addr,
+        base: &str,
+        quote: &str,
+        now: u64,
+        max_staleness: u64,
+    ) -> StdResult<Price>;
+}
+
+/// Subset of the feed registry's query messages
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum RegistryQuery<'a> {
+    GetFeed {
+        base: &'a str,
+        quote: &'a str,
+    },
+    AggregatorQuery {
+        base: &'a str,
+        quote: &'a str,
+        query: QueryMsg,
+    },
+}
+
+fn query_by_pair<T: DeserializeOwned>(
+    querier: &QuerierWrapper,
+    registry: Addr,
+    base: &str,
+    quote: &str,
+    query: QueryMsg,
+) -> StdResult<T> {
+    querier.query_wasm_smart(
+        registry,
+        &RegistryQuery::AggregatorQuery { base, quote, query },
+    )
+}
+
+impl<'a> FeedRegistryQuerier for QuerierWrapper<'a> {
+    fn get_feed(&self, registry: Addr, base: &str, quote: &str) -> StdResult<Addr> {
+        self.query_wasm_smart(registry, &RegistryQuery::GetFeed { base, quote })
+    }
+
+    fn get_latest_answer_by_pair(
+        &self,
+        registry: Addr,
+        base: &str,
+        quote: &str,
+    ) -> StdResult<LatestAnswerResponse> {
+        query_by_pair(self, registry, base, quote, QueryMsg::GetLatestAnswer {})
+    }
+
+    fn get_round_data_by_pair(
+        &self,
+        registry: Addr,
+        base: &str,
+        quote: &str,
+        round_id: u64,
+    ) -> StdResult<RoundDataResponse> {
+        query_by_pair(
+            self,
+            registry,
+            base,
+            quote,
+            QueryMsg::GetRoundData { round_id },
+        )
+    }
+
+    fn get_latest_round_data_by_pair(
+        &self,
+        registry: Addr,
+        base: &str,
+        quote: &str,
+    ) -> StdResult<RoundDataResponse> {
+        query_by_pair(self, registry, base, quote, QueryMsg::GetLatestRoundData {})
+    }
+
+    fn get_description_by_pair(
+        &self,
+        registry: Addr,
+        base: &str,
+        quote: &str,
+    ) -> StdResult<String> {
+        query_by_pair(self, registry, base, quote, QueryMsg::GetDescription {})
+    }
+
+    fn get_decimals_by_pair(&self, registry: Addr, base: &str, quote: &str) -> StdResult<u8> {
+        query_by_pair(self, registry, base, quote, QueryMsg::GetDecimals {})
+    }
+
+    fn get_version_by_pair(&self, registry: Addr, base: &str, quote: &str) -> StdResult<Uint128> {
+        query_by_pair(self, registry, base, quote, QueryMsg::GetVersion {})
+    }
+
+    fn get_price_by_pair(
+        &self,
+        registry: Addr,
+        base: &str,
+        quote: &str,
+        now: u64,
+        max_staleness: u64,
+    ) -> StdResult<Price> {
+        let round = self.get_latest_round_data_by_pair(registry.clone(), base, quote)?;
+        let decimals = self.get_decimals_by_pair(registry, base, quote)?;
+        Price::from_fresh_round(&round, decimals, now, max_staleness)
+    }
 }
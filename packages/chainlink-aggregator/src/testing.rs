@@ -0,0 +1,219 @@
+//! Mocks for unit testing contracts reading feeds with `mock_dependencies`-style helpers
+//! instead of a multi-test app
+
+use std::collections::{BTreeMap, HashMap};
+
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_slice, to_binary, Binary, ContractResult, Empty, OwnedDeps, Querier, QuerierResult,
+    QueryRequest, StdError, StdResult, SystemError, SystemResult, Uint128, WasmQuery,
+};
+
+use crate::{AggregatorQuery, LatestAnswerResponse, QueryMsg, RoundDataResponse};
+
+/// In-memory feed answering aggregator queries from its rounds. Like the flux
+/// aggregator, the latest round is the one with the highest ID and is empty if there
+/// are no rounds
+#[derive(Clone, Debug, PartialEq)]
+pub struct MockFeed {
+    pub decimals: u8,
+    pub description: String,
+    pub version: Uint128,
+    pub rounds: BTreeMap<u64, RoundDataResponse>,
+}
+
+impl MockFeed {
+    pub fn new(decimals: u8, description: impl Into<String>) -> Self {
+        MockFeed {
+            decimals,
+            description: description.into(),
+            version: Uint128::zero(),
+            rounds: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a round with the given answer after the latest round, started and updated
+    /// at `updated_at`
+    pub fn with_answer(mut self, answer: u128, updated_at: u64) -> Self {
+        self.push_answer(answer, updated_at);
+        self
+    }
+
+    /// Adds or replaces a round
+    pub fn with_round(mut self, round: RoundDataResponse) -> Self {
+        self.rounds.insert(round.round_id, round);
+        self
+    }
+
+    pub fn push_answer(&mut self, answer: u128, updated_at: u64) {
+        let round_id = self.latest_round_id() + 1;
+        self.rounds.insert(
+            round_id,
+            RoundDataResponse {
+                round_id,
+                answer: Some(Uint128::new(answer)),
+                started_at: Some(updated_at),
+                updated_at: Some(updated_at),
+                answered_in_round: round_id,
+            },
+        );
+    }
+
+    fn latest_round_id(&self) -> u64 {
+        self.rounds.keys().next_back().copied().unwrap_or_default()
+    }
+
+    fn latest_round_data(&self) -> RoundDataResponse {
+        match self.rounds.values().next_back() {
+            Some(round) => round.clone(),
+            None => RoundDataResponse {
+                round_id: 0,
+                answer: None,
+                started_at: None,
+                updated_at: None,
+                answered_in_round: 0,
+            },
+        }
+    }
+
+    pub fn query(&self, msg: &QueryMsg) -> StdResult<Binary> {
+        match msg {
+            QueryMsg::GetRoundData { round_id } => match self.rounds.get(round_id) {
+                Some(round) => to_binary(round),
+                None => Err(StdError::generic_err("No data present")),
+            },
+            QueryMsg::GetLatestRoundData {} => to_binary(&self.latest_round_data()),
+            QueryMsg::GetDecimals {} => to_binary(&self.decimals),
+            QueryMsg::GetDescription {} => to_binary(&self.description),
+            QueryMsg::GetVersion {} => to_binary(&self.version),
+            QueryMsg::GetLatestAnswer {} => {
+                to_binary(&LatestAnswerResponse(self.latest_round_data().answer))
+            }
+        }
+    }
+}
+
+/// Querier answering wrapped [`QueryMsg`] smart queries to the registered feeds, and
+/// passing every other query to the base `MockQuerier`
+pub struct MockFeedQuerier {
+    pub base: MockQuerier,
+    feeds: HashMap<String, MockFeed>,
+}
+
+impl MockFeedQuerier {
+    pub fn new(base: MockQuerier) -> Self {
+        MockFeedQuerier {
+            base,
+            feeds: HashMap::new(),
+        }
+    }
+
+    /// Sets the feed at the given address and returns the previous one
+    pub fn update_feed(&mut self, addr: impl Into<String>, feed: MockFeed) -> Option<MockFeed> {
+        self.feeds.insert(addr.into(), feed)
+    }
+
+    pub fn feed_mut(&mut self, addr: &str) -> Option<&mut MockFeed> {
+        self.feeds.get_mut(addr)
+    }
+
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                match self.feeds.get(contract_addr) {
+                    Some(feed) => handle_feed_query(feed, msg),
+                    None => self.base.handle_query(request),
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+}
+
+fn handle_feed_query(feed: &MockFeed, msg: &Binary) -> QuerierResult {
+    match from_slice::<AggregatorQuery>(msg) {
+        Ok(query) => SystemResult::Ok(ContractResult::from(feed.query(&query.aggregator_query))),
+        Err(e) => SystemResult::Err(SystemError::InvalidRequest {
+            error: format!("Parsing aggregator query: {}", e),
+            request: msg.clone(),
+        }),
+    }
+}
+
+impl Querier for MockFeedQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+/// Like `mock_dependencies`, with the given feeds answering at their addresses
+pub fn mock_dependencies_with_feeds(
+    feeds: Vec<(&str, MockFeed)>,
+) -> OwnedDeps<MockStorage, MockApi, MockFeedQuerier> {
+    let mut querier = MockFeedQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, &[])]));
+    for (addr, feed) in feeds {
+        querier.update_feed(addr, feed);
+    }
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AggregatorContract, FeedQuerier, Price};
+    use cosmwasm_std::{Addr, QuerierWrapper};
+
+    #[test]
+    fn answers_feed_queries() {
+        let feed = MockFeed::new(8, "LUNA / USD")
+            .with_answer(100, 10)
+            .with_answer(200, 20);
+        let deps =
+            mock_dependencies_with_feeds(vec![("feed", feed), ("empty", MockFeed::new(6, ""))]);
+        let querier = QuerierWrapper::new(&deps.querier);
+
+        let contract = AggregatorContract(Addr::unchecked("feed"));
+        let latest = contract.latest_round_data(&querier).unwrap();
+        assert_eq!(latest.round_id, 2);
+        assert_eq!(latest.answer, Some(Uint128::new(200)));
+        assert_eq!(latest.updated_at, Some(20));
+        assert_eq!(
+            contract.round_data(&querier, 1).unwrap().answer,
+            Some(Uint128::new(100))
+        );
+        assert!(contract.round_data(&querier, 3).is_err());
+        assert_eq!(
+            contract.latest_answer(&querier).unwrap(),
+            Some(Uint128::new(200))
+        );
+        assert_eq!(contract.decimals(&querier).unwrap(), 8);
+        assert_eq!(contract.description(&querier).unwrap(), "LUNA / USD");
+        assert_eq!(contract.version(&querier).unwrap(), Uint128::zero());
+        assert_eq!(
+            contract.price(&querier, 25, 5).unwrap(),
+            Price::new(Uint128::new(200), 8)
+        );
+        assert!(querier.get_price(Addr::unchecked("feed"), 26, 5).is_err());
+
+        let empty = querier
+            .get_latest_round_data(Addr::unchecked("empty"))
+            .unwrap();
+        assert_eq!(empty.round_id, 0);
+        assert_eq!(empty.answer, None);
+
+        assert!(querier.get_decimals(Addr::unchecked("unknown")).is_err());
+    }
+}
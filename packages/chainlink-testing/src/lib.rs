@@ -0,0 +1,76 @@
+//! Helpers for testing contracts reading Chainlink feeds
+
+use cosmwasm_std::Empty;
+use cw_multi_test::{Contract, ContractWrapper};
+
+pub use mock_aggregator::msg::{
+    ExecuteMsg as MockAggregatorExecuteMsg, InstantiateMsg as MockAggregatorInstantiateMsg,
+};
+
+/// Mock aggregator answering `chainlink_aggregator::QueryMsg` queries with rounds,
+/// decimals and description set by its owner, see [`MockAggregatorExecuteMsg`]
+pub fn contract_mock_aggregator() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        mock_aggregator::contract::execute,
+        mock_aggregator::contract::instantiate,
+        mock_aggregator::contract::query,
+    );
+    Box::new(contract)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chainlink_aggregator::FeedQuerier;
+    use cosmwasm_std::{
+        testing::{mock_env, MockApi, MockStorage},
+        Addr, Uint128,
+    };
+    use cw_multi_test::{App, BankKeeper, Executor};
+
+    #[test]
+    fn mock_aggregator() {
+        let env = mock_env();
+        let mut app = App::new(
+            MockApi::default(),
+            env.block,
+            BankKeeper::new(),
+            MockStorage::new(),
+        );
+        let owner = Addr::unchecked("owner");
+
+        let id = app.store_code(contract_mock_aggregator());
+        let aggregator = app
+            .instantiate_contract(
+                id,
+                owner.clone(),
+                &MockAggregatorInstantiateMsg {
+                    decimals: 8,
+                    description: "LUNA / USD".to_owned(),
+                    initial_answer: Some(Uint128::new(100)),
+                },
+                &[],
+                "Mock Aggregator",
+                None,
+            )
+            .unwrap();
+        app.execute_contract(
+            owner,
+            aggregator.clone(),
+            &MockAggregatorExecuteMsg::UpdateAnswer {
+                answer: Uint128::new(200),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let querier = app.wrap();
+        let res = querier.get_latest_round_data(aggregator.clone()).unwrap();
+        assert_eq!(res.round_id, 2);
+        assert_eq!(res.answer, Some(Uint128::new(200)));
+        let res = querier.get_round_data(aggregator.clone(), 1).unwrap();
+        assert_eq!(res.answer, Some(Uint128::new(100)));
+        assert_eq!(querier.get_decimals(aggregator.clone()).unwrap(), 8);
+        assert_eq!(querier.get_description(aggregator).unwrap(), "LUNA / USD");
+    }
+}
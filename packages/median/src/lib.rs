@@ -1,26 +1,114 @@
-use num_traits::{cast, PrimInt};
+use cosmwasm_std::{Uint128, Uint256, Uint512, Uint64};
+
+pub mod stats;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct EmptyArrayError;
 
+/// Integers a median can be calculated of
+pub trait Median: Ord + Copy {
+    /// Mean of two values rounded towards zero, like `(a + b) / 2` without overflowing
+    fn midpoint(a: Self, b: Self) -> Self;
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ty),*) => {$(
+        impl Median for $t {
+            fn midpoint(a: Self, b: Self) -> Self {
+                // common bits plus half of the differing ones
+                (a & b) + ((a ^ b) >> 1)
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_signed {
+    ($($t:ty),*) => {$(
+        impl Median for $t {
+            fn midpoint(a: Self, b: Self) -> Self {
+                // arithmetic shift rounds towards negative infinity
+                let floor = (a & b) + ((a ^ b) >> 1);
+                if floor < 0 && (a ^ b) & 1 == 1 {
+                    floor + 1
+                } else {
+                    floor
+                }
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_cosmwasm_uint {
+    ($($t:ty),*) => {$(
+        impl Median for $t {
+            fn midpoint(a: Self, b: Self) -> Self {
+                let is_odd = |x: Self| (x >> 1) + (x >> 1) != x;
+                let carry = if is_odd(a) && is_odd(b) { 1u8 } else { 0u8 };
+                (a >> 1) + (b >> 1) + <$t>::from(carry)
+            }
+        }
+    )*};
+}
+
+impl_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_signed!(i8, i16, i32, i64, i128, isize);
+impl_cosmwasm_uint!(Uint64, Uint128, Uint256, Uint512);
+
+/// Median of the entries, the mean of the two middle ones rounded towards zero for an even
+/// count. Runs in linear time, leaving the entries partially reordered
 pub fn calculate_median<T>(entries: &mut [T]) -> Result<T, EmptyArrayError>
 where
-    T: PrimInt,
+    T: Median,
 {
     if entries.is_empty() {
         return Err(EmptyArrayError);
     }
-    entries.sort_unstable();
 
-    let mid = entries.len() / 2;
-    let median = match entries.len() % 2 {
-        0 => (entries[mid - 1] + entries[mid]) / cast(2).unwrap(),
-        _ => entries[mid],
+    let len = entries.len();
+    let mid = len / 2;
+    let (lower, upper, _) = entries.select_nth_unstable(mid);
+    let upper = *upper;
+    let median = match lower.iter().max() {
+        Some(&lower) if mid * 2 == len => T::midpoint(lower, upper),
+        _ => upper,
     };
 
     Ok(median)
 }
 
+/// Median of the values, each counting `weight` times. Like [`calculate_median`], the mean of
+/// the values on both sides is taken when exactly half of the weight lies below one. Entries
+/// get sorted, ones without weight are ignored
+pub fn weighted_median<T>(entries: &mut [(T, u64)]) -> Result<T, EmptyArrayError>
+where
+    T: Median,
+{
+    let total: u128 = entries.iter().map(|&(_, weight)| u128::from(weight)).sum();
+    if total == 0 {
+        return Err(EmptyArrayError);
+    }
+
+    entries.sort_unstable_by_key(|&(value, _)| value);
+    let mut cumulative = 0u128;
+    for (index, &(value, weight)) in entries.iter().enumerate() {
+        cumulative += u128::from(weight);
+        if cumulative * 2 > total {
+            return Ok(value);
+        }
+        if cumulative * 2 == total {
+            // the remaining half has weight, so there is a next value
+            let next = entries[index + 1..]
+                .iter()
+                .find(|&&(_, weight)| weight > 0)
+                .map(|&(next, _)| next)
+                .unwrap_or(value);
+            return Ok(T::midpoint(value, next));
+        }
+    }
+
+    Err(EmptyArrayError)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -41,4 +129,246 @@ mod tests {
         let median_err = calculate_median::<u32>(&mut vec![]).unwrap_err();
         assert_eq!(median_err, EmptyArrayError);
     }
+
+    #[test]
+    fn test_calculate_median_no_overflow() {
+        let mut entries = vec![u128::MAX, u128::MAX - 2];
+        assert_eq!(calculate_median(&mut entries).unwrap(), u128::MAX - 1);
+
+        let mut entries = vec![Uint128::MAX, Uint128::MAX];
+        assert_eq!(calculate_median(&mut entries).unwrap(), Uint128::MAX);
+
+        let max = Uint256::from(u128::MAX) * Uint256::from(u128::MAX);
+        let mut entries = vec![max, max - Uint256::from(2u8)];
+        assert_eq!(
+            calculate_median(&mut entries).unwrap(),
+            max - Uint256::from(1u8)
+        );
+
+        let mut entries = vec![i128::MIN, i128::MIN + 1];
+        assert_eq!(calculate_median(&mut entries).unwrap(), i128::MIN + 1);
+    }
+
+    #[test]
+    fn test_calculate_median_signed() {
+        let mut entries = vec![-5_i64, 2, -1, 8];
+        assert_eq!(calculate_median(&mut entries).unwrap(), 0);
+        // rounds towards zero like `(a + b) / 2`
+        let mut entries = vec![-2_i32, -1];
+        assert_eq!(calculate_median(&mut entries).unwrap(), -1);
+        let mut entries = vec![-1_i32, 2];
+        assert_eq!(calculate_median(&mut entries).unwrap(), 0);
+        let mut entries = vec![i8::MIN, i8::MAX];
+        assert_eq!(calculate_median(&mut entries).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_weighted_median() {
+        let mut entries = vec![(1_u128, 1), (3, 1), (2, 5), (100, 3)];
+        assert_eq!(weighted_median(&mut entries).unwrap(), 2);
+
+        // heavier values pull the median towards them
+        let mut entries = vec![(10_u32, 1), (20, 1), (30, 1), (40, 4)];
+        assert_eq!(weighted_median(&mut entries).unwrap(), 40);
+
+        // exactly half of the weight on each side
+        let mut entries = vec![
+            (Uint128::new(10), 2),
+            (Uint128::new(21), 2),
+            (Uint128::new(5), 1),
+            (Uint128::new(30), 1),
+        ];
+        assert_eq!(weighted_median(&mut entries).unwrap(), Uint128::new(15));
+        let mut entries = vec![(-4_i64, 2), (7, 0), (-1, 2)];
+        assert_eq!(weighted_median(&mut entries).unwrap(), -2);
+
+        let mut entries = vec![(u64::MAX, u64::MAX), (u64::MAX - 2, u64::MAX)];
+        assert_eq!(weighted_median(&mut entries).unwrap(), u64::MAX - 1);
+    }
+
+    #[test]
+    fn test_weighted_median_no_weight() {
+        assert_eq!(
+            weighted_median::<u32>(&mut []).unwrap_err(),
+            EmptyArrayError
+        );
+        assert_eq!(
+            weighted_median(&mut [(1_u32, 0), (2, 0)]).unwrap_err(),
+            EmptyArrayError
+        );
+    }
+
+    mod properties {
+        use super::*;
+
+        /// xorshift64*, enough to spread test cases without pulling in a crate
+        struct Rng(u64);
+
+        impl Rng {
+            fn next(&mut self) -> u64 {
+                self.0 ^= self.0 >> 12;
+                self.0 ^= self.0 << 25;
+                self.0 ^= self.0 >> 27;
+                self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+            }
+
+            fn next_u128(&mut self) -> u128 {
+                (self.next() as u128) << 64 | self.next() as u128
+            }
+
+            /// Mostly random values, with extremes and duplicates mixed in
+            fn entries(&mut self) -> Vec<u128> {
+                let len = 1 + (self.next() % 40) as usize;
+                let mut entries = Vec::with_capacity(len);
+                for _ in 0..len {
+                    let entry = match self.next() % 8 {
+                        0 => 0,
+                        1 => u128::MAX,
+                        2 => u128::MAX / 2 + 1,
+                        3 if !entries.is_empty() => entries[self.next() as usize % entries.len()],
+                        _ => self.next_u128(),
+                    };
+                    entries.push(entry);
+                }
+                entries
+            }
+        }
+
+        /// Full sort followed by the mean of the middle entries in a wider type
+        fn reference<T: Ord + Copy, W>(
+            entries: &[T],
+            widen: impl Fn(T) -> W,
+            mean: impl Fn(W, W) -> T,
+        ) -> T {
+            let mut sorted = entries.to_vec();
+            sorted.sort_unstable();
+            let mid = sorted.len() / 2;
+            if mid * 2 == sorted.len() {
+                mean(widen(sorted[mid - 1]), widen(sorted[mid]))
+            } else {
+                sorted[mid]
+            }
+        }
+
+        fn check<T: Median + std::fmt::Debug, W>(
+            cases: impl Fn(Vec<u128>) -> Vec<T>,
+            widen: impl Fn(T) -> W + Copy,
+            mean: impl Fn(W, W) -> T + Copy,
+        ) {
+            let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+            for _ in 0..2000 {
+                let mut entries = cases(rng.entries());
+                let expected = reference(&entries, widen, mean);
+                assert_eq!(
+                    calculate_median(&mut entries).unwrap(),
+                    expected,
+                    "{:?}",
+                    entries
+                );
+            }
+        }
+
+        #[test]
+        fn matches_reference_for_small_types() {
+            check(
+                |e| e.into_iter().map(|x| x as u8).collect(),
+                i32::from,
+                |a, b| ((a + b) / 2) as u8,
+            );
+            check(
+                |e| e.into_iter().map(|x| x as i8).collect(),
+                i32::from,
+                |a, b| ((a + b) / 2) as i8,
+            );
+        }
+
+        #[test]
+        fn matches_reference_for_64_bits() {
+            check(
+                |e| e.into_iter().map(|x| x as u64).collect(),
+                i128::from,
+                |a, b| ((a + b) / 2) as u64,
+            );
+            check(
+                |e| e.into_iter().map(|x| x as i64).collect(),
+                i128::from,
+                |a, b| ((a + b) / 2) as i64,
+            );
+            check(
+                |e| e.into_iter().map(|x| Uint64::new(x as u64)).collect(),
+                |x: Uint64| i128::from(x.u64()),
+                |a, b| Uint64::new(((a + b) / 2) as u64),
+            );
+        }
+
+        #[test]
+        fn matches_reference_for_128_bits() {
+            let mean = |a: Uint256, b: Uint256| {
+                let mean = (a + b) / Uint256::from(2u8);
+                mean.to_string().parse::<u128>().unwrap()
+            };
+            check(|e| e, Uint256::from, mean);
+            check(
+                |e| e.into_iter().map(Uint128::new).collect(),
+                |x: Uint128| Uint256::from(x.u128()),
+                move |a, b| Uint128::new(mean(a, b)),
+            );
+            // no wider signed type, the sum only overflows for equal signs
+            check(
+                |e| e.into_iter().map(|x| x as i128).collect(),
+                |x| x,
+                |a: i128, b: i128| match a.checked_add(b) {
+                    Some(sum) => sum / 2,
+                    None => a / 2 + b / 2 + (a % 2 + b % 2) / 2,
+                },
+            );
+        }
+
+        #[test]
+        fn matches_reference_for_256_bits() {
+            let to_512 = |x: Uint256| Uint512::from_le_bytes(pad(x.to_le_bytes()));
+            check(
+                |e| {
+                    e.into_iter()
+                        .map(|x| Uint256::from(x) * Uint256::from(x % 7 + 1))
+                        .collect()
+                },
+                to_512,
+                |a, b| {
+                    let mean = ((a + b) / Uint512::from(2u8)).to_le_bytes();
+                    let mut bytes = [0u8; 32];
+                    bytes.copy_from_slice(&mean[..32]);
+                    Uint256::from_le_bytes(bytes)
+                },
+            );
+        }
+
+        #[test]
+        fn weighted_matches_repeated_entries() {
+            let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+            for _ in 0..2000 {
+                let mut entries = rng
+                    .entries()
+                    .into_iter()
+                    .map(|value| (value as i64, rng.next() % 4))
+                    .collect::<Vec<_>>();
+                let mut repeated = entries
+                    .iter()
+                    .flat_map(|&(value, weight)| vec![value; weight as usize])
+                    .collect::<Vec<_>>();
+                assert_eq!(
+                    weighted_median(&mut entries).ok(),
+                    calculate_median(&mut repeated).ok(),
+                    "{:?}",
+                    entries
+                );
+            }
+        }
+
+        fn pad(bytes: [u8; 32]) -> [u8; 64] {
+            let mut padded = [0u8; 64];
+            padded[..32].copy_from_slice(&bytes);
+            padded
+        }
+    }
 }
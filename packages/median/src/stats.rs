@@ -0,0 +1,262 @@
+use std::convert::TryFrom;
+
+use cosmwasm_std::{Uint128, Uint256, Uint512, Uint64};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{calculate_median, EmptyArrayError, Median};
+
+/// Integers whose spread can be measured
+pub trait Deviation: Median + Default {
+    /// Distance between two values, saturating at the maximum for signed types
+    fn abs_diff(a: Self, b: Self) -> Self;
+    /// `self * numerator / denominator`, `None` on overflow or a zero denominator
+    fn checked_scale(self, numerator: u32, denominator: u32) -> Option<Self>;
+}
+
+macro_rules! impl_primitive {
+    ($wide:ty; $($t:ty),*) => {$(
+        impl Deviation for $t {
+            fn abs_diff(a: Self, b: Self) -> Self {
+                if a > b {
+                    a.saturating_sub(b)
+                } else {
+                    b.saturating_sub(a)
+                }
+            }
+
+            fn checked_scale(self, numerator: u32, denominator: u32) -> Option<Self> {
+                let scaled = (self as $wide)
+                    .checked_mul(numerator as $wide)?
+                    .checked_div(denominator as $wide)?;
+                Self::try_from(scaled).ok()
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_cosmwasm_uint {
+    ($($t:ty),*) => {$(
+        impl Deviation for $t {
+            fn abs_diff(a: Self, b: Self) -> Self {
+                if a > b {
+                    a.saturating_sub(b)
+                } else {
+                    b.saturating_sub(a)
+                }
+            }
+
+            fn checked_scale(self, numerator: u32, denominator: u32) -> Option<Self> {
+                self.checked_mul(numerator.into())
+                    .ok()?
+                    .checked_div(denominator.into())
+                    .ok()
+            }
+        }
+    )*};
+}
+
+impl_primitive!(u128; u8, u16, u32, u64, u128, usize);
+impl_primitive!(i128; i8, i16, i32, i64, i128, isize);
+impl_cosmwasm_uint!(Uint64, Uint128, Uint256, Uint512);
+
+/// Median of the distances of the entries from their median
+pub fn median_absolute_deviation<T: Deviation>(entries: &[T]) -> Result<T, EmptyArrayError> {
+    calculate_median(&mut deviations(entries)?)
+}
+
+/// Nearest-rank percentile, the smallest entry that at least `percent` percent of the entries
+/// don't exceed. `percent` is capped at 100. Leaves the entries partially reordered
+pub fn percentile<T: Median>(entries: &mut [T], percent: u8) -> Result<T, EmptyArrayError> {
+    if entries.is_empty() {
+        return Err(EmptyArrayError);
+    }
+
+    // zero-based index of rank `ceil(percent * len / 100)`
+    let index = (usize::from(percent.min(100)) * entries.len()).saturating_sub(1) / 100;
+    let (_, entry, _) = entries.select_nth_unstable(index);
+    Ok(*entry)
+}
+
+/// Distance between the 25th and the 75th percentile
+pub fn interquartile_range<T: Deviation>(entries: &mut [T]) -> Result<T, EmptyArrayError> {
+    let lower = percentile(entries, 25)?;
+    let upper = percentile(entries, 75)?;
+    Ok(T::abs_diff(upper, lower))
+}
+
+/// Basis points of the median tolerated by filters stored before the tolerance was introduced
+pub const DEFAULT_TOLERANCE_BPS: u32 = 10;
+
+/// Discards entries deviating from the median by more than `k` median absolute deviations,
+/// where `k = numerator / denominator`
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+pub struct OutlierFilter {
+    pub numerator: u32,
+    pub denominator: u32,
+    /// Deviations up to this many basis points of the median are never outliers, so entries
+    /// agreeing all but exactly don't exclude the rest
+    #[serde(default = "default_tolerance_bps")]
+    pub tolerance_bps: u32,
+}
+
+fn default_tolerance_bps() -> u32 {
+    DEFAULT_TOLERANCE_BPS
+}
+
+impl OutlierFilter {
+    /// Indices of the entries the filter discards, none if `k · MAD` or the tolerance overflow
+    pub fn outliers<T: Deviation>(&self, entries: &[T]) -> Result<Vec<usize>, EmptyArrayError> {
+        let median = calculate_median(&mut entries.to_vec())?;
+        let deviations = deviations(entries)?;
+        let mad = calculate_median(&mut deviations.clone())?;
+        let limit = match mad.checked_scale(self.numerator, self.denominator) {
+            Some(limit) => limit,
+            None => return Ok(vec![]),
+        };
+        let tolerance =
+            match T::abs_diff(median, T::default()).checked_scale(self.tolerance_bps, 10_000) {
+                Some(tolerance) => tolerance,
+                None => return Ok(vec![]),
+            };
+        let limit = limit.max(tolerance);
+
+        Ok(deviations
+            .into_iter()
+            .enumerate()
+            .filter(|(_, deviation)| *deviation > limit)
+            .map(|(index, _)| index)
+            .collect())
+    }
+}
+
+fn deviations<T: Deviation>(entries: &[T]) -> Result<Vec<T>, EmptyArrayError> {
+    let median = calculate_median(&mut entries.to_vec())?;
+    Ok(entries
+        .iter()
+        .map(|&entry| T::abs_diff(entry, median))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_median_absolute_deviation() {
+        // deviations from 2 are [1, 1, 0, 0, 2, 4, 7]
+        let entries = [1_u32, 1, 2, 2, 4, 6, 9];
+        assert_eq!(median_absolute_deviation(&entries).unwrap(), 1);
+
+        let entries = [-3_i64, 3];
+        assert_eq!(median_absolute_deviation(&entries).unwrap(), 3);
+
+        let entries = [Uint128::zero(), Uint128::MAX];
+        assert_eq!(
+            median_absolute_deviation(&entries).unwrap(),
+            Uint128::MAX / Uint128::new(2)
+        );
+
+        assert_eq!(
+            median_absolute_deviation::<u8>(&[]).unwrap_err(),
+            EmptyArrayError
+        );
+    }
+
+    #[test]
+    fn test_percentile() {
+        let mut entries = [15_u32, 20, 35, 40, 50];
+        assert_eq!(percentile(&mut entries, 0).unwrap(), 15);
+        assert_eq!(percentile(&mut entries, 5).unwrap(), 15);
+        assert_eq!(percentile(&mut entries, 30).unwrap(), 20);
+        assert_eq!(percentile(&mut entries, 40).unwrap(), 20);
+        assert_eq!(percentile(&mut entries, 50).unwrap(), 35);
+        assert_eq!(percentile(&mut entries, 100).unwrap(), 50);
+        assert_eq!(percentile(&mut entries, 255).unwrap(), 50);
+
+        assert_eq!(percentile::<u8>(&mut [], 50).unwrap_err(), EmptyArrayError);
+    }
+
+    #[test]
+    fn test_interquartile_range() {
+        let mut entries = [7_i32, -3, 1, 5, 20, 8, 2, 4];
+        // quartiles are 1 and 7
+        assert_eq!(interquartile_range(&mut entries).unwrap(), 6);
+
+        let mut entries = [Uint256::from(3u8)];
+        assert_eq!(interquartile_range(&mut entries).unwrap(), Uint256::zero());
+    }
+
+    #[test]
+    fn test_outliers() {
+        let filter = OutlierFilter {
+            numerator: 3,
+            denominator: 1,
+            tolerance_bps: 0,
+        };
+        // median 100, MAD 2
+        let entries = [100_u128, 98, 102, 250, 101, 99, 10];
+        assert_eq!(filter.outliers(&entries).unwrap(), vec![3, 6]);
+
+        // median -10, MAD 2 and k = 2.5 keep deviations up to 5
+        let filter = OutlierFilter {
+            numerator: 5,
+            denominator: 2,
+            tolerance_bps: 0,
+        };
+        let entries = [-10_i8, -12, -8, -11, -9, -15, -5];
+        assert!(filter.outliers(&entries).unwrap().is_empty());
+        let entries = [-10_i8, -12, -8, -11, -9, -16, -4];
+        assert_eq!(filter.outliers(&entries).unwrap(), vec![5, 6]);
+
+        // without any spread or tolerance, every deviation is an outlier
+        let entries = [Uint64::new(7), Uint64::new(7), Uint64::new(8)];
+        assert_eq!(filter.outliers(&entries).unwrap(), vec![2]);
+
+        assert_eq!(filter.outliers::<u32>(&[]).unwrap_err(), EmptyArrayError);
+    }
+
+    #[test]
+    fn test_outliers_within_tolerance() {
+        // 10 basis points of the median 1000 tolerate deviations up to 1
+        let filter = OutlierFilter {
+            numerator: 3,
+            denominator: 1,
+            tolerance_bps: 10,
+        };
+        let entries = [1000_u64, 1000, 1001];
+        assert_eq!(median_absolute_deviation(&entries).unwrap(), 0);
+        assert!(filter.outliers(&entries).unwrap().is_empty());
+        let entries = [Uint128::new(1000), Uint128::new(1000), Uint128::new(1002)];
+        assert_eq!(filter.outliers(&entries).unwrap(), vec![2]);
+
+        // relative to the median's distance from zero
+        let entries = [-1000_i32, -1000, -999, -1003];
+        assert_eq!(filter.outliers(&entries).unwrap(), vec![3]);
+    }
+
+    #[test]
+    fn test_outliers_overflowing_limit() {
+        let filter = OutlierFilter {
+            numerator: u32::MAX,
+            denominator: 1,
+            tolerance_bps: 0,
+        };
+        let entries = [0_u8, 100, 200];
+        assert!(filter.outliers(&entries).unwrap().is_empty());
+
+        let filter = OutlierFilter {
+            numerator: 1,
+            denominator: 0,
+            tolerance_bps: 0,
+        };
+        assert!(filter.outliers(&entries).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_saturating_abs_diff() {
+        assert_eq!(Deviation::abs_diff(i8::MIN, i8::MAX), i8::MAX);
+        assert_eq!(Deviation::abs_diff(-3, 4), 7);
+        assert_eq!(Deviation::abs_diff(0, u128::MAX), u128::MAX);
+    }
+}